@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use alloc::vec::Vec;
 
 /// Algorithm configurations. A trait for preparing the algorithm.
 ///
@@ -14,6 +15,20 @@ pub trait AlgCfg {
     fn pop_num() -> usize {
         200
     }
+    /// Minimum population number required by the algorithm, see
+    /// [`SolverBuilder::pop_num_factor()`].
+    fn min_pop_num() -> usize {
+        1
+    }
+    /// Default maximum generation, decided by the population number and the
+    /// dimension.
+    ///
+    /// This value is the default termination condition, and the reference of
+    /// [`Ctx::progress()`].
+    #[allow(unused_variables)]
+    fn max_gen(pop_num: usize, dim: usize) -> u64 {
+        200
+    }
 }
 
 /// The methods of the metaheuristic algorithms.
@@ -86,6 +101,38 @@ pub trait Algorithm<F: ObjFunc>: MaybeParallel {
 
     /// Processing implementation of each generation.
     fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng);
+
+    /// Name of the method, used for reporting.
+    ///
+    /// The default value is the type name.
+    fn name(&self) -> &'static str {
+        core::any::type_name::<Self>()
+    }
+
+    /// Hyperparameters of the method, used for reporting.
+    ///
+    /// The default value is empty.
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        Vec::new()
+    }
+
+    /// The (maximum) number of the objective function evaluations in each
+    /// generation for the population number `pop_num`, used for converting
+    /// the generation budgets to the evaluation budgets.
+    ///
+    /// The default value is `pop_num`, one evaluation per individual.
+    fn evals_per_gen(&self, pop_num: usize) -> usize {
+        pop_num
+    }
+
+    /// Return true if the method keeps no state between the generations
+    /// other than the [`Ctx`], so it can be resumed from a [`SolverState`] by
+    /// [`SolverBuilder::resume_from()`].
+    ///
+    /// The default value is false.
+    fn is_stateless(&self) -> bool {
+        false
+    }
 }
 
 /// Implement for `Box<dyn Algorithm<F>>`.
@@ -101,4 +148,24 @@ impl<F: ObjFunc, T: Algorithm<F> + ?Sized> Algorithm<F> for alloc::boxed::Box<T>
     fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
         self.as_mut().generation(ctx, rng);
     }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        self.as_ref().name()
+    }
+
+    #[inline]
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        self.as_ref().params()
+    }
+
+    #[inline]
+    fn evals_per_gen(&self, pop_num: usize) -> usize {
+        self.as_ref().evals_per_gen(pop_num)
+    }
+
+    #[inline]
+    fn is_stateless(&self) -> bool {
+        self.as_ref().is_stateless()
+    }
 }
@@ -0,0 +1,75 @@
+use crate::prelude::*;
+use alloc::vec::Vec;
+use argmin::core::CostFunction;
+
+/// An adapter of the `argmin` cost function, requires the `argmin` feature.
+///
+/// The cost function of `argmin` has no bounds, so the bounds are supplied
+/// separately. The error of the cost function is mapped to the worst fitness
+/// value (positive infinity), like [`FxResult`], so the cost is only
+/// minimized and [`SolverBuilder::maximize()`] is rejected.
+///
+/// ```
+/// use argmin::core::{CostFunction, Error};
+/// use metaheuristics_nature::{ArgminObj, De, Solver};
+///
+/// struct Sphere;
+///
+/// impl CostFunction for Sphere {
+///     type Param = Vec<f64>;
+///     type Output = f64;
+///
+///     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+///         Ok(p.iter().map(|x| x * x).sum())
+///     }
+/// }
+///
+/// let f = ArgminObj::new(Sphere, vec![[-10., 10.]; 3]);
+/// let s = Solver::build(De::default(), f)
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .solve();
+/// ```
+pub struct ArgminObj<P> {
+    problem: P,
+    bound: Vec<[f64; 2]>,
+}
+
+impl<P> ArgminObj<P> {
+    /// Create the objective function from a cost function and its bounds.
+    pub fn new(problem: P, bound: Vec<[f64; 2]>) -> Self {
+        Self { problem, bound }
+    }
+
+    /// Get the reference of the cost function.
+    pub fn problem(&self) -> &P {
+        &self.problem
+    }
+
+    /// Consume and get the cost function.
+    pub fn into_problem(self) -> P {
+        self.problem
+    }
+}
+
+impl<P: MaybeParallel> Bounded for ArgminObj<P> {
+    #[inline]
+    fn bound(&self) -> &[[f64; 2]] {
+        &self.bound
+    }
+}
+
+impl<P> ObjFunc for ArgminObj<P>
+where
+    P: CostFunction<Param = Vec<f64>, Output = f64> + MaybeParallel,
+{
+    type Ys = f64;
+
+    fn fitness(&self, xs: &[f64]) -> Self::Ys {
+        self.problem.cost(&xs.to_vec()).unwrap_or(f64::INFINITY)
+    }
+
+    fn is_minimize_only(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,61 @@
+//! Exporting the recorded data to the Parquet format, see
+//! [`Solver::write_parquet()`].
+use crate::prelude::*;
+use alloc::sync::Arc;
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, UInt64Array};
+use arrow_schema::{DataType, Field, Schema};
+use parquet::{arrow::ArrowWriter, errors::Result};
+
+impl<F: ObjFunc> Solver<F> {
+    /// Write the best history to the Parquet format as a columnar table,
+    /// which can be loaded by `pandas.read_parquet()` and
+    /// `polars.read_parquet()` directly.
+    ///
+    /// Each row is an improvement of the best, see
+    /// [`SolverBuilder::record_best()`]. The columns are:
+    /// + `gen`: The generation (`UInt64`).
+    /// + `eval_count`: The number of the evaluations (`UInt64`).
+    /// + `best_fitness`: The evaluation value of the best (`Float64`).
+    /// + `x0`, `x1`, ...: The best parameters of each dimension (`Float64`).
+    ///
+    /// The table has no row if the best history is not recorded.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .record_best(true)
+    ///     .solve();
+    /// let mut buf = Vec::new();
+    /// s.write_parquet(&mut buf).unwrap();
+    /// ```
+    pub fn write_parquet(&self, writer: impl std::io::Write + Send) -> Result<()>
+    where
+        <F::Ys as Fitness>::Eval: Into<f64>,
+    {
+        let history = &self.best_history;
+        let mut fields = alloc::vec![
+            Field::new("gen", DataType::UInt64, false),
+            Field::new("eval_count", DataType::UInt64, false),
+            Field::new("best_fitness", DataType::Float64, false),
+        ];
+        let gen = UInt64Array::from_iter_values(history.iter().map(|([gen, _], ..)| *gen));
+        let evals = UInt64Array::from_iter_values(history.iter().map(|([_, evals], ..)| *evals));
+        let best = Float64Array::from_iter_values(history.iter().map(|(.., ys)| ys.eval().into()));
+        let mut columns: Vec<ArrayRef> =
+            alloc::vec![Arc::new(gen), Arc::new(evals), Arc::new(best)];
+        for s in 0..self.ctx.dim() {
+            fields.push(Field::new(format!("x{s}"), DataType::Float64, false));
+            let xs = Float64Array::from_iter_values(history.iter().map(|(_, xs, _)| xs[s]));
+            columns.push(Arc::new(xs));
+        }
+        let batch = RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)?;
+        let mut writer = ArrowWriter::try_new(writer, batch.schema(), None)?;
+        writer.write(&batch)?;
+        writer.close()?;
+        Ok(())
+    }
+}
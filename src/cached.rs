@@ -0,0 +1,217 @@
+use crate::prelude::*;
+use alloc::{sync::Arc, vec::Vec};
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::{collections::HashMap, sync::Mutex};
+
+type Cache<Y> = Mutex<HashMap<Vec<u64>, Y>>;
+
+/// A thread-safe cache of the objective function, which can be shared by
+/// multiple solvers.
+///
+/// The clones share the same objective function and the same cache, so the
+/// evaluations of a solver are reused by the others, such as a parameter
+/// sweep or the runs of different methods on an expensive objective
+/// function. The variables are matched exactly by their bits.
+///
+/// The other methods of the objective function are forwarded. The partial
+/// evaluations ([`ObjFunc::fitness_partial()`]) are cached unless they are
+/// aborted, and the gradients are not cached.
+///
+/// ```
+/// use metaheuristics_nature::{Cached, De, Pso, Solver};
+/// # use metaheuristics_nature::tests::TestObj as MyFunc;
+///
+/// let f = Cached::new(MyFunc::new());
+/// let s1 = Solver::build(De::default(), f.clone())
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .solve();
+/// let s2 = Solver::build(Pso::default(), f.clone())
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .solve();
+/// println!("{} evaluations, {} hits", f.evals(), f.hits());
+/// ```
+pub struct Cached<F: ObjFunc> {
+    func: Arc<F>,
+    cache: Arc<Cache<F::Ys>>,
+    evals: Arc<AtomicU64>,
+    hits: Arc<AtomicU64>,
+}
+
+impl<F: ObjFunc> Clone for Cached<F> {
+    fn clone(&self) -> Self {
+        Self {
+            func: self.func.clone(),
+            cache: self.cache.clone(),
+            evals: self.evals.clone(),
+            hits: self.hits.clone(),
+        }
+    }
+}
+
+impl<F: ObjFunc> Cached<F> {
+    /// Create a cache of the objective function.
+    pub fn new(func: F) -> Self {
+        Self {
+            func: Arc::new(func),
+            cache: Default::default(),
+            evals: Default::default(),
+            hits: Default::default(),
+        }
+    }
+
+    /// Get the reference of the objective function.
+    pub fn func(&self) -> &F {
+        &self.func
+    }
+
+    /// Get the number of the true evaluations of the objective function.
+    pub fn evals(&self) -> u64 {
+        self.evals.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of the evaluations answered by the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of the cached variables.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Return true if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Clear the cache, the counters are kept.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    // Get the cached value and count the hit
+    fn get(&self, key: &[u64]) -> Option<F::Ys> {
+        let ys = self.lock().get(key).cloned();
+        if ys.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        }
+        ys
+    }
+
+    // Insert the evaluated value and count the evaluation
+    fn insert(&self, key: Vec<u64>, ys: &F::Ys) {
+        self.evals.fetch_add(1, Ordering::Relaxed);
+        self.lock().insert(key, ys.clone());
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<Vec<u64>, F::Ys>> {
+        (self.cache.lock()).unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl<F: ObjFunc> Bounded for Cached<F> {
+    #[inline]
+    fn bound(&self) -> &[[f64; 2]] {
+        self.func.bound()
+    }
+
+    #[inline]
+    fn dim(&self) -> usize {
+        self.func.dim()
+    }
+
+    #[inline]
+    fn bound_of(&self, s: usize) -> [f64; 2] {
+        self.func.bound_of(s)
+    }
+
+    #[inline]
+    fn bound_width(&self, s: usize) -> f64 {
+        self.func.bound_width(s)
+    }
+
+    #[inline]
+    fn bound_range(&self, s: usize) -> core::ops::RangeInclusive<f64> {
+        self.func.bound_range(s)
+    }
+
+    #[inline]
+    fn lb(&self, s: usize) -> f64 {
+        self.func.lb(s)
+    }
+
+    #[inline]
+    fn ub(&self, s: usize) -> f64 {
+        self.func.ub(s)
+    }
+
+    #[inline]
+    fn clamp(&self, s: usize, v: f64) -> f64 {
+        self.func.clamp(s, v)
+    }
+}
+
+impl<F: ObjFunc> ObjFunc for Cached<F> {
+    type Ys = F::Ys;
+
+    fn fitness(&self, xs: &[f64]) -> Self::Ys {
+        let key = xs.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+        if let Some(ys) = self.get(&key) {
+            return ys;
+        }
+        // Evaluate without the lock, the other threads may evaluate the same
+        // variables concurrently
+        let ys = self.func.fitness(xs);
+        self.insert(key, &ys);
+        ys
+    }
+
+    fn fitness_partial(&self, xs: &[f64], is_dominated: &dyn Fn(&[f64]) -> bool) -> Self::Ys {
+        let key = xs.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+        if let Some(ys) = self.get(&key) {
+            return ys;
+        }
+        // The aborted result is not cached
+        let aborted = core::cell::Cell::new(false);
+        let ys = self.func.fitness_partial(xs, &|ys| {
+            let dominated = is_dominated(ys);
+            aborted.set(aborted.get() || dominated);
+            dominated
+        });
+        if aborted.get() {
+            self.evals.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.insert(key, &ys);
+        }
+        ys
+    }
+
+    fn gradient(&self, xs: &[f64]) -> Option<Vec<f64>> {
+        self.func.gradient(xs)
+    }
+
+    fn fitness_and_grad(&self, xs: &[f64]) -> (Self::Ys, Option<Vec<f64>>) {
+        // The gradient is not cached, so the joint evaluation is always run
+        let key = xs.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+        let (ys, grad) = self.func.fitness_and_grad(xs);
+        self.insert(key, &ys);
+        (ys, grad)
+    }
+
+    #[inline]
+    fn is_joint_grad(&self) -> bool {
+        self.func.is_joint_grad()
+    }
+
+    #[inline]
+    fn var_kind(&self, s: usize) -> VarKind {
+        self.func.var_kind(s)
+    }
+
+    #[inline]
+    fn is_minimize_only(&self) -> bool {
+        self.func.is_minimize_only()
+    }
+}
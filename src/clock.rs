@@ -0,0 +1,38 @@
+use core::time::Duration;
+
+/// A monotonic clock for the time-based termination, see
+/// [`SolverBuilder::clock()`] and [`SolverBuilder::time_limit()`].
+///
+/// [`SolverBuilder::clock()`]: crate::SolverBuilder::clock
+/// [`SolverBuilder::time_limit()`]: crate::SolverBuilder::time_limit
+///
+/// The `std` implementation is [`std::time::Instant`], which measures the
+/// elapsed time since the instant. The `no_std` targets can implement this
+/// trait with a hardware tick counter.
+///
+/// ```
+/// use core::time::Duration;
+/// use metaheuristics_nature::Clock;
+///
+/// /// A tick counter of 1 kHz.
+/// struct Ticks(fn() -> u64);
+///
+/// impl Clock for Ticks {
+///     fn now(&mut self) -> Duration {
+///         Duration::from_millis((self.0)())
+///     }
+/// }
+/// ```
+pub trait Clock {
+    /// Get the current time since an arbitrary fixed origin.
+    ///
+    /// The value should never decrease.
+    fn now(&mut self) -> Duration;
+}
+
+#[cfg(feature = "std")]
+impl Clock for std::time::Instant {
+    fn now(&mut self) -> Duration {
+        self.elapsed()
+    }
+}
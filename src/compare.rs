@@ -0,0 +1,122 @@
+use crate::{math, prelude::*};
+use alloc::vec::Vec;
+
+/// The report of [`compare()`], from the view of the solver A.
+///
+/// The better value is decided by the comparator of the solver A, see
+/// [`SolverBuilder::maximize()`] and [`SolverBuilder::compare()`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct ComparisonReport {
+    /// Number of the seeds that A is better
+    pub wins: usize,
+    /// Number of the seeds that A and B are equal, or either of them is NaN
+    pub ties: usize,
+    /// Number of the seeds that B is better
+    pub losses: usize,
+    /// Sum of the signed ranks that A is better
+    pub w_plus: f64,
+    /// Sum of the signed ranks that B is better
+    pub w_minus: f64,
+    /// The z-score of the Wilcoxon signed-rank test in normal approximation,
+    /// positive if A is better
+    pub z: f64,
+}
+
+impl ComparisonReport {
+    /// Return true if the difference is significant at the 5% level
+    /// (two-sided).
+    ///
+    /// The normal approximation needs at least about 10 non-tied seeds.
+    pub fn is_significant(&self) -> bool {
+        self.z.abs() > 1.96
+    }
+}
+
+/// Run two solvers across the same seeds and compare their final best
+/// evaluation values.
+///
+/// The builders are created by the functions for each seed, and the seed of
+/// the builder is overwritten. The final fitness values are compared by the
+/// win/tie/loss counts and the Wilcoxon signed-rank test.
+///
+/// ```
+/// use metaheuristics_nature::{compare, De, Solver};
+/// # use metaheuristics_nature::tests::TestObj as MyFunc;
+///
+/// let build = |f| {
+///     Solver::build(De::default().f(f), MyFunc::new())
+///         .pop_num(20)
+///         .task(|ctx| ctx.gen == 20)
+/// };
+/// let report = compare(|| build(0.6), || build(0.01), 0..10);
+/// println!("{} wins, {} losses", report.wins, report.losses);
+/// ```
+pub fn compare<'a, 'b, A1, A2, F, FA, FB>(
+    a: FA,
+    b: FB,
+    seeds: impl IntoIterator<Item = u64>,
+) -> ComparisonReport
+where
+    A1: Algorithm<F>,
+    A2: Algorithm<F>,
+    F: ObjFunc,
+    FA: Fn() -> SolverBuilder<'a, A1, F>,
+    FB: Fn() -> SolverBuilder<'b, A2, F>,
+    <F::Ys as Fitness>::Eval: Into<f64>,
+{
+    // Differences of the evaluation values, negative if A is better
+    let diff = (seeds.into_iter())
+        .map(|seed| {
+            let a = a().seed(seed).solve();
+            let b = b().seed(seed).solve();
+            let (ya, yb) = (a.as_best_fit(), b.as_best_fit());
+            let d = (ya.eval().into() - yb.eval().into()).abs();
+            if a.ctx.is_better(ya, yb) {
+                -d
+            } else if a.ctx.is_better(yb, ya) {
+                d
+            } else {
+                0.
+            }
+        })
+        .collect::<Vec<f64>>();
+    let wins = diff.iter().filter(|d| **d < 0.).count();
+    let losses = diff.iter().filter(|d| **d > 0.).count();
+    let ties = diff.len() - wins - losses;
+    // Rank the absolute differences, the tied values have the average rank
+    // The NaN differences are ties, which are dropped as well
+    let mut diff = (diff.into_iter())
+        .filter(|d| *d != 0. && !d.is_nan())
+        .collect::<Vec<_>>();
+    diff.sort_unstable_by(|a, b| a.abs().total_cmp(&b.abs()));
+    let n = diff.len();
+    let (mut w_plus, mut w_minus, mut tie_term) = (0., 0., 0.);
+    let mut i = 0;
+    while i < n {
+        let mut j = i + 1;
+        while j < n && diff[j].abs() == diff[i].abs() {
+            j += 1;
+        }
+        let rank = (i + j + 1) as f64 / 2.;
+        for d in &diff[i..j] {
+            if *d < 0. {
+                w_plus += rank;
+            } else {
+                w_minus += rank;
+            }
+        }
+        let t = (j - i) as f64;
+        tie_term += t * t * t - t;
+        i = j;
+    }
+    let n = n as f64;
+    let mean = n * (n + 1.) / 4.;
+    let var = n * (n + 1.) * (2. * n + 1.) / 24. - tie_term / 48.;
+    let z = if var > 0. {
+        (w_plus - mean) / math::sqrt(var)
+    } else {
+        0.
+    };
+    ComparisonReport { wins, ties, losses, w_plus, w_minus, z }
+}
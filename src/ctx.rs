@@ -1,7 +1,78 @@
-use crate::prelude::*;
+use crate::{
+    math,
+    obj_func::{is_discrete, repair, repaired},
+    prelude::*,
+};
 use alloc::vec::Vec;
+use core::{
+    iter::zip,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    time::Duration,
+};
 
 pub(crate) type BestCon<F> = <F as Fitness>::Best<F>;
+#[cfg(feature = "std")]
+type EvalLog<Y> = std::sync::Mutex<Vec<(Vec<f64>, Y)>>;
+
+/// The replacement policy of the offspring, see
+/// [`SolverBuilder::replacement()`].
+///
+/// The offspring only replaces the target individual if it is better.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Replacement {
+    /// Replace the parent, which preserves the diversity
+    #[default]
+    Parent,
+    /// Replace the worst individual, which maximizes the selection pressure
+    Worst,
+    /// Replace a random individual
+    Random,
+}
+
+/// Order of the batch evaluation, see [`SolverBuilder::eval_order()`].
+///
+/// The objective functions that warm-start from the previous input run faster
+/// if the successive inputs are close.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EvalOrder {
+    /// The order of the pool
+    #[default]
+    PoolOrder,
+    /// Sorted by the first variable in ascending order
+    SortedByFirstVar,
+    /// A nearest-neighbor tour from the first individual, which greedily
+    /// visits the closest unvisited individual in the Euclidean distance
+    NearestNeighbor,
+}
+
+impl EvalOrder {
+    /// Return the evaluation order of the indices of the pool.
+    pub fn order(&self, pool: &[Vec<f64>]) -> Vec<usize> {
+        let mut order = (0..pool.len()).collect::<Vec<_>>();
+        match self {
+            Self::PoolOrder => (),
+            Self::SortedByFirstVar => order.sort_by(|&a, &b| pool[a][0].total_cmp(&pool[b][0])),
+            Self::NearestNeighbor => {
+                let dist = |a: &[f64], b: &[f64]| -> f64 {
+                    core::iter::zip(a, b).map(|(a, b)| (a - b) * (a - b)).sum()
+                };
+                for i in 1..order.len() {
+                    let last = &pool[order[i - 1]];
+                    let (j, _) = (order[i..].iter().enumerate())
+                        .map(|(j, &k)| (j, dist(last, &pool[k])))
+                        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                        .unwrap();
+                    order.swap(i, i + j);
+                }
+            }
+        }
+        order
+    }
+}
 
 /// A basic context type of the algorithms.
 ///
@@ -14,6 +85,7 @@ pub(crate) type BestCon<F> = <F as Fitness>::Best<F>;
 /// [`SolverBuilder::callback()`].
 ///
 /// + `ctx.gen` - Get generation number.
+/// + `ctx.progress()` - Get the progress of the generation.
 /// + `ctx.pop_num()` - Get population number.
 /// + `ctx.best.get_eval()` - Get the current best evaluation value.
 /// + `ctx.best.get_xs()` - Get the current best variables.
@@ -22,6 +94,19 @@ pub(crate) type BestCon<F> = <F as Fitness>::Best<F>;
 ///
 /// Do everything you want to do with the context. Please see [`Algorithm`] for
 /// the implementation.
+///
+/// Please use [`Ctx::is_better()`] and [`Ctx::cmp_fitness()`] to compare the
+/// fitness values instead of [`Fitness::is_dominated()`], which respect the
+/// direction of [`SolverBuilder::maximize()`] and the comparator of
+/// [`SolverBuilder::compare()`].
+///
+/// The context implements [`Bounded`] with the effective bounds of the search,
+/// which may be narrower than the objective function's bounds (for example,
+/// [`SolverBuilder::cooperative()`] freezes some variables). Please use
+/// `ctx.bound()` and other methods of [`Bounded`] instead of
+/// `ctx.func.bound()`. The bounds are snapshotted from the objective function
+/// once at the beginning, so [`Bounded::bound()`] is called only once for
+/// each solving.
 #[non_exhaustive]
 pub struct Ctx<F: ObjFunc> {
     /// Best container
@@ -34,27 +119,83 @@ pub struct Ctx<F: ObjFunc> {
     pub func: F,
     /// Generation (iteration) number
     pub gen: u64,
+    /// Maximum generation (iteration) number, a hint of the termination
+    pub max_gen: u64,
+    // Comparison of the fitness values
+    pub(crate) compare: Comparator<F::Ys>,
+    // Replacement policy of the offspring
+    pub(crate) replacement: Replacement,
+    // Niche radius and the shape of the fitness sharing
+    pub(crate) sharing: Option<(f64, f64)>,
+    // Chunk size of the parallel evaluation
+    pub(crate) eval_chunk: usize,
+    // Order of the batch evaluation
+    pub(crate) eval_order: EvalOrder,
+    // Bounds of the objective function
+    pub(crate) func_bound: Vec<[f64; 2]>,
+    // Effective bounds
+    pub(crate) bound: Vec<[f64; 2]>,
+    // Pending seed of the random number generator
+    reseed: PendingSeed,
+    // Elapsed time of the clock
+    pub(crate) elapsed: Option<Duration>,
+    // Repair the moves into the bounds
+    pub(crate) clamp: bool,
+    // The best fitness value before the last generation
+    pub(crate) prev_best: Option<F::Ys>,
+    // The running minimum of each objective
+    pub(crate) ideal: Vec<f64>,
+    // The number of the evaluations
+    pub(crate) evals: AtomicU64,
+    // The log of the evaluations
+    #[cfg(feature = "std")]
+    pub(crate) eval_log: Option<EvalLog<F::Ys>>,
+}
+
+// A seed set by the shared reference, taken before the next generation.
+#[derive(Default)]
+struct PendingSeed {
+    set: AtomicBool,
+    hi: AtomicU64,
+    lo: AtomicU64,
 }
 
 impl<F: ObjFunc> Ctx<F> {
-    pub(crate) fn from_parts(
+    pub(crate) fn new(
         func: F,
-        limit: usize,
+        bound: Vec<[f64; 2]>,
+        mut best: BestCon<F::Ys>,
         pool: Vec<Vec<f64>>,
         pool_y: Vec<F::Ys>,
+        compare: Comparator<F::Ys>,
     ) -> Self {
-        let mut best = BestCon::<F::Ys>::from_limit(limit);
+        best.set_compare(compare.clone());
         best.update_all(&pool, &pool_y);
-        Self { best, pool, pool_y, func, gen: 0 }
-    }
-
-    pub(crate) fn from_pool(func: F, limit: usize, pool: Vec<Vec<f64>>) -> Self {
-        #[cfg(not(feature = "rayon"))]
-        let iter = pool.iter();
-        #[cfg(feature = "rayon")]
-        let iter = pool.par_iter();
-        let pool_y = iter.map(|xs| func.fitness(xs)).collect();
-        Self::from_parts(func, limit, pool, pool_y)
+        let mut ctx = Self {
+            best,
+            pool,
+            pool_y,
+            func,
+            gen: 0,
+            max_gen: 0,
+            compare,
+            replacement: Replacement::default(),
+            sharing: None,
+            eval_chunk: 1,
+            eval_order: EvalOrder::default(),
+            func_bound: bound.clone(),
+            bound,
+            reseed: PendingSeed::default(),
+            elapsed: None,
+            clamp: true,
+            prev_best: None,
+            ideal: Vec::new(),
+            evals: AtomicU64::new(0),
+            #[cfg(feature = "std")]
+            eval_log: None,
+        };
+        ctx.update_ideal();
+        ctx
     }
 
     /// Get population number.
@@ -63,16 +204,537 @@ impl<F: ObjFunc> Ctx<F> {
         self.pool.len()
     }
 
+    /// Get the progress of the generation in `0..=1`, the ratio of `ctx.gen`
+    /// and `ctx.max_gen`.
+    ///
+    /// The value is unreliable if the termination condition is not decided by
+    /// the maximum generation. Please see [`SolverBuilder::max_gen()`].
+    pub fn progress(&self) -> f64 {
+        (self.gen as f64 / self.max_gen as f64).min(1.)
+    }
+
+    /// Evaluate the design variables, where the dominated candidates may be
+    /// aborted early, see [`ObjFunc::fitness_partial()`].
+    ///
+    /// The design variables are snapped before evaluating, see
+    /// [`ObjFunc::var_kind()`].
+    pub fn fitness(&self, xs: &[f64]) -> F::Ys {
+        let xs = repaired(&self.func, xs);
+        let ys = (self.func).fitness_partial(&xs, &|prefix| self.best.dominates_prefix(prefix));
+        self.log_eval(&xs, &ys);
+        ys
+    }
+
+    /// Snap the design variables to the nearest valid values, see
+    /// [`ObjFunc::var_kind()`].
+    ///
+    /// The evaluations and the pool are snapped by the solver, the methods
+    /// only need it for the variables that are not evaluated.
+    pub fn repair(&self, xs: &mut [f64]) {
+        repair(&self.func, xs);
+    }
+
+    // Snap the pool and the best elements in place, their fitness values are
+    // evaluated from the snapped variables.
+    pub(crate) fn repair_all(&mut self) {
+        if !is_discrete(&self.func, self.dim()) {
+            return;
+        }
+        let func = &self.func;
+        self.pool.iter_mut().for_each(|xs| repair(func, xs));
+        self.best.repair(&|xs| repair(func, xs));
+    }
+
+    /// Get the number of the objective function evaluations so far, including
+    /// the initial pool.
+    pub fn eval_count(&self) -> u64 {
+        self.evals.load(Ordering::Relaxed)
+    }
+
+    // Count an evaluation, and append it to the log if recorded.
+    #[allow(unused_variables)]
+    pub(crate) fn log_eval(&self, xs: &[f64], ys: &F::Ys) {
+        self.evals.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "std")]
+        if let Some(log) = &self.eval_log {
+            let mut log = log
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            log.push((repaired(&self.func, xs).into_owned(), ys.clone()));
+        }
+    }
+
+    // Append the batch evaluations to the log if recorded.
+    pub(crate) fn log_evals(&self, pool: &[Vec<f64>], pool_y: &[F::Ys]) {
+        zip(pool, pool_y).for_each(|(xs, ys)| self.log_eval(xs, ys));
+    }
+
+    /// Get the elapsed time since the solver started, measured before each
+    /// generation by the [`Clock`].
+    ///
+    /// The clock is [`std::time::Instant`] by default, so the value is `None`
+    /// only if the `std` feature is disabled and no clock is provided, see
+    /// [`SolverBuilder::clock()`].
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.elapsed
+    }
+
+    /// Get the diversity of the population, the mean distance between the
+    /// individuals and their centroid, where each variable is normalized by
+    /// the width of the objective function's bounds.
+    ///
+    /// The value is zero if all individuals are the same.
+    pub fn diversity(&self) -> f64 {
+        let n = self.pop_num() as f64;
+        let scale = self.dist_scale();
+        let center = (0..self.dim())
+            .map(|s| self.pool.iter().map(|xs| xs[s]).sum::<f64>() / n)
+            .collect::<Vec<_>>();
+        let dist = |xs: &[f64]| math::sqrt(math::dist2(xs, &center, Some(&scale)));
+        self.pool.iter().map(|xs| dist(xs)).sum::<f64>() / n
+    }
+
+    /// Get the maximum spread of the population over the variables, the range
+    /// (max - min) of each variable normalized by the width of the objective
+    /// function's bounds.
+    ///
+    /// The value is zero if all individuals are the same, see also
+    /// [`SolverBuilder::stop_on_param_convergence()`].
+    pub fn max_spread(&self) -> f64 {
+        let scale = self.dist_scale();
+        (0..self.dim())
+            .map(|s| {
+                let (min, max) = (self.pool.iter())
+                    .map(|xs| xs[s])
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), x| {
+                        (min.min(x), max.max(x))
+                    });
+                (max - min) * scale[s]
+            })
+            .fold(0., f64::max)
+    }
+
+    // The scale of each variable to normalize the distance by the bounds
+    fn dist_scale(&self) -> Vec<f64> {
+        (self.func_bound.iter())
+            .map(|[lb, ub]| if ub > lb { 1. / (ub - lb) } else { 1. })
+            .collect()
+    }
+
+    /// Get the shared fitness of the population for the selection, where the
+    /// higher value is better, or `None` if the fitness sharing is disabled,
+    /// see [`SolverBuilder::fitness_sharing()`].
+    ///
+    /// The raw fitness is the reversed rank of the individual (`pop_num` for
+    /// the best, 1 for the worst), so it works for any [`Fitness`] type. The
+    /// raw fitness is divided by the niche count `sum(sh(d))`, where `d` is
+    /// the distance to each individual normalized by the bounds, and
+    /// `sh(d) = 1 - (d / radius)^alpha` inside the niche radius, otherwise
+    /// zero.
+    pub fn shared_fitness(&self) -> Option<Vec<f64>> {
+        let (radius, alpha) = self.sharing?;
+        let n = self.pop_num();
+        let mut order = (0..n).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| self.cmp_fitness(&self.pool_y[a], &self.pool_y[b]));
+        let mut raw = alloc::vec![0.; n];
+        for (rank, i) in order.into_iter().enumerate() {
+            raw[i] = (n - rank) as f64;
+        }
+        let scale = self.dist_scale();
+        let shared = zip(&self.pool, raw)
+            .map(|(a, raw)| {
+                let niche = (self.pool.iter())
+                    .map(|b| math::sqrt(math::dist2(a, b, Some(&scale))) / radius)
+                    .filter(|d| *d < 1.)
+                    .map(|d| 1. - math::powf(d, alpha))
+                    .sum::<f64>();
+                // The niche count includes the individual itself
+                raw / niche
+            })
+            .collect();
+        Some(shared)
+    }
+
+    /// Get the fraction of the current population that satisfies all
+    /// constraints, see [`Constrained`].
+    ///
+    /// The constraints are checked in each call, so it always reflects the
+    /// current generation and can be recorded by the callback.
+    pub fn feasible_ratio(&self) -> f64
+    where
+        F: Constrained,
+    {
+        let n = self
+            .pool
+            .iter()
+            .filter(|xs| self.func.is_feasible(xs))
+            .count();
+        n as f64 / self.pop_num() as f64
+    }
+
+    /// Return true if the moves of the methods are repaired into the bounds,
+    /// see [`SolverBuilder::clamp_to_bounds()`].
+    #[inline]
+    pub fn clamp_to_bounds(&self) -> bool {
+        self.clamp
+    }
+
+    /// Get the minimum chunk size of the parallel evaluation, see
+    /// [`SolverBuilder::eval_chunk()`].
+    ///
+    /// The methods can pass it to `with_min_len()` of the parallel iterators.
+    #[inline]
+    pub fn eval_chunk(&self) -> usize {
+        self.eval_chunk
+    }
+
+    /// Replace the random number generator with the seed before the next
+    /// generation, the ChaCha state is fully reset as
+    /// `Rng::new(SeedOpt::U128(seed))`.
+    ///
+    /// It can be called from [`SolverBuilder::callback()`].
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .callback(|ctx| {
+    ///         if ctx.gen == 10 {
+    ///             ctx.reseed(1);
+    ///         }
+    ///     })
+    ///     .solve();
+    /// ```
+    pub fn reseed(&self, seed: u128) {
+        let PendingSeed { set, hi, lo } = &self.reseed;
+        hi.store((seed >> 64) as u64, Ordering::Relaxed);
+        lo.store(seed as u64, Ordering::Relaxed);
+        set.store(true, Ordering::Release);
+    }
+
+    pub(crate) fn take_seed(&self) -> Option<u128> {
+        let PendingSeed { set, hi, lo } = &self.reseed;
+        if !set.swap(false, Ordering::Acquire) {
+            return None;
+        }
+        let hi = hi.load(Ordering::Relaxed) as u128;
+        let lo = lo.load(Ordering::Relaxed) as u128;
+        Some(hi << 64 | lo)
+    }
+
+    /// Return true if the fitness value `a` is better than `b`.
+    ///
+    /// See also [`SolverBuilder::maximize()`] and [`SolverBuilder::compare()`].
+    #[inline]
+    pub fn is_better(&self, a: &F::Ys, b: &F::Ys) -> bool {
+        self.compare.is_better(a, b)
+    }
+
+    /// Compare the fitness values, where the better one is less. It can be
+    /// used to sort the individuals.
+    ///
+    /// See also [`SolverBuilder::maximize()`] and [`SolverBuilder::compare()`].
+    #[inline]
+    pub fn cmp_fitness(&self, a: &F::Ys, b: &F::Ys) -> core::cmp::Ordering {
+        self.compare.cmp(a, b)
+    }
+
+    /// The ideal point, the running minimum of each objective found so far,
+    /// which is refreshed from the pool after each generation.
+    ///
+    /// The objectives are obtained from [`Fitness::objectives()`], so it is
+    /// empty for the single-objective fitness values. See also
+    /// [`RefPoint::Adaptive`].
+    pub fn ideal_point(&self) -> &[f64] {
+        &self.ideal
+    }
+
+    /// The weighted Tchebycheff scalarization of the fitness value, see
+    /// [`pareto::tchebycheff()`].
+    ///
+    /// The decomposition-based methods use it to compare the individuals of a
+    /// subproblem with its weights.
+    pub fn tchebycheff(&self, ys: &F::Ys, weights: &[f64], reference: &RefPoint) -> f64 {
+        let reference = reference.resolve(&self.ideal);
+        pareto::tchebycheff(&ys.objectives(), weights, reference)
+    }
+
+    // Refresh the ideal point from the pool.
+    pub(crate) fn update_ideal(&mut self) {
+        for ys in &self.pool_y {
+            let ys = ys.objectives();
+            if self.ideal.is_empty() {
+                self.ideal = ys;
+            } else {
+                zip(&mut self.ideal, ys).for_each(|(z, y)| *z = z.min(y));
+            }
+        }
+    }
+
+    /// How much the best fitness value improved in the most recent generation,
+    /// zero if none.
+    ///
+    /// The value is always non-negative, and the maximization is also
+    /// considered, see [`SolverBuilder::maximize()`].
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .callback(|ctx| println!("{}", ctx.last_improvement()))
+    ///     .solve();
+    /// ```
+    pub fn last_improvement(&self) -> f64
+    where
+        <F::Ys as Fitness>::Eval: Into<f64>,
+    {
+        match &self.prev_best {
+            Some(ys) => (self.compare.cost(ys.eval().into()) - self.best_cost()).max(0.),
+            None => 0.,
+        }
+    }
+
+    // The current best fitness value as a cost, negated for the maximization,
+    // where the lower value is always better.
+    pub(crate) fn best_cost(&self) -> f64
+    where
+        <F::Ys as Fitness>::Eval: Into<f64>,
+    {
+        self.compare.cost(self.best.get_eval().into())
+    }
+
     /// Assign the index from source.
     pub fn set_from(&mut self, i: usize, xs: Vec<f64>, ys: F::Ys) {
         self.pool[i] = xs;
         self.pool_y[i] = ys;
     }
 
+    /// Replace an individual with the offspring of the parent `i` by the
+    /// replacement policy, and return the replaced index if the offspring is
+    /// better than the target.
+    ///
+    /// See also [`SolverBuilder::replacement()`].
+    pub fn replace(&mut self, i: usize, xs: Vec<f64>, ys: F::Ys, rng: &mut Rng) -> Option<usize> {
+        let target = match self.replacement {
+            Replacement::Parent => i,
+            Replacement::Worst => (0..self.pop_num())
+                .max_by(|&a, &b| self.cmp_fitness(&self.pool_y[a], &self.pool_y[b]))
+                .unwrap_or(i),
+            Replacement::Random => rng.ub(self.pop_num()),
+        };
+        if self.is_better(&ys, &self.pool_y[target]) {
+            self.set_from(target, xs, ys);
+            Some(target)
+        } else {
+            None
+        }
+    }
+
     /// Find the best, and set it globally.
     pub fn find_best(&mut self) {
         self.best.update_all(&self.pool, &self.pool_y);
     }
+
+    // Freeze the variables outside the group at the current best (the context
+    // vector), restore the group variables of each individual from the stash,
+    // and then re-evaluate the pool.
+    pub(crate) fn freeze_except(&mut self, group: &[usize], stash: &[Vec<f64>]) {
+        let best = self.best.get_xs();
+        for (s, bound) in self.bound.iter_mut().enumerate() {
+            *bound = if group.contains(&s) {
+                self.func_bound[s]
+            } else {
+                [best[s]; 2]
+            };
+        }
+        let func = &self.func;
+        let bound = &self.bound;
+        #[cfg(not(feature = "rayon"))]
+        let iter = self.pool.iter_mut().zip(&mut self.pool_y);
+        #[cfg(feature = "rayon")]
+        let iter = (self.pool.par_iter_mut())
+            .zip(&mut self.pool_y)
+            .with_min_len(self.eval_chunk);
+        iter.zip(stash).for_each(|((xs, ys), stash)| {
+            for (s, &[min, max]) in bound.iter().enumerate() {
+                xs[s] = stash[s].clamp(min, max);
+            }
+            repair(func, xs);
+            *ys = func.fitness(xs);
+        });
+        self.log_evals(&self.pool, &self.pool_y);
+        self.find_best();
+    }
+
+    // Regenerate the pool uniformly in the effective bounds. The best set is
+    // kept.
+    pub(crate) fn reinit(&mut self, rng: &mut Rng) {
+        let pool = (0..self.pop_num())
+            .map(|_| {
+                (0..self.dim())
+                    .map(|s| rng.range(self.bound_range(s)))
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+        self.pool_y = fitness_all(&self.func, &pool, self.eval_chunk, self.eval_order);
+        self.pool = pool;
+        self.log_evals(&self.pool, &self.pool_y);
+        self.find_best();
+    }
+
+    // Replace the worst individual with the external candidate if it is
+    // better.
+    pub(crate) fn immigrate(&mut self, xs: Vec<f64>) {
+        assert_eq!(xs.len(), self.dim(), "Immigrant dimension mismatched");
+        let ys = self.fitness(&xs);
+        let worst = (0..self.pop_num())
+            .max_by(|&a, &b| self.cmp_fitness(&self.pool_y[a], &self.pool_y[b]))
+            .unwrap();
+        if self.is_better(&ys, &self.pool_y[worst]) {
+            self.best.update(&xs, &ys);
+            self.set_from(worst, xs, ys);
+        }
+    }
+
+    // Regenerate the individuals that are within the tolerance of a better
+    // one in all variables. The pool is swept in the order of the first
+    // variable, so only the neighbors in the window are compared.
+    pub(crate) fn dedup(&mut self, tol: f64, rng: &mut Rng) {
+        let n = self.pop_num();
+        let mut ind = (0..n).collect::<Vec<_>>();
+        ind.sort_unstable_by(|&a, &b| self.pool[a][0].total_cmp(&self.pool[b][0]));
+        let mut dup = alloc::vec![false; n];
+        for (k, &i) in ind.iter().enumerate() {
+            for &j in &ind[k + 1..] {
+                if dup[i] || self.pool[j][0] - self.pool[i][0] > tol {
+                    break;
+                }
+                if dup[j] || zip(&self.pool[i], &self.pool[j]).any(|(a, b)| (a - b).abs() > tol) {
+                    continue;
+                }
+                if self.is_better(&self.pool_y[j], &self.pool_y[i]) {
+                    dup[i] = true;
+                } else {
+                    dup[j] = true;
+                }
+            }
+        }
+        let ind = (0..n).filter(|i| dup[*i]).collect::<Vec<_>>();
+        if ind.is_empty() {
+            return;
+        }
+        let pool = (ind.iter())
+            .map(|_| {
+                (0..self.dim())
+                    .map(|s| rng.range(self.bound_range(s)))
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+        let pool_y = fitness_all(&self.func, &pool, self.eval_chunk, self.eval_order);
+        self.log_evals(&pool, &pool_y);
+        for ((i, xs), ys) in zip(zip(ind, pool), pool_y) {
+            self.best.update(&xs, &ys);
+            self.set_from(i, xs, ys);
+        }
+    }
+
+    // Move the variables at the effective bounds inward by a random amount up
+    // to half of the width with the probability, then re-evaluate the moved
+    // individuals.
+    pub(crate) fn desaturate(&mut self, prob: f64, rng: &mut Rng) {
+        let mut ind = Vec::new();
+        let mut pool = Vec::new();
+        for (i, xs) in self.pool.iter().enumerate() {
+            let mut moved = false;
+            let mut xs = xs.clone();
+            for (x, &[min, max]) in zip(&mut xs, &self.bound) {
+                let half = (max - min) * 0.5;
+                let saturated = *x <= min || *x >= max;
+                if !saturated || half <= 0. || !rng.maybe(prob) {
+                    continue;
+                }
+                *x = if *x <= min {
+                    min + rng.ub(half)
+                } else {
+                    max - rng.ub(half)
+                };
+                moved = true;
+            }
+            if moved {
+                ind.push(i);
+                pool.push(xs);
+            }
+        }
+        if ind.is_empty() {
+            return;
+        }
+        let pool_y = fitness_all(&self.func, &pool, self.eval_chunk, self.eval_order);
+        self.log_evals(&pool, &pool_y);
+        for ((i, xs), ys) in zip(zip(ind, pool), pool_y) {
+            self.best.update(&xs, &ys);
+            self.set_from(i, xs, ys);
+        }
+    }
+
+    // Contract the effective bounds toward the current best by the rate. The
+    // width is not shrunk below a tiny fraction of the original width.
+    pub(crate) fn shrink(&mut self, rate: f64) {
+        const FLOOR: f64 = 1e-10;
+        let best = self.best.get_xs();
+        for ((bound, [lb0, ub0]), x) in zip(zip(&mut self.bound, &self.func_bound), best) {
+            let [lb, ub] = *bound;
+            let x = x.clamp(lb, ub);
+            let new = [lb + rate * (x - lb), ub - rate * (ub - x)];
+            if new[1] - new[0] >= (ub0 - lb0) * FLOOR {
+                *bound = new;
+            }
+        }
+    }
+
+    // Restore the bounds from the objective function.
+    pub(crate) fn unfreeze(&mut self) {
+        self.bound.clone_from(&self.func_bound);
+    }
+}
+
+pub(crate) fn fitness_all<F: ObjFunc>(
+    func: &F,
+    pool: &[Vec<f64>],
+    chunk: usize,
+    order: EvalOrder,
+) -> Vec<F::Ys> {
+    #[cfg(not(feature = "rayon"))]
+    let _ = chunk;
+    if order == EvalOrder::PoolOrder {
+        #[cfg(not(feature = "rayon"))]
+        let iter = pool.iter();
+        #[cfg(feature = "rayon")]
+        let iter = pool.par_iter().with_min_len(chunk);
+        return iter.map(|xs| func.fitness(&repaired(func, xs))).collect();
+    }
+    let order = order.order(pool);
+    #[cfg(not(feature = "rayon"))]
+    let iter = order.into_iter();
+    #[cfg(feature = "rayon")]
+    let iter = order.into_par_iter().with_min_len(chunk);
+    let mut ys = iter
+        .map(|i| (i, func.fitness(&repaired(func, &pool[i]))))
+        .collect::<Vec<_>>();
+    ys.sort_unstable_by_key(|(i, _)| *i);
+    ys.into_iter().map(|(_, ys)| ys).collect()
+}
+
+impl<F: ObjFunc> Bounded for Ctx<F> {
+    #[inline]
+    fn bound(&self) -> &[[f64; 2]] {
+        &self.bound
+    }
 }
 
 impl<F: ObjFunc> core::ops::Deref for Ctx<F> {
@@ -1,5 +1,6 @@
 use crate::prelude::*;
-use alloc::sync::Arc;
+use alloc::{sync::Arc, vec::Vec};
+use core::{cmp::Ordering, marker::PhantomData};
 
 /// Trait for dominance comparison.
 ///
@@ -10,7 +11,27 @@ use alloc::sync::Arc;
 ///
 /// # Example
 ///
-/// Single objective problems can simply use the `f32`/`f64` number type.
+/// Single objective problems can simply use the `f32`/`f64` number type, or
+/// the integer types such as `i64`/`u64` for the counts, which are compared
+/// exactly. Use the `MAX` value as the infeasible sentinel of the integers
+/// like `f64::INFINITY`. The float utilities that require
+/// `Fitness::Eval: Into<f64>`, such as [`SolverBuilder::stop_on_slope()`], are
+/// available for `i32`/`u32` but not for the 64-bit integers, which are not
+/// converted losslessly.
+///
+/// ```
+/// use metaheuristics_nature::{Fx, Rga, Solver};
+///
+/// // Number of the variables away from the origin
+/// let f = Fx::new(&[[-5., 5.]; 3], |xs: &[f64; 3]| {
+///     xs.iter().filter(|x| x.abs() > 0.1).count() as u64
+/// });
+/// let s = Solver::build(Rga::default(), f)
+///     .seed(0)
+///     .task(|ctx| ctx.best.get_eval() == 0)
+///     .solve();
+/// assert_eq!(s.get_best_eval(), 0);
+/// ```
 ///
 /// Multi-objective problems can specify the [`Pareto`] container as
 /// [`Fitness::Best`] and implement [`Fitness::eval()`] to decide the final
@@ -36,6 +57,34 @@ use alloc::sync::Arc;
 ///     }
 /// }
 /// ```
+///
+/// The comparison is not limited to [`PartialOrd`]. Implement
+/// [`Fitness::is_dominated()`] for a custom comparator, for example, a
+/// single objective with a domain-specific tie-breaking. The selection and
+/// finding best of all methods are decided by this function, unless a
+/// comparator is set by [`SolverBuilder::compare()`].
+///
+/// ```
+/// use metaheuristics_nature::{pareto::SingleBest, Fitness};
+///
+/// #[derive(Clone)]
+/// struct Cost {
+///     cost: f64,
+///     // Prefer the fewer parts if the cost is the same
+///     parts: u32,
+/// }
+///
+/// impl Fitness for Cost {
+///     type Best<T: Fitness> = SingleBest<T>;
+///     type Eval = f64;
+///     fn is_dominated(&self, rhs: &Self) -> bool {
+///         (self.cost, self.parts) < (rhs.cost, rhs.parts)
+///     }
+///     fn eval(&self) -> Self::Eval {
+///         self.cost
+///     }
+/// }
+/// ```
 pub trait Fitness: MaybeParallel + Clone + 'static {
     /// The best element container.
     /// + Use [`SingleBest`] for single objective.
@@ -50,6 +99,26 @@ pub trait Fitness: MaybeParallel + Clone + 'static {
     /// Used in [`Best::as_result()`] and [`Best::update()`] when reaching the
     /// limit.
     fn eval(&self) -> Self::Eval;
+    /// Check if `self` dominates any candidate whose first objectives are
+    /// `prefix`, whatever its rest objectives are, see
+    /// [`ObjFunc::fitness_partial()`].
+    ///
+    /// The rest objectives of the candidate are unknown, so their lower bounds
+    /// should be considered. It returns false by default, which never
+    /// short-circuits the evaluation.
+    fn dominates_prefix(&self, prefix: &[f64]) -> bool {
+        let _ = prefix;
+        false
+    }
+    /// The objective values, used by the [`Truncation`] strategies of the
+    /// Pareto front, see [`SolverBuilder::truncation()`].
+    ///
+    /// It returns an empty vector by default, then the front is pruned by the
+    /// worst [`Fitness::eval()`]. The hypervolume ([`Pareto::hypervolume()`])
+    /// requires this method to be implemented.
+    fn objectives(&self) -> Vec<f64> {
+        Vec::new()
+    }
 }
 
 impl<T: MaybeParallel + PartialOrd + Clone + 'static> Fitness for T {
@@ -91,6 +160,137 @@ where
     }
 }
 
+/// A [`Fitness`] type for maximization, which reverses the comparison of the
+/// inner value.
+///
+/// The methods are minimizing the fitness value, wrap the fitness value with
+/// `Maximize(y)` instead of negating it, then the reported fitness value keeps
+/// the true sign. The conversion into [`f64`] is negated as the cost, which is
+/// used by the float utilities like [`SolverBuilder::stop_on_slope()`], take
+/// the field `.0` for the true value. See also [`SolverBuilder::maximize()`]
+/// for the plain fitness values.
+///
+/// ```
+/// use metaheuristics_nature::{Fx, Maximize, Rga, Solver};
+///
+/// let f = Fx::new(&[[-5., 5.]], |&[x]: &[f64; 1]| Maximize(1. - x * x));
+/// let s = Solver::build(Rga::default(), f)
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .solve();
+/// let Maximize(y) = s.get_best_eval();
+/// assert!((1. - y).abs() < 1e-4);
+/// assert_eq!(f64::from(Maximize(y)), -y);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[repr(transparent)]
+pub struct Maximize<T>(pub T);
+
+impl<T: PartialOrd> PartialOrd for Maximize<T> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<core::cmp::Ordering> {
+        rhs.0.partial_cmp(&self.0)
+    }
+}
+
+impl<T: Ord> Ord for Maximize<T> {
+    fn cmp(&self, rhs: &Self) -> core::cmp::Ordering {
+        rhs.0.cmp(&self.0)
+    }
+}
+
+impl<T> From<Maximize<T>> for f64
+where
+    f64: From<T>,
+{
+    #[inline]
+    fn from(Maximize(v): Maximize<T>) -> Self {
+        -f64::from(v)
+    }
+}
+
+/// A comparator of the fitness values, returns [`Ordering::Less`] if the first
+/// value is better, see [`SolverBuilder::compare()`].
+///
+/// It is always thread-safe, so the [`Solver`] can be sent between threads
+/// without the `rayon` feature.
+pub type CompareFn<Y> = Arc<dyn Fn(&Y, &Y) -> Ordering + Send + Sync>;
+
+/// The comparison of the fitness values, which decides the selection of the
+/// methods and the best element.
+///
+/// The direction is set by [`SolverBuilder::maximize()`], and a custom
+/// comparator is set by [`SolverBuilder::compare()`]. The methods can use it
+/// through [`Ctx::is_better()`] and [`Ctx::cmp_fitness()`].
+pub struct Comparator<Y> {
+    maximize: bool,
+    custom: Option<CompareFn<Y>>,
+    _marker: PhantomData<fn(&Y, &Y)>,
+}
+
+impl<Y> Comparator<Y> {
+    pub(crate) fn new(maximize: bool, custom: Option<CompareFn<Y>>) -> Self {
+        Self { maximize, custom, _marker: PhantomData }
+    }
+
+    /// Return true if maximizing the fitness value.
+    pub fn is_maximize(&self) -> bool {
+        self.maximize
+    }
+
+    // Orient a float fitness value as a cost, where the lower value is better
+    pub(crate) fn cost(&self, v: f64) -> f64 {
+        if self.maximize {
+            -v
+        } else {
+            v
+        }
+    }
+}
+
+impl<Y: Fitness> Comparator<Y> {
+    /// Return true if `a` is better than `b`.
+    pub fn is_better(&self, a: &Y, b: &Y) -> bool {
+        if let Some(f) = &self.custom {
+            f(a, b) == Ordering::Less
+        } else if self.maximize {
+            b.is_dominated(a)
+        } else {
+            a.is_dominated(b)
+        }
+    }
+
+    /// Compare the fitness values, where the better one is less. The
+    /// incomparable values are equal.
+    pub fn cmp(&self, a: &Y, b: &Y) -> Ordering {
+        if let Some(f) = &self.custom {
+            return f(a, b);
+        }
+        let (a, b) = if self.maximize { (b, a) } else { (a, b) };
+        a.eval().partial_cmp(&b.eval()).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<Y> Clone for Comparator<Y> {
+    fn clone(&self) -> Self {
+        Self::new(self.maximize, self.custom.clone())
+    }
+}
+
+impl<Y> Default for Comparator<Y> {
+    fn default() -> Self {
+        Self::new(false, None)
+    }
+}
+
+impl<Y> core::fmt::Debug for Comparator<Y> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Comparator")
+            .field("maximize", &self.maximize)
+            .field("custom", &self.custom.is_some())
+            .finish()
+    }
+}
+
 /// A [`Fitness`] type carrying final results.
 ///
 /// You can use [`Solver::as_best_xs()`] / [`Solver::as_best_fit()`] /
@@ -158,4 +358,10 @@ where
     fn eval(&self) -> Self::Eval {
         self.ys.eval()
     }
+    fn dominates_prefix(&self, prefix: &[f64]) -> bool {
+        self.ys.dominates_prefix(prefix)
+    }
+    fn objectives(&self) -> Vec<f64> {
+        self.ys.objectives()
+    }
 }
@@ -44,3 +44,85 @@ impl<Y: Fitness, const DIM: usize> ObjFunc for Fx<'_, '_, Y, DIM> {
         (self.func)(xs.try_into().unwrap_or_else(|_| unreachable!()))
     }
 }
+
+/// A quick interface help to create objective function from a fallible
+/// callable object.
+///
+/// The error is mapped to the worst fitness value (positive infinity), so the
+/// failed design variables are simply avoided. The error can be logged by
+/// [`FxResult::on_error()`]. Since the worst value is only for minimization,
+/// [`SolverBuilder::maximize()`] is rejected, please negate the value
+/// instead.
+///
+/// ```
+/// use metaheuristics_nature::{FxResult, Rga, Solver};
+///
+/// let bound = [[-50., 50.]; 2];
+/// let f = FxResult::new(&bound, |&[a, b]| {
+///     if a < 0. {
+///         Err("diverged")
+///     } else {
+///         Ok(a * a + b * b)
+///     }
+/// })
+/// .on_error(|e| eprintln!("{e}"));
+/// let s = Solver::build(Rga::default(), f)
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .solve();
+/// ```
+pub struct FxResult<'b, 'f, E, const DIM: usize> {
+    bound: &'b [[f64; 2]; DIM],
+    #[allow(clippy::type_complexity)]
+    func: Box<dyn Fn(&[f64; DIM]) -> Result<f64, E> + Sync + Send + 'f>,
+    #[allow(clippy::type_complexity)]
+    on_error: Option<Box<dyn Fn(&E) + Sync + Send + 'f>>,
+}
+
+impl<'b, 'f, E, const DIM: usize> FxResult<'b, 'f, E, DIM> {
+    /// Create objective function from a fallible callable object.
+    pub fn new<F>(bound: &'b [[f64; 2]; DIM], func: F) -> Self
+    where
+        F: Fn(&[f64; DIM]) -> Result<f64, E> + Sync + Send + 'f,
+    {
+        Self { bound, func: Box::new(func), on_error: None }
+    }
+
+    /// Set the function to log the errors.
+    ///
+    /// # Default
+    ///
+    /// By default, the errors are ignored.
+    pub fn on_error<C>(self, on_error: C) -> Self
+    where
+        C: Fn(&E) + Sync + Send + 'f,
+    {
+        Self { on_error: Some(Box::new(on_error)), ..self }
+    }
+}
+
+impl<E, const DIM: usize> Bounded for FxResult<'_, '_, E, DIM> {
+    #[inline]
+    fn bound(&self) -> &[[f64; 2]] {
+        self.bound
+    }
+}
+
+impl<E, const DIM: usize> ObjFunc for FxResult<'_, '_, E, DIM> {
+    type Ys = f64;
+    fn fitness(&self, xs: &[f64]) -> Self::Ys {
+        match (self.func)(xs.try_into().unwrap_or_else(|_| unreachable!())) {
+            Ok(ys) => ys,
+            Err(e) => {
+                if let Some(on_error) = &self.on_error {
+                    on_error(&e);
+                }
+                f64::INFINITY
+            }
+        }
+    }
+
+    fn is_minimize_only(&self) -> bool {
+        true
+    }
+}
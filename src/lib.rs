@@ -46,6 +46,16 @@
 //!   is not complicate enough. This feature require `std` feature.
 //! + `clap`: Add CLI argument support for the provided algorithms and their
 //!   options.
+//! + `argmin`: Add the adapter `ArgminObj` for the cost functions of crate
+//!   "argmin". This feature require `std` feature.
+//! + `arrow`: Export the best history to the Parquet format via crate
+//!   "parquet", see `Solver::write_parquet()`. This feature require `std`
+//!   feature.
+//! + `libm`: Use crate "libm" for the math functions of the provided methods
+//!   even if `std` is enabled. The platform implementations may differ in the
+//!   last bits, so this feature makes the `std` and `no_std` builds produce
+//!   identical results for the same seed. (Except the sampling of the
+//!   distributions from `rand_distr`, such as [`random::Rng::normal()`].)
 //!
 //! # Compatibility
 //!
@@ -66,9 +76,14 @@ pub use rand;
 #[cfg(feature = "rayon")]
 pub use rayon;
 
+#[cfg(feature = "argmin")]
+pub use self::argmin_obj::*;
+#[cfg(feature = "std")]
+pub use self::cached::*;
 pub use self::{
-    algorithm::*, ctx::*, fitness::*, fx_func::*, methods::*, obj_func::*, solver::*,
-    solver_builder::*,
+    algorithm::*, clock::*, compare::*, ctx::*, fitness::*, fx_func::*, methods::*, obj_func::*,
+    permutation::*, report::*, run_config::*, solver::*, solver_builder::*, solver_iter::*,
+    solver_state::*,
 };
 
 /// A tool macro used to generate multiple builder functions (methods).
@@ -127,6 +142,21 @@ macro_rules! impl_builders {
 ///
 /// This module includes all items of this crate, some hidden types,
 /// and external items from "ndarray" and "rayon" (if `rayon` feature enabled).
+///
+/// The method settings, the [`Pool`] options, and the [`Solver`] are included,
+/// so a single import is enough to build and solve a task.
+///
+/// ```
+/// use metaheuristics_nature::prelude::*;
+///
+/// let f = Fx::new(&[[-10., 10.]; 2], |&[x, y]| x * x + y * y);
+/// let s = Solver::build(De::default(), f)
+///     .seed(0)
+///     .init_pool(Pool::Func(Box::new(gaussian_pool(&[0.; 2], &[1.; 2]))))
+///     .task(|ctx| ctx.gen == 50)
+///     .solve();
+/// assert!(s.get_best_eval() < 1e-2);
+/// ```
 pub mod prelude {
     pub use super::*;
     pub use crate::{pareto::*, random::*};
@@ -139,15 +169,32 @@ pub mod prelude {
 }
 
 mod algorithm;
+#[cfg(feature = "argmin")]
+mod argmin_obj;
+#[cfg(feature = "arrow")]
+mod arrow_io;
+#[cfg(feature = "std")]
+mod cached;
+mod clock;
+mod compare;
 mod ctx;
 mod fitness;
 mod fx_func;
+mod local;
+mod math;
 pub mod methods;
 mod obj_func;
 pub mod pareto;
+mod permutation;
+#[cfg(feature = "std")]
+mod pool_io;
 pub mod random;
+mod report;
+mod run_config;
 mod solver;
 mod solver_builder;
+mod solver_iter;
+mod solver_state;
 pub mod tests;
 
 /// A marker trait for parallel computation.
@@ -0,0 +1,174 @@
+//! Local refinement of a solution, see [`Solver::refine_lbfgs()`].
+use crate::prelude::*;
+use alloc::{collections::VecDeque, vec::Vec};
+use core::iter::zip;
+
+// Number of the correction pairs of L-BFGS
+const MEMORY: usize = 10;
+// Sufficient decrease of the Armijo condition
+const C1: f64 = 1e-4;
+// Maximum number of the backtracking steps
+const BACKTRACK: usize = 40;
+// Tolerance of the projected gradient
+const TOL: f64 = 1e-10;
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    zip(a, b).map(|(a, b)| a * b).sum()
+}
+
+fn project(bound: &[[f64; 2]], xs: &mut [f64]) {
+    zip(xs, bound).for_each(|(x, [lb, ub])| *x = x.clamp(*lb, *ub));
+}
+
+// Central finite differences, one-sided if the step is clamped by the bounds
+fn finite_diff(f: &mut impl FnMut(&[f64]) -> f64, bound: &[[f64; 2]], xs: &[f64]) -> Vec<f64> {
+    let mut xs = xs.to_vec();
+    (0..xs.len())
+        .map(|s| {
+            let x = xs[s];
+            let [lb, ub] = bound[s];
+            let h = 1e-7 * x.abs().max(1.);
+            let (x1, x2) = ((x + h).min(ub), (x - h).max(lb));
+            xs[s] = x1;
+            let y1 = f(&xs);
+            xs[s] = x2;
+            let y2 = f(&xs);
+            xs[s] = x;
+            if x1 > x2 {
+                (y1 - y2) / (x1 - x2)
+            } else {
+                0.
+            }
+        })
+        .collect()
+}
+
+// The gradient with the components that point out of the bounds removed
+fn projected(bound: &[[f64; 2]], xs: &[f64], g: &[f64]) -> Vec<f64> {
+    zip(zip(xs, g), bound)
+        .map(|((x, g), [lb, ub])| {
+            if (*x <= *lb && *g > 0.) || (*x >= *ub && *g < 0.) {
+                0.
+            } else {
+                *g
+            }
+        })
+        .collect()
+}
+
+// The two-loop recursion, returns the descent direction -H * g
+fn two_loop(mem: &VecDeque<(Vec<f64>, Vec<f64>)>, g: &[f64]) -> Vec<f64> {
+    let mut q = g.to_vec();
+    let mut alpha = Vec::with_capacity(mem.len());
+    for (s, y) in mem.iter().rev() {
+        let a = dot(s, &q) / dot(y, s);
+        zip(&mut q, y).for_each(|(q, y)| *q -= a * y);
+        alpha.push(a);
+    }
+    // Initial Hessian approximation
+    if let Some((s, y)) = mem.back() {
+        let gamma = dot(s, y) / dot(y, y);
+        q.iter_mut().for_each(|q| *q *= gamma);
+    }
+    for ((s, y), a) in zip(mem, alpha.into_iter().rev()) {
+        let b = dot(y, &q) / dot(y, s);
+        zip(&mut q, s).for_each(|(q, s)| *q += (a - b) * s);
+    }
+    q.iter_mut().for_each(|q| *q = -*q);
+    q
+}
+
+/// Refine `xs` with L-BFGS in the bounds, return the final variables, the
+/// fitness value, and the number of the evaluations.
+///
+/// The fitness value and the gradient are computed jointly by
+/// [`ObjFunc::fitness_and_grad()`] if [`ObjFunc::is_joint_grad()`] is true,
+/// otherwise the trial points are evaluated without the gradient. The
+/// gradient or the finite differences are only computed at the accepted
+/// points.
+///
+/// The fitness value is maximized instead if `maximize` is true.
+pub(crate) fn lbfgs<F: ObjFunc>(
+    func: &F,
+    bound: &[[f64; 2]],
+    xs: &[f64],
+    max_iter: usize,
+    maximize: bool,
+) -> (Vec<f64>, F::Ys, usize)
+where
+    <F::Ys as Fitness>::Eval: Into<f64>,
+{
+    // Orient the value and the gradient as a cost
+    let sign = if maximize { -1. } else { 1. };
+    let cost = |ys: &F::Ys| sign * ys.eval().into();
+    let orient = |g: Vec<f64>| g.into_iter().map(|g| sign * g).collect::<Vec<_>>();
+    let joint = func.is_joint_grad();
+    let mut evals = 0;
+    let mut f = |xs: &[f64]| {
+        evals += 1;
+        if joint {
+            let (ys, g) = func.fitness_and_grad(xs);
+            (ys, g.map(orient))
+        } else {
+            (func.fitness(xs), None)
+        }
+    };
+    let grad = |f: &mut dyn FnMut(&[f64]) -> F::Ys, xs: &[f64], g: Option<Vec<f64>>| {
+        g.or_else(|| (!joint).then(|| func.gradient(xs).map(orient)).flatten())
+            .unwrap_or_else(|| finite_diff(&mut |xs| cost(&f(xs)), bound, xs))
+    };
+    let mut xs = xs.to_vec();
+    project(bound, &mut xs);
+    let (mut ys, g) = f(&xs);
+    let mut y = cost(&ys);
+    let mut g = grad(&mut |xs| f(xs).0, &xs, g);
+    let mut mem = VecDeque::with_capacity(MEMORY);
+    for _ in 0..max_iter {
+        let pg = projected(bound, &xs, &g);
+        let norm = crate::math::sqrt(dot(&pg, &pg));
+        if norm < TOL {
+            break;
+        }
+        let mut d = two_loop(&mem, &pg);
+        if dot(&d, &pg) >= 0. {
+            mem.clear();
+            d = pg.iter().map(|g| -g).collect();
+        }
+        // Projected backtracking line search
+        let mut step = if mem.is_empty() {
+            1. / norm.max(1.)
+        } else {
+            1.
+        };
+        let mut next = None;
+        for _ in 0..BACKTRACK {
+            let mut trial = zip(&xs, &d).map(|(x, d)| x + step * d).collect::<Vec<_>>();
+            project(bound, &mut trial);
+            let s = zip(&trial, &xs).map(|(a, b)| a - b).collect::<Vec<_>>();
+            let (trial_ys, trial_g) = f(&trial);
+            let trial_y = cost(&trial_ys);
+            if trial_y <= y + C1 * dot(&g, &s) {
+                next = Some((trial, trial_ys, trial_y, trial_g, s));
+                break;
+            }
+            step *= 0.5;
+        }
+        let Some((next_xs, next_ys, next_y, next_g, s)) = next else {
+            break;
+        };
+        let next_g = grad(&mut |xs| f(xs).0, &next_xs, next_g);
+        let dy = zip(&next_g, &g).map(|(a, b)| a - b).collect::<Vec<_>>();
+        if dot(&s, &dy) > f64::EPSILON * dot(&dy, &dy) {
+            if mem.len() == MEMORY {
+                mem.pop_front();
+            }
+            mem.push_back((s, dy));
+        }
+        let converged = y - next_y <= f64::EPSILON * y.abs();
+        (xs, ys, y, g) = (next_xs, next_ys, next_y, next_g);
+        if converged {
+            break;
+        }
+    }
+    (xs, ys, evals)
+}
@@ -0,0 +1,150 @@
+//! Math functions of the provided methods.
+//!
+//! The functions are routed to crate "libm" if the `libm` feature is enabled,
+//! otherwise, they are the `std` functions (or the "libm" functions via
+//! `num_traits::Float` in the `no_std` build).
+use alloc::vec::Vec;
+use core::iter::zip;
+#[cfg(not(feature = "libm"))]
+use num_traits::Float;
+
+/// Exponential function.
+#[inline]
+pub(crate) fn exp(x: f64) -> f64 {
+    #[cfg(feature = "libm")]
+    return libm::exp(x);
+    #[cfg(not(feature = "libm"))]
+    return Float::exp(x);
+}
+
+/// Square root function.
+#[inline]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    #[cfg(feature = "libm")]
+    return libm::sqrt(x);
+    #[cfg(not(feature = "libm"))]
+    return Float::sqrt(x);
+}
+
+/// Natural logarithm function.
+#[inline]
+pub(crate) fn ln(x: f64) -> f64 {
+    #[cfg(feature = "libm")]
+    return libm::log(x);
+    #[cfg(not(feature = "libm"))]
+    return Float::ln(x);
+}
+
+/// Cosine function.
+#[inline]
+pub(crate) fn cos(x: f64) -> f64 {
+    #[cfg(feature = "libm")]
+    return libm::cos(x);
+    #[cfg(not(feature = "libm"))]
+    return Float::cos(x);
+}
+
+/// Power function.
+#[inline]
+pub(crate) fn powf(x: f64, y: f64) -> f64 {
+    #[cfg(feature = "libm")]
+    return libm::pow(x, y);
+    #[cfg(not(feature = "libm"))]
+    return Float::powf(x, y);
+}
+
+/// Round half away from zero.
+#[inline]
+pub(crate) fn round(x: f64) -> f64 {
+    #[cfg(feature = "libm")]
+    return libm::round(x);
+    #[cfg(not(feature = "libm"))]
+    return Float::round(x);
+}
+
+// Number of the independent accumulators, which let the compiler vectorize the
+// floating-point sum without reordering a single accumulator.
+const LANES: usize = 4;
+
+/// Squared Euclidean distance between `a` and `b`, where the difference of
+/// each variable is multiplied by `scale` if provided.
+///
+/// The sum is accumulated in [`LANES`] independent lanes over the contiguous
+/// chunks, so the compiler can auto-vectorize (SIMD) it instead of a serial
+/// dependency chain. The result only differs in the rounding errors.
+pub(crate) fn dist2(a: &[f64], b: &[f64], scale: Option<&[f64]>) -> f64 {
+    debug_assert_eq!(a.len(), b.len());
+    match scale {
+        Some(scale) => lanes_sum(a, b, scale, |a, b, w| (a - b) * w),
+        None => lanes_sum(a, b, b, |a, b, _| a - b),
+    }
+}
+
+#[inline(always)]
+fn lanes_sum(a: &[f64], b: &[f64], w: &[f64], diff: impl Fn(f64, f64, f64) -> f64) -> f64 {
+    let mut acc = [0.; LANES];
+    let a_iter = a.chunks_exact(LANES);
+    let b_iter = b.chunks_exact(LANES);
+    let w_iter = w.chunks_exact(LANES);
+    let tail = zip(
+        a_iter.remainder(),
+        zip(b_iter.remainder(), w_iter.remainder()),
+    )
+    .map(|(a, (b, w))| diff(*a, *b, *w))
+    .fold(0., |acc, d| acc + d * d);
+    for (a, (b, w)) in zip(a_iter, zip(b_iter, w_iter)) {
+        for l in 0..LANES {
+            let d = diff(a[l], b[l], w[l]);
+            acc[l] += d * d;
+        }
+    }
+    acc.iter().sum::<f64>() + tail
+}
+
+/// Eigendecomposition of the symmetric matrix `a` by the cyclic Jacobi
+/// method, returns the eigenvalues and the eigenvectors in the columns.
+///
+/// The sweeps are stopped after the off-diagonal elements are vanished or at
+/// most 50 sweeps.
+pub(crate) fn eigh(mut a: Vec<Vec<f64>>) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = a.len();
+    let mut v = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1. } else { 0. }).collect())
+        .collect::<Vec<Vec<_>>>();
+    for _ in 0..50 {
+        let off = (0..n)
+            .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+            .map(|(i, j)| a[i][j] * a[i][j])
+            .sum::<f64>();
+        if off <= f64::MIN_POSITIVE {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                if a[p][q] == 0. {
+                    continue;
+                }
+                // Rotation that vanishes `a[p][q]`
+                let theta = (a[q][q] - a[p][p]) / (2. * a[p][q]);
+                let t = theta.signum() / (theta.abs() + sqrt(theta * theta + 1.));
+                let c = 1. / sqrt(t * t + 1.);
+                let s = t * c;
+                for row in &mut a {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (head, tail) = a.split_at_mut(q);
+                for (apk, aqk) in zip(&mut head[p], &mut tail[0]) {
+                    (*apk, *aqk) = (c * *apk - s * *aqk, s * *apk + c * *aqk);
+                }
+                for row in &mut v {
+                    let (vp, vq) = (row[p], row[q]);
+                    row[p] = c * vp - s * vq;
+                    row[q] = s * vp + c * vq;
+                }
+            }
+        }
+    }
+    ((0..n).map(|i| a[i][i]).collect(), v)
+}
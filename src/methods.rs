@@ -3,15 +3,41 @@
 //! Each methods are also has some variants on implementation,
 //! current methods are just designed for application.
 pub use self::{
+    abc::Abc,
+    cma_es::CmaEs,
+    csa::Csa,
     de::{De, Strategy},
+    ensemble::Ensemble,
     fa::Fa,
-    pso::Pso,
+    hyper_heuristic::HyperHeuristic,
+    immigration::WithImmigration,
+    mfo::Mfo,
+    nsga2::NsgaII,
+    pattern::Pattern,
+    perm_ga::{PermCross, PermGa, PermMutate},
+    phased::Phased,
+    pso::{InitVel, Neighborhood, Pso},
     rga::Rga,
+    sa::{CoolingSchedule, Sa},
+    ssa::Ssa,
     tlbo::Tlbo,
 };
 
+pub mod abc;
+pub mod cma_es;
+pub mod csa;
 pub mod de;
+pub mod ensemble;
 pub mod fa;
+pub mod hyper_heuristic;
+pub mod immigration;
+pub mod mfo;
+pub mod nsga2;
+pub mod pattern;
+pub mod perm_ga;
+pub mod phased;
 pub mod pso;
 pub mod rga;
+pub mod sa;
+pub mod ssa;
 pub mod tlbo;
@@ -0,0 +1,180 @@
+//! # Artificial Bee Colony
+//!
+//! <https://doi.org/10.1007/s10898-007-9149-x>
+use crate::prelude::*;
+use alloc::vec::Vec;
+
+const DEF: Abc = Abc { limit: 100 };
+
+/// Artificial Bee Colony settings.
+///
+/// Each individual is a food source. In each generation:
+/// + The employed bees search around their food sources.
+/// + The onlooker bees select the food sources by the roulette wheel, and
+///   search around them.
+/// + The scout bees abandon the food sources that are not improved after
+///   `limit` trials, and reinitialize them uniformly in the bounds.
+///
+/// Since the fitness values are only comparable, the selection probability of
+/// the roulette wheel is proportional to the rank of the food source, where
+/// the best (the lowest cost) has the largest weight. The rank follows the
+/// shared fitness if [`SolverBuilder::fitness_sharing()`] is enabled.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Abc {
+    /// Abandonment limit of the trials
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.limit))]
+    pub limit: u64,
+}
+
+impl Abc {
+    /// Constant default value.
+    pub const fn new() -> Self {
+        DEF
+    }
+
+    /// Abandonment limit, the number of the trials without improvement before
+    /// a food source is abandoned.
+    pub fn limit(self, limit: u64) -> Self {
+        Self { limit }
+    }
+}
+
+impl Default for Abc {
+    fn default() -> Self {
+        DEF
+    }
+}
+
+impl AlgCfg for Abc {
+    type Algorithm<F: ObjFunc> = Method;
+    fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+        Method { abc: self, trials: Vec::new() }
+    }
+    fn pop_num() -> usize {
+        50
+    }
+    fn min_pop_num() -> usize {
+        2
+    }
+}
+
+/// Algorithm of the Artificial Bee Colony.
+pub struct Method {
+    abc: Abc,
+    trials: Vec<u64>,
+}
+
+impl core::ops::Deref for Method {
+    type Target = Abc;
+
+    fn deref(&self) -> &Self::Target {
+        &self.abc
+    }
+}
+
+impl Method {
+    /// The numbers of the trials without improvement of the food sources.
+    pub fn trials(&self) -> &[u64] {
+        &self.trials
+    }
+
+    // Search around the food source `i` with the random neighbor
+    fn search<F: ObjFunc>(ctx: &Ctx<F>, rng: &mut Rng, i: usize) -> (Vec<f64>, F::Ys) {
+        let k = (i + 1 + rng.ub(ctx.pop_num() - 1)) % ctx.pop_num();
+        let s = rng.ub(ctx.dim());
+        let mut xs = ctx.pool[i].clone();
+        let v = xs[s] + rng.range(-1. ..=1.) * (xs[s] - ctx.pool[k][s]);
+        xs[s] = if ctx.clamp_to_bounds() {
+            let [min, max] = ctx.bound_of(s);
+            v.clamp(min, max)
+        } else {
+            v
+        };
+        let ys = ctx.fitness(&xs);
+        (xs, ys)
+    }
+
+    // Search around the food sources in order, and replace them greedily
+    fn forage<F: ObjFunc>(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng, sources: Vec<usize>) {
+        let streams = rng.stream(sources.len());
+        #[cfg(not(feature = "rayon"))]
+        let iter = streams.into_iter();
+        #[cfg(feature = "rayon")]
+        let iter = streams.into_par_iter().with_min_len(ctx.eval_chunk());
+        let ctx_ref = &*ctx;
+        let trials = iter
+            .zip(&sources)
+            .map(|(mut rng, &i)| Self::search(ctx_ref, &mut rng, i))
+            .collect::<Vec<_>>();
+        for (i, (xs, ys)) in sources.into_iter().zip(trials) {
+            if ctx.is_better(&ys, &ctx.pool_y[i]) {
+                ctx.set_from(i, xs, ys);
+                self.trials[i] = 0;
+            } else {
+                self.trials[i] += 1;
+            }
+        }
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for Method {
+    fn init(&mut self, ctx: &mut Ctx<F>, _rng: &mut Rng) {
+        self.trials = alloc::vec![0; ctx.pop_num()];
+    }
+
+    fn name(&self) -> &'static str {
+        "ABC"
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        alloc::vec![("limit", self.limit as f64)]
+    }
+
+    fn evals_per_gen(&self, pop_num: usize) -> usize {
+        // Employed bees and onlooker bees, the scout bees are occasional
+        2 * pop_num
+    }
+
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let pop_num = ctx.pop_num();
+        self.trials.resize(pop_num, 0);
+        // Employed bees
+        self.forage(ctx, rng, (0..pop_num).collect());
+        // Onlooker bees, the rank weight is from `pop_num` (the best) to 1
+        let mut order = (0..pop_num).collect::<Vec<_>>();
+        match ctx.shared_fitness() {
+            Some(f) => order.sort_by(|&a, &b| f[b].total_cmp(&f[a])),
+            None => order.sort_by(|&a, &b| ctx.cmp_fitness(&ctx.pool_y[a], &ctx.pool_y[b])),
+        }
+        let total = pop_num * (pop_num + 1) / 2;
+        let sources = (0..pop_num)
+            .map(|_| {
+                let mut r = rng.ub(total);
+                for (rank, &i) in order.iter().enumerate() {
+                    let weight = pop_num - rank;
+                    if r < weight {
+                        return i;
+                    }
+                    r -= weight;
+                }
+                unreachable!()
+            })
+            .collect();
+        self.forage(ctx, rng, sources);
+        // Scout bees
+        for i in 0..pop_num {
+            if self.trials[i] > self.limit {
+                let xs = (0..ctx.dim())
+                    .map(|s| rng.range(ctx.bound_range(s)))
+                    .collect::<Vec<_>>();
+                let ys = ctx.fitness(&xs);
+                ctx.set_from(i, xs, ys);
+                self.trials[i] = 0;
+            }
+        }
+        ctx.find_best();
+    }
+}
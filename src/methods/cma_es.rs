@@ -0,0 +1,283 @@
+//! # Covariance Matrix Adaptation Evolution Strategy
+//!
+//! <https://arxiv.org/abs/1604.00772>
+//!
+//! This method require exponential, logarithm, square root and power
+//! functions.
+use crate::{math, prelude::*};
+use alloc::vec::Vec;
+use core::iter::zip;
+
+const DEF: CmaEs = CmaEs { sigma0: 0.3, mu: None };
+
+/// Covariance Matrix Adaptation Evolution Strategy settings.
+///
+/// The population is sampled from the multivariate normal distribution
+/// `N(m, sigma^2 C)` in each generation, then the mean `m`, the step size
+/// `sigma` and the covariance matrix `C` are updated by the `mu` best samples
+/// with the (μ/μ_w, λ) rank-one and rank-μ updates, where λ is the population
+/// number.
+///
+/// The mean is started from the centroid of the initial pool, and the
+/// covariance matrix is started from the squared widths of the bounds, so the
+/// step size is relative to the bounds. The previous pool is replaced by the
+/// samples in each generation.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct CmaEs {
+    /// Initial step size, relative to the widths of the bounds
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.sigma0))]
+    pub sigma0: f64,
+    /// Parent number, half of the population number by default
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub mu: Option<usize>,
+}
+
+impl CmaEs {
+    /// Constant default value.
+    pub const fn new() -> Self {
+        DEF
+    }
+
+    impl_builders! {
+        /// Initial step size.
+        fn sigma0(f64)
+    }
+
+    /// Parent number, the number of the best samples to update the
+    /// distribution, which is clamped to the population number.
+    ///
+    /// # Default
+    ///
+    /// By default, half of the population number is used.
+    pub fn mu(self, mu: usize) -> Self {
+        Self { mu: Some(mu), ..self }
+    }
+}
+
+impl Default for CmaEs {
+    fn default() -> Self {
+        DEF
+    }
+}
+
+impl AlgCfg for CmaEs {
+    type Algorithm<F: ObjFunc> = Method;
+    fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+        Method {
+            cma: self,
+            sigma: 0.,
+            mean: Vec::new(),
+            cov: Vec::new(),
+            b: Vec::new(),
+            d: Vec::new(),
+            ps: Vec::new(),
+            pc: Vec::new(),
+        }
+    }
+    fn pop_num() -> usize {
+        20
+    }
+    fn min_pop_num() -> usize {
+        2
+    }
+}
+
+/// Algorithm of the Covariance Matrix Adaptation Evolution Strategy.
+pub struct Method {
+    cma: CmaEs,
+    sigma: f64,
+    mean: Vec<f64>,
+    cov: Vec<Vec<f64>>,
+    // Eigenvectors in the columns and the square roots of the eigenvalues
+    b: Vec<Vec<f64>>,
+    d: Vec<f64>,
+    // Evolution paths of the step size and the covariance matrix
+    ps: Vec<f64>,
+    pc: Vec<f64>,
+}
+
+impl core::ops::Deref for Method {
+    type Target = CmaEs;
+
+    fn deref(&self) -> &Self::Target {
+        &self.cma
+    }
+}
+
+impl Method {
+    /// The current mean of the distribution.
+    pub fn mean(&self) -> &[f64] {
+        &self.mean
+    }
+
+    /// The current step size.
+    pub fn sigma(&self) -> f64 {
+        self.sigma
+    }
+
+    // The recombination weights of the parents
+    fn weights(&self, pop_num: usize) -> Vec<f64> {
+        let mu = self.mu.unwrap_or(pop_num / 2).clamp(1, pop_num.max(1));
+        let w = (1..=mu)
+            .map(|i| math::ln(mu as f64 + 0.5) - math::ln(i as f64))
+            .collect::<Vec<_>>();
+        let sum = w.iter().sum::<f64>();
+        w.into_iter().map(|w| w / sum).collect()
+    }
+
+    // Multiply `B * diag(d) * z`
+    fn transform(&self, z: &[f64]) -> Vec<f64> {
+        (self.b.iter())
+            .map(|row| zip(row, zip(&self.d, z)).map(|(b, (d, z))| b * d * z).sum())
+            .collect()
+    }
+
+    // Multiply `C^(-1/2) * y = B * diag(1 / d) * B^T * y`
+    fn whiten(&self, y: &[f64]) -> Vec<f64> {
+        let n = y.len();
+        let z = (0..n)
+            .map(|j| {
+                let d = self.d[j];
+                let v = (0..n).map(|i| self.b[i][j] * y[i]).sum::<f64>();
+                if d > 0. {
+                    v / d
+                } else {
+                    0.
+                }
+            })
+            .collect::<Vec<_>>();
+        (self.b.iter())
+            .map(|row| zip(row, &z).map(|(b, z)| b * z).sum())
+            .collect()
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for Method {
+    fn init(&mut self, ctx: &mut Ctx<F>, _rng: &mut Rng) {
+        let n = ctx.dim();
+        let pop_num = ctx.pop_num() as f64;
+        self.mean = (0..n)
+            .map(|s| ctx.pool.iter().map(|xs| xs[s]).sum::<f64>() / pop_num)
+            .collect();
+        self.sigma = self.sigma0;
+        self.d = ctx.bound().iter().map(|[min, max]| max - min).collect();
+        self.cov = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| if i == j { self.d[i] * self.d[i] } else { 0. })
+                    .collect()
+            })
+            .collect();
+        self.b = (0..n)
+            .map(|i| (0..n).map(|j| if i == j { 1. } else { 0. }).collect())
+            .collect();
+        self.ps = alloc::vec![0.; n];
+        self.pc = alloc::vec![0.; n];
+    }
+
+    fn name(&self) -> &'static str {
+        "CMA-ES"
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        let mut params = alloc::vec![("sigma0", self.sigma0)];
+        if let Some(mu) = self.mu {
+            params.push(("mu", mu as f64));
+        }
+        params
+    }
+
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let n = ctx.dim();
+        let nf = n as f64;
+        let weights = self.weights(ctx.pop_num());
+        let mu_eff = weights.iter().map(|w| w * w).sum::<f64>().recip();
+        // Learning rates
+        let cs = (mu_eff + 2.) / (nf + mu_eff + 5.);
+        let ds = 1. + 2. * (math::sqrt((mu_eff - 1.) / (nf + 1.)) - 1.).max(0.) + cs;
+        let cc = (4. + mu_eff / nf) / (nf + 4. + 2. * mu_eff / nf);
+        let c1 = 2. / ((nf + 1.3) * (nf + 1.3) + mu_eff);
+        let cmu =
+            (1. - c1).min(2. * (mu_eff - 2. + 1. / mu_eff) / ((nf + 2.) * (nf + 2.) + mu_eff));
+        // Expectation of the norm of the standard normal distribution
+        let chi_n = math::sqrt(nf) * (1. - 1. / (4. * nf) + 1. / (21. * nf * nf));
+        // Sample the population
+        let clamp = ctx.clamp_to_bounds();
+        let this = &*self;
+        let streams = rng.stream(ctx.pop_num());
+        #[cfg(not(feature = "rayon"))]
+        let iter = streams.into_iter();
+        #[cfg(feature = "rayon")]
+        let iter = streams.into_par_iter().with_min_len(ctx.eval_chunk());
+        let (pool, pool_y): (Vec<_>, Vec<_>) = iter
+            .map(|mut rng| {
+                let z = (0..n).map(|_| rng.normal(0., 1.)).collect::<Vec<_>>();
+                let y = this.transform(&z);
+                let xs = zip(&this.mean, zip(y, ctx.bound()))
+                    .map(|(m, (y, &[min, max]))| {
+                        let v = m + this.sigma * y;
+                        if clamp {
+                            v.clamp(min, max)
+                        } else {
+                            v
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                let ys = ctx.fitness(&xs);
+                (xs, ys)
+            })
+            .unzip();
+        ctx.pool = pool;
+        ctx.pool_y = pool_y;
+        ctx.find_best();
+        // Select the parents, the steps are recomputed from the repaired samples
+        let mut order = (0..ctx.pop_num()).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| ctx.cmp_fitness(&ctx.pool_y[a], &ctx.pool_y[b]));
+        let steps = (order.iter().take(weights.len()))
+            .map(|&i| {
+                zip(&ctx.pool[i], &self.mean)
+                    .map(|(x, m)| (x - m) / self.sigma)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let yw = (0..n)
+            .map(|s| zip(&weights, &steps).map(|(w, y)| w * y[s]).sum::<f64>())
+            .collect::<Vec<_>>();
+        for (m, y) in zip(&mut self.mean, &yw) {
+            *m += self.sigma * y;
+        }
+        // Evolution paths
+        let cy = self.whiten(&yw);
+        let a = math::sqrt(cs * (2. - cs) * mu_eff);
+        for (p, y) in zip(&mut self.ps, cy) {
+            *p = (1. - cs) * *p + a * y;
+        }
+        let ps_norm = math::sqrt(self.ps.iter().map(|p| p * p).sum::<f64>());
+        let decay = 1. - math::powf(1. - cs, 2. * ctx.gen.max(1) as f64);
+        let hs = ps_norm / math::sqrt(decay) / chi_n < 1.4 + 2. / (nf + 1.);
+        let hs = if hs { 1. } else { 0. };
+        let a = hs * math::sqrt(cc * (2. - cc) * mu_eff);
+        for (p, y) in zip(&mut self.pc, &yw) {
+            *p = (1. - cc) * *p + a * y;
+        }
+        // Rank-one and rank-μ updates
+        let keep = 1. - c1 - cmu + c1 * (1. - hs) * cc * (2. - cc);
+        for i in 0..n {
+            for j in i..n {
+                let rank_mu = zip(&weights, &steps)
+                    .map(|(w, y)| w * y[i] * y[j])
+                    .sum::<f64>();
+                let v = keep * self.cov[i][j] + c1 * self.pc[i] * self.pc[j] + cmu * rank_mu;
+                self.cov[i][j] = v;
+                self.cov[j][i] = v;
+            }
+        }
+        self.sigma *= math::exp(cs / ds * (ps_norm / chi_n - 1.));
+        let (eigen, b) = math::eigh(self.cov.clone());
+        self.d = eigen.into_iter().map(|e| math::sqrt(e.max(0.))).collect();
+        self.b = b;
+    }
+}
@@ -0,0 +1,132 @@
+//! # Crow Search Algorithm
+//!
+//! <https://doi.org/10.1016/j.compstruc.2016.03.001>
+use crate::{obj_func::repair, prelude::*};
+use alloc::vec::Vec;
+
+const DEF: Csa = Csa { awareness: 0.1, flight_length: 2. };
+
+/// Crow Search Algorithm settings.
+///
+/// Each crow remembers the best position it found (the hidden food), and
+/// follows a random crow to steal its food. If the followed crow is not
+/// aware of it, the crow flies toward the memory of that crow, otherwise it
+/// is fooled to a random position in the bounds.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Csa {
+    /// Awareness probability
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.awareness))]
+    pub awareness: f64,
+    /// Flight length
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.flight_length))]
+    pub flight_length: f64,
+}
+
+impl Csa {
+    /// Constant default value.
+    pub const fn new() -> Self {
+        DEF
+    }
+
+    impl_builders! {
+        /// Awareness probability, the probability of a random flight.
+        fn awareness(f64)
+        /// Flight length, the step scale toward the memory of the followed
+        /// crow. The value greater than 1 leads to the global search.
+        fn flight_length(f64)
+    }
+}
+
+impl Default for Csa {
+    fn default() -> Self {
+        DEF
+    }
+}
+
+impl AlgCfg for Csa {
+    type Algorithm<F: ObjFunc> = Method<F::Ys>;
+    fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+        Method {
+            csa: self,
+            memory: Vec::new(),
+            memory_y: Vec::new(),
+        }
+    }
+    fn pop_num() -> usize {
+        50
+    }
+}
+
+/// Algorithm of the Crow Search Algorithm.
+pub struct Method<Y: Fitness> {
+    csa: Csa,
+    memory: Vec<Vec<f64>>,
+    memory_y: Vec<Y>,
+}
+
+impl<Y: Fitness> core::ops::Deref for Method<Y> {
+    type Target = Csa;
+
+    fn deref(&self) -> &Self::Target {
+        &self.csa
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for Method<F::Ys> {
+    fn init(&mut self, ctx: &mut Ctx<F>, _rng: &mut Rng) {
+        self.memory = ctx.pool.clone();
+        self.memory_y = ctx.pool_y.clone();
+    }
+
+    fn name(&self) -> &'static str {
+        "CSA"
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        alloc::vec![
+            ("awareness", self.awareness),
+            ("flight_length", self.flight_length),
+        ]
+    }
+
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let awareness = self.awareness;
+        let fl = self.flight_length;
+        let clamp = ctx.clamp_to_bounds();
+        let memory = &self.memory;
+        let rng = rng.stream(ctx.pop_num());
+        #[cfg(not(feature = "rayon"))]
+        let iter = rng.into_iter();
+        #[cfg(feature = "rayon")]
+        let iter = rng.into_par_iter().with_min_len(ctx.eval_chunk());
+        iter.zip(&mut ctx.pool)
+            .zip(&mut ctx.pool_y)
+            .for_each(|((mut rng, xs), ys)| {
+                if rng.maybe(awareness) {
+                    for (x, &[min, max]) in xs.iter_mut().zip(&ctx.bound) {
+                        *x = rng.range(min..=max);
+                    }
+                } else {
+                    let target = &memory[rng.ub(memory.len())];
+                    let r = rng.rand();
+                    for (s, &[min, max]) in ctx.bound.iter().enumerate() {
+                        let v = xs[s] + r * fl * (target[s] - xs[s]);
+                        xs[s] = if clamp { v.clamp(min, max) } else { v };
+                    }
+                }
+                repair(&ctx.func, xs);
+                *ys = ctx.func.fitness(xs);
+            });
+        ctx.log_evals(&ctx.pool, &ctx.pool_y);
+        for (i, ys) in ctx.pool_y.iter().enumerate() {
+            if ctx.is_better(ys, &self.memory_y[i]) {
+                self.memory[i].clone_from(&ctx.pool[i]);
+                self.memory_y[i] = ys.clone();
+            }
+        }
+        ctx.find_best();
+    }
+}
@@ -9,7 +9,13 @@ use alloc::{boxed::Box, vec::Vec};
 pub type Method = De;
 type Func<F> = Box<dyn Fn(&Ctx<F>, &[f64], usize) -> f64>;
 
-const DEF: De = De { strategy: C1F1, f: 0.6, cross: 0.9 };
+const DEF: De = De {
+    strategy: C1F1,
+    f: 0.6,
+    cross: 0.9,
+    p_best_rate: 0.05,
+    lambda: None,
+};
 
 /// The Differential Evolution strategy.
 ///
@@ -26,6 +32,21 @@ const DEF: De = De { strategy: C1F1, f: 0.6, cross: 0.9 };
 /// + *f3*: self{n} + F * (best{n} - self{n} + v0{n} - v1{n})
 /// + *f4*: best{n} + F * (v0{n} + v1{n} - v2{n} - v3{n})
 /// + *f5*: v4{n} + F * (v0{n} + v1{n} - v2{n} - v3{n})
+/// + *f6*: self{n} + F * (pbest{n} - self{n} + v0{n} - v1{n})
+///
+/// Where *pbest* is randomly chosen from the top `p_best_rate` individuals,
+/// also known as the "current-to-pbest" formula.
+///
+/// If the *lambda* coefficient is set by [`De::lambda()`], the formulas are
+/// interpolated between the random base and the best:
+///
+/// + *f1* and *f2*: base{n} + F * (v1{n} - v2{n})
+/// + *f3*: self{n} + lambda * (best{n} - self{n}) + F * (v0{n} - v1{n})
+/// + *f4* and *f5*: base{n} + F * (v1{n} + v2{n} - v3{n} - v4{n})
+/// + *f6*: self{n} + lambda * (pbest{n} - self{n}) + F * (v0{n} - v1{n})
+///
+/// Where base{n} = v0{n} + lambda * (best{n} - v0{n}), so zero *lambda* is
+/// the "rand" formula and one is the "best" formula.
 ///
 /// # Crossover formula
 ///
@@ -46,6 +67,8 @@ pub enum Strategy {
     C1F4,
     /// *f5* + *c1*
     C1F5,
+    /// *f6* + *c1*
+    C1F6,
     /// *f1* + *c2*
     C2F1,
     /// *f2* + *c2*
@@ -56,11 +79,15 @@ pub enum Strategy {
     C2F4,
     /// *f5* + *c2*
     C2F5,
+    /// *f6* + *c2*
+    C2F6,
 }
 
 impl Strategy {
     /// A list of all strategies.
-    pub const LIST: [Self; 10] = [C1F1, C1F2, C1F3, C1F4, C1F5, C2F1, C2F2, C2F3, C2F4, C2F5];
+    pub const LIST: [Self; 12] = [
+        C1F1, C1F2, C1F3, C1F4, C1F5, C1F6, C2F1, C2F2, C2F3, C2F4, C2F5, C2F6,
+    ];
 }
 
 /// Differential Evolution settings.
@@ -78,6 +105,12 @@ pub struct De {
     /// Crossover rate
     #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.cross))]
     pub cross: f64,
+    /// Rate of the top individuals to choose the *pbest* in the formula
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.p_best_rate))]
+    pub p_best_rate: f64,
+    /// Coefficient between the random base and the best in the formula
+    #[cfg_attr(feature = "clap", clap(long))]
+    pub lambda: Option<f64>,
 }
 
 impl De {
@@ -94,6 +127,32 @@ impl De {
         /// Crossing probability.
         fn cross(f64)
     }
+
+    /// Rate of the top individuals to choose the *pbest*, used in the *f6*
+    /// formula. At least one individual (the best) is chosen.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rate is not in the range `(0, 1]`.
+    pub fn p_best_rate(self, p_best_rate: f64) -> Self {
+        assert!(
+            p_best_rate > 0. && p_best_rate <= 1.,
+            "The pbest rate should be in (0, 1]"
+        );
+        Self { p_best_rate, ..self }
+    }
+
+    /// Coefficient between the random base and the best in the formula, see
+    /// [`Strategy`] for the interpolated formulas.
+    ///
+    /// The higher value converges faster but risks the premature convergence.
+    ///
+    /// # Default
+    ///
+    /// By default, the formula of the strategy is used as is.
+    pub fn lambda(self, lambda: f64) -> Self {
+        Self { lambda: Some(lambda), ..self }
+    }
 }
 
 impl Default for De {
@@ -110,10 +169,26 @@ impl AlgCfg for De {
     fn pop_num() -> usize {
         400
     }
+    fn min_pop_num() -> usize {
+        // The base and two differences are distinct individuals
+        5
+    }
 }
 
 impl Method {
-    fn formula<F: ObjFunc>(&self, ctx: &Ctx<F>, rng: &mut Rng) -> Func<F> {
+    // Indices of the top individuals, at least one
+    fn pbest<F: ObjFunc>(&self, ctx: &Ctx<F>) -> Vec<usize> {
+        let n = (self.p_best_rate * ctx.pop_num() as f64).round() as usize;
+        let mut ind = (0..ctx.pop_num()).collect::<Vec<_>>();
+        ind.sort_unstable_by(|&i, &j| ctx.cmp_fitness(&ctx.pool_y[i], &ctx.pool_y[j]));
+        ind.truncate(n.clamp(1, ctx.pop_num()));
+        ind
+    }
+
+    fn formula<F: ObjFunc>(&self, ctx: &Ctx<F>, pbest: &[usize], rng: &mut Rng) -> Func<F> {
+        if let Some(lambda) = self.lambda {
+            return self.formula_lambda(ctx, pbest, rng, lambda);
+        }
         let f = self.f;
         match self.strategy {
             C1F1 | C2F1 => {
@@ -149,6 +224,68 @@ impl Method {
                             - ctx.pool[v3][s])
                 }
             }),
+            C1F6 | C2F6 => Box::new({
+                let [v0, v1] = rng.array(0..ctx.pop_num());
+                let pbest = pbest[rng.ub(pbest.len())];
+                move |ctx, xs, s| {
+                    xs[s] + f * (ctx.pool[pbest][s] - xs[s] + ctx.pool[v0][s] - ctx.pool[v1][s])
+                }
+            }),
+        }
+    }
+
+    fn formula_lambda<F: ObjFunc>(
+        &self,
+        ctx: &Ctx<F>,
+        pbest: &[usize],
+        rng: &mut Rng,
+        lambda: f64,
+    ) -> Func<F> {
+        let f = self.f;
+        match self.strategy {
+            C1F1 | C2F1 | C1F2 | C2F2 => Box::new({
+                let [v0, v1, v2] = rng.array(0..ctx.pop_num());
+                let best = ctx.best.sample_xs(rng).to_vec();
+                move |ctx, _, s| {
+                    let base = ctx.pool[v0][s];
+                    base + lambda * (best[s] - base) + f * (ctx.pool[v1][s] - ctx.pool[v2][s])
+                }
+            }),
+            C1F3 | C2F3 => Box::new({
+                let [v0, v1] = rng.array(0..ctx.pop_num());
+                let best = ctx.best.sample_xs(rng).to_vec();
+                move |ctx, xs, s| {
+                    xs[s] + lambda * (best[s] - xs[s]) + f * (ctx.pool[v0][s] - ctx.pool[v1][s])
+                }
+            }),
+            C1F4 | C2F4 | C1F5 | C2F5 => Box::new({
+                let [v0, v1, v2, v3, v4] = rng.array(0..ctx.pop_num());
+                let best = ctx.best.sample_xs(rng).to_vec();
+                move |ctx, _, s| {
+                    let base = ctx.pool[v0][s];
+                    base + lambda * (best[s] - base)
+                        + f * (ctx.pool[v1][s] + ctx.pool[v2][s]
+                            - ctx.pool[v3][s]
+                            - ctx.pool[v4][s])
+                }
+            }),
+            C1F6 | C2F6 => Box::new({
+                let [v0, v1] = rng.array(0..ctx.pop_num());
+                let pbest = pbest[rng.ub(pbest.len())];
+                move |ctx, xs, s| {
+                    let attract = lambda * (ctx.pool[pbest][s] - xs[s]);
+                    xs[s] + attract + f * (ctx.pool[v0][s] - ctx.pool[v1][s])
+                }
+            }),
+        }
+    }
+
+    // Re-sample the variable if it is out of the bounds
+    fn repair<F: ObjFunc>(&self, ctx: &Ctx<F>, rng: &mut Rng, s: usize, v: f64) -> f64 {
+        if ctx.clamp_to_bounds() {
+            rng.clamp(v, ctx.bound_range(s))
+        } else {
+            v
         }
     }
 
@@ -162,7 +299,7 @@ impl Method {
             if i >= 1 && !rng.maybe(self.cross) {
                 break;
             }
-            xs[s] = rng.clamp(formula(ctx, xs, s), ctx.bound_range(s));
+            xs[s] = self.repair(ctx, rng, s, formula(ctx, xs, s));
         }
     }
 
@@ -174,49 +311,71 @@ impl Method {
         let sss = rng.ub(ctx.dim());
         for s in 0..ctx.dim() {
             if sss == s || rng.maybe(self.cross) {
-                xs[s] = rng.clamp(formula(ctx, xs, s), ctx.bound_range(s));
+                xs[s] = self.repair(ctx, rng, s, formula(ctx, xs, s));
             }
         }
     }
 }
 
 impl<F: ObjFunc> Algorithm<F> for Method {
+    fn name(&self) -> &'static str {
+        "DE"
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        let mut params = alloc::vec![
+            ("f", self.f),
+            ("cross", self.cross),
+            ("p_best_rate", self.p_best_rate),
+        ];
+        if let Some(lambda) = self.lambda {
+            params.push(("lambda", lambda));
+        }
+        params
+    }
+
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
     fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
-        let mut pool = ctx.pool.clone();
-        let mut pool_y = ctx.pool_y.clone();
-        let rng = rng.stream(ctx.pop_num());
+        let pbest = match self.strategy {
+            C1F6 | C2F6 => self.pbest(ctx),
+            _ => Vec::new(),
+        };
+        let streams = rng.stream(ctx.pop_num());
         #[cfg(not(feature = "rayon"))]
-        let iter = rng.into_iter();
+        let iter = streams.into_iter();
         #[cfg(feature = "rayon")]
-        let iter = rng.into_par_iter();
-        let (xs, ys): (Vec<_>, Vec<_>) = iter
-            .zip(&mut pool)
-            .zip(&mut pool_y)
-            .filter_map(|((mut rng, xs), ys)| {
+        let iter = streams.into_par_iter().with_min_len(ctx.eval_chunk());
+        let trials = iter
+            .zip(&ctx.pool)
+            .map(|(mut rng, xs)| {
                 // Generate Vector
-                let formula = self.formula(ctx, &mut rng);
+                let formula = self.formula(ctx, &pbest, &mut rng);
                 // Recombination
                 let mut xs_trial = xs.clone();
                 match self.strategy {
-                    C1F1 | C1F2 | C1F3 | C1F4 | C1F5 => {
+                    C1F1 | C1F2 | C1F3 | C1F4 | C1F5 | C1F6 => {
                         self.c1(ctx, &mut rng, &mut xs_trial, formula)
                     }
-                    C2F1 | C2F2 | C2F3 | C2F4 | C2F5 => {
+                    C2F1 | C2F2 | C2F3 | C2F4 | C2F5 | C2F6 => {
                         self.c2(ctx, &mut rng, &mut xs_trial, formula)
                     }
                 }
                 let ys_trial = ctx.fitness(&xs_trial);
-                if ys_trial.is_dominated(ys) {
-                    *xs = xs_trial;
-                    *ys = ys_trial;
-                    Some((&*xs, &*ys))
-                } else {
-                    None
-                }
+                (xs_trial, ys_trial)
             })
-            .unzip();
-        ctx.best.update_all(xs, ys);
-        ctx.pool = pool;
-        ctx.pool_y = pool_y;
+            .collect::<Vec<_>>();
+        // Replacement, the accepted offspring may be replaced again
+        let mut xs_new = Vec::new();
+        let mut ys_new = Vec::new();
+        for (i, (xs, ys)) in trials.into_iter().enumerate() {
+            if let Some(i) = ctx.replace(i, xs, ys, rng) {
+                xs_new.push(ctx.pool[i].clone());
+                ys_new.push(ctx.pool_y[i].clone());
+            }
+        }
+        ctx.best.update_all(&xs_new, &ys_new);
     }
 }
@@ -0,0 +1,173 @@
+//! # Ensemble
+//!
+//! A meta-method that partitions the population for multiple methods. All
+//! methods share the same context and the best set in each generation.
+use crate::prelude::*;
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    iter::zip,
+    mem::{replace, take},
+};
+
+/// The boxed algorithm of the meta-methods.
+pub type BoxAlg<F> = Box<dyn Algorithm<F>>;
+
+/// Algorithm of the Ensemble.
+///
+/// The shares are normalized to partition the population in order. For
+/// example, a DE+PSO ensemble of 200 individuals:
+///
+/// ```
+/// use metaheuristics_nature::{ensemble::Ensemble, De, Pso, Solver};
+/// # use metaheuristics_nature::tests::TestObj as MyFunc;
+///
+/// let method = Ensemble::new()
+///     .add(De::default(), 0.5)
+///     .add(Pso::default(), 0.5);
+/// let s = Solver::build_algorithm(method, MyFunc::new())
+///     .seed(0)
+///     .pop_num(200)
+///     .task(|ctx| ctx.gen == 20)
+///     .solve();
+/// ```
+///
+/// The partitions below the minimum population number of their methods
+/// ([`AlgCfg::min_pop_num()`]) are enlarged from the largest surplus, and the
+/// initialization panics if the population is less than the sum of the
+/// minimums. The minimum of the boxed methods is unknown, so please note that
+/// their partitions should satisfy the requirement of the methods.
+pub struct Ensemble<F: ObjFunc> {
+    members: Vec<(BoxAlg<F>, f64)>,
+    // The minimum population number of each method
+    mins: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl<F: ObjFunc> Default for Ensemble<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: ObjFunc> Ensemble<F> {
+    /// Create an empty ensemble.
+    pub fn new() -> Self {
+        Self {
+            members: Vec::new(),
+            mins: Vec::new(),
+            sizes: Vec::new(),
+        }
+    }
+
+    /// Create from a list of the methods and their shares.
+    pub fn from_list(members: Vec<(BoxAlg<F>, f64)>) -> Self {
+        let mins = alloc::vec![0; members.len()];
+        Self { members, mins, sizes: Vec::new() }
+    }
+
+    /// Add a method from its setting with the share of the population.
+    ///
+    /// The partition is at least the minimum population number of the method,
+    /// see [`AlgCfg::min_pop_num()`].
+    pub fn add<A: AlgCfg>(mut self, cfg: A, share: f64) -> Self {
+        self.members.push((Box::new(cfg.algorithm()), share));
+        self.mins.push(A::min_pop_num());
+        self
+    }
+
+    /// Add a boxed method with the share of the population.
+    pub fn add_boxed(mut self, method: BoxAlg<F>, share: f64) -> Self {
+        self.members.push((method, share));
+        self.mins.push(0);
+        self
+    }
+
+    /// Get the partition sizes of the population.
+    ///
+    /// The sizes are decided at the initialization.
+    pub fn sizes(&self) -> &[usize] {
+        &self.sizes
+    }
+
+    // Partition the population by the shares
+    fn partition(&self, pop_num: usize) -> Vec<usize> {
+        assert!(
+            self.mins.iter().sum::<usize>() <= pop_num,
+            "The population is less than the minimums of the ensemble methods"
+        );
+        let total = self.members.iter().map(|(_, share)| share).sum::<f64>();
+        let n = pop_num as f64;
+        let mut acc = 0.;
+        let mut start = 0;
+        let mut sizes = (self.members.iter())
+            .map(|(_, share)| {
+                acc += share;
+                let end = ((acc / total * n).round() as usize).max(start);
+                end - replace(&mut start, end)
+            })
+            .collect::<Vec<_>>();
+        // Move the individuals from the largest surplus to the lacking ones
+        for i in 0..sizes.len() {
+            while sizes[i] < self.mins[i] {
+                let (j, _) = zip(&sizes, &self.mins)
+                    .enumerate()
+                    .max_by_key(|(_, (size, min))| size.saturating_sub(**min))
+                    .unwrap();
+                sizes[j] -= 1;
+                sizes[i] += 1;
+            }
+        }
+        sizes
+    }
+
+    // Run each method on its partition
+    fn each<C>(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng, mut f: C)
+    where
+        C: FnMut(&mut BoxAlg<F>, &mut Ctx<F>, &mut Rng),
+    {
+        let mut pool = take(&mut ctx.pool);
+        let mut pool_y = take(&mut ctx.pool_y);
+        let mut new_pool = Vec::with_capacity(pool.len());
+        let mut new_pool_y = Vec::with_capacity(pool_y.len());
+        for ((method, _), &size) in self.members.iter_mut().zip(&self.sizes) {
+            let rest = pool.split_off(size);
+            let rest_y = pool_y.split_off(size);
+            ctx.pool = replace(&mut pool, rest);
+            ctx.pool_y = replace(&mut pool_y, rest_y);
+            f(method, ctx, rng);
+            new_pool.append(&mut ctx.pool);
+            new_pool_y.append(&mut ctx.pool_y);
+        }
+        ctx.pool = new_pool;
+        ctx.pool_y = new_pool_y;
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for Ensemble<F> {
+    fn init(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        assert!(!self.members.is_empty(), "Ensemble should have methods");
+        self.sizes = self.partition(ctx.pop_num());
+        self.each(ctx, rng, |method, ctx, rng| method.init(ctx, rng));
+    }
+
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        self.each(ctx, rng, |method, ctx, rng| method.generation(ctx, rng));
+    }
+
+    fn name(&self) -> &'static str {
+        "Ensemble"
+    }
+
+    fn evals_per_gen(&self, pop_num: usize) -> usize {
+        zip(&self.members, self.partition(pop_num))
+            .map(|((method, _), size)| method.evals_per_gen(size))
+            .sum()
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        let total = self.members.iter().map(|(_, share)| share).sum::<f64>();
+        (self.members.iter())
+            .map(|(method, share)| (method.name(), share / total))
+            .collect()
+    }
+}
@@ -3,14 +3,16 @@
 //! <https://en.wikipedia.org/wiki/Firefly_algorithm>
 //!
 //! This method require exponential function.
-use crate::prelude::*;
+use crate::{math, prelude::*};
 use alloc::vec::Vec;
 use core::iter::zip;
 
 /// Algorithm of the Firefly Algorithm.
 pub type Method = Fa;
 
-const DEF: Fa = Fa { alpha: 1., beta_min: 1., gamma: 0.01 };
+// Bound of the exponent, `exp()` is overflowed beyond `ln(f64::MAX)`.
+const EXP_BOUND: f64 = 700.;
+const DEF: Fa = Fa { alpha: 1., beta_min: 1., gamma: 5. };
 
 /// Firefly Algorithm settings.
 #[derive(Clone, PartialEq)]
@@ -24,7 +26,9 @@ pub struct Fa {
     /// Min beta value
     #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.beta_min))]
     pub beta_min: f64,
-    /// Gamma factor
+    /// Gamma factor, the light absorption of the squared distance, which is
+    /// normalized by the squared diagonal of the bounds into `[0, 1]`, so the
+    /// attraction decays to `exp(-gamma)` at the farthest distance
     #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.gamma))]
     pub gamma: f64,
 }
@@ -40,7 +44,8 @@ impl Fa {
         fn alpha(f64)
         /// Minimum beta factor.
         fn beta_min(f64)
-        /// Gamma factor.
+        /// Gamma factor, the attraction decays to `exp(-gamma)` at the diagonal
+        /// of the bounds.
         fn gamma(f64)
     }
 }
@@ -66,23 +71,28 @@ impl Method {
         &self,
         ctx: &Ctx<F>,
         rng: &mut Rng,
+        diag2: f64,
         i: usize,
         j: usize,
     ) -> (Vec<f64>, F::Ys) {
-        let (i, j) = if ctx.pool_y[j].is_dominated(&ctx.pool_y[i]) {
+        let (i, j) = if ctx.is_better(&ctx.pool_y[j], &ctx.pool_y[i]) {
             (i, j)
         } else {
             (j, i)
         };
-        let r = zip(&ctx.pool[i], &ctx.pool[j])
-            .map(|(a, b)| a - b)
-            .fold(0., |acc, x| acc + x * x);
-        let beta = self.beta_min * (-self.gamma * r).exp();
+        // Squared distance normalized by the diagonal of the bounding box
+        let r = math::dist2(&ctx.pool[i], &ctx.pool[j], None) / diag2;
+        let beta = self.beta_min * math::exp((-self.gamma * r).clamp(-EXP_BOUND, EXP_BOUND));
         let xs = zip(ctx.bound(), zip(&ctx.pool[i], &ctx.pool[j]))
             .map(|(&[min, max], (a, b))| {
                 let step = self.alpha * (max - min) * rng.range(-0.5..0.5);
                 let surround = a + beta * (b - a);
-                (surround + step).clamp(min, max)
+                let v = surround + step;
+                if ctx.clamp_to_bounds() {
+                    v.clamp(min, max)
+                } else {
+                    v
+                }
             })
             .collect::<Vec<_>>();
         let ys = ctx.fitness(&xs);
@@ -91,22 +101,43 @@ impl Method {
 }
 
 impl<F: ObjFunc> Algorithm<F> for Method {
+    fn name(&self) -> &'static str {
+        "FA"
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        alloc::vec![
+            ("alpha", self.alpha),
+            ("beta_min", self.beta_min),
+            ("gamma", self.gamma)
+        ]
+    }
+
+    fn evals_per_gen(&self, pop_num: usize) -> usize {
+        // Each pair of the fireflies
+        pop_num * pop_num.saturating_sub(1) / 2
+    }
+
     fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
         // Move fireflies
+        let diag2 = (ctx.bound().iter())
+            .map(|[min, max]| (max - min) * (max - min))
+            .sum::<f64>()
+            .max(f64::MIN_POSITIVE);
         let mut pool = ctx.pool.clone();
         let mut pool_y = ctx.pool_y.clone();
         let rng = rng.stream(ctx.pop_num());
         #[cfg(not(feature = "rayon"))]
         let iter = rng.into_iter();
         #[cfg(feature = "rayon")]
-        let iter = rng.into_par_iter();
+        let iter = rng.into_par_iter().with_min_len(ctx.eval_chunk());
         iter.zip(&mut pool)
             .zip(&mut pool_y)
             .enumerate()
             .for_each(|(i, ((mut rng, xs), ys))| {
                 for j in i + 1..ctx.pop_num() {
-                    let (xs_new, ys_new) = self.move_firefly(ctx, &mut rng, i, j);
-                    if ys_new.is_dominated(ys) {
+                    let (xs_new, ys_new) = self.move_firefly(ctx, &mut rng, diag2, i, j);
+                    if ctx.is_better(&ys_new, ys) {
                         *xs = xs_new;
                         *ys = ys_new;
                     }
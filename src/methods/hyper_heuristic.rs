@@ -0,0 +1,228 @@
+//! # Hyper-heuristic
+//!
+//! A meta-method that selects a low-level heuristic for each generation by
+//! its recent performance, which is a multi-armed bandit problem. All
+//! heuristics share the same context, the selected one runs on the whole
+//! population.
+use super::ensemble::BoxAlg;
+use crate::prelude::*;
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
+
+/// The selection policy of the heuristics, see [`HyperHeuristic::policy()`].
+///
+/// The credit of a heuristic is the improvement of the best cost per
+/// evaluation, averaged over its recent generations.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Policy {
+    /// Choose a random heuristic with the probability, otherwise the one with
+    /// the highest credit
+    EpsilonGreedy(f64),
+    /// Choose the one with the highest upper confidence bound (UCB1), the
+    /// value is the exploration factor, and the credits are normalized by the
+    /// highest one
+    Ucb(f64),
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::EpsilonGreedy(0.1)
+    }
+}
+
+/// Algorithm of the Hyper-heuristic.
+///
+/// Each heuristic is applied once before the selection, then the policy
+/// decides. A heuristic is initialized with the current population when it
+/// is switched to. For example, a DE/PSO/FA controller:
+///
+/// ```
+/// use metaheuristics_nature::{hyper_heuristic::HyperHeuristic, De, Fa, Pso, Solver};
+/// # use metaheuristics_nature::tests::TestObj as MyFunc;
+///
+/// let method = HyperHeuristic::new()
+///     .with(De::default())
+///     .with(Pso::default())
+///     .with(Fa::default());
+/// let s = Solver::build_algorithm(method, MyFunc::new())
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .solve();
+/// ```
+///
+/// The fitness evaluation value should be convertible to `f64` for the
+/// credits. Please note that the population should satisfy the requirement of
+/// all heuristics, such as the minimum population number.
+pub struct HyperHeuristic<F: ObjFunc> {
+    heuristics: Vec<BoxAlg<F>>,
+    policy: Policy,
+    window: usize,
+    credits: Vec<VecDeque<f64>>,
+    usage: Vec<u64>,
+    current: Option<usize>,
+}
+
+impl<F: ObjFunc> Default for HyperHeuristic<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: ObjFunc> HyperHeuristic<F> {
+    /// Create an empty controller.
+    pub fn new() -> Self {
+        Self::from_list(Vec::new())
+    }
+
+    /// Create from a list of the heuristics.
+    pub fn from_list(heuristics: Vec<BoxAlg<F>>) -> Self {
+        Self {
+            heuristics,
+            policy: Policy::default(),
+            window: 10,
+            credits: Vec::new(),
+            usage: Vec::new(),
+            current: None,
+        }
+    }
+
+    /// Selection policy of the heuristics.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the probability of [`Policy::EpsilonGreedy`] is not in the
+    /// range `[0, 1]`.
+    ///
+    /// # Default
+    ///
+    /// By default, it is [`Policy::EpsilonGreedy`] with 0.1 probability.
+    pub fn policy(self, policy: Policy) -> Self {
+        if let Policy::EpsilonGreedy(eps) = policy {
+            assert!((0. ..=1.).contains(&eps), "The epsilon should be in [0, 1]");
+        }
+        Self { policy, ..self }
+    }
+
+    /// Number of the recent generations of each heuristic to average its
+    /// credit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is zero.
+    ///
+    /// # Default
+    ///
+    /// By default, the window is 10 generations.
+    pub fn window(self, window: usize) -> Self {
+        assert!(window > 0, "Credit window should be positive");
+        Self { window, ..self }
+    }
+
+    /// Add a heuristic from its setting.
+    pub fn with<A: AlgCfg>(self, cfg: A) -> Self {
+        self.with_boxed(Box::new(cfg.algorithm()))
+    }
+
+    /// Add a boxed heuristic.
+    pub fn with_boxed(mut self, heuristic: BoxAlg<F>) -> Self {
+        self.heuristics.push(heuristic);
+        self
+    }
+
+    /// Get the number of the generations applied by each heuristic.
+    pub fn usage(&self) -> &[u64] {
+        &self.usage
+    }
+
+    /// Get the index of the last applied heuristic.
+    pub fn current(&self) -> Option<usize> {
+        self.current
+    }
+
+    // The average credit of each heuristic
+    fn means(&self) -> Vec<f64> {
+        (self.credits.iter())
+            .map(|c| c.iter().sum::<f64>() / c.len() as f64)
+            .collect()
+    }
+
+    fn select(&self, rng: &mut Rng) -> usize {
+        // Apply each heuristic once
+        if let Some(i) = self.credits.iter().position(VecDeque::is_empty) {
+            return i;
+        }
+        let means = self.means();
+        let argmax = |v: &[f64]| (0..v.len()).max_by(|a, b| v[*a].total_cmp(&v[*b])).unwrap();
+        match self.policy {
+            Policy::EpsilonGreedy(eps) if rng.maybe(eps) => rng.ub(self.heuristics.len()),
+            Policy::EpsilonGreedy(_) => argmax(&means),
+            Policy::Ucb(c) => {
+                let scale = means.iter().fold(0., |a: f64, b| a.max(*b));
+                let scale = if scale > 0. { scale.recip() } else { 0. };
+                let total = self.usage.iter().sum::<u64>() as f64;
+                let ucb = (means.iter().zip(&self.usage))
+                    .map(|(m, n)| {
+                        m * scale + c * crate::math::sqrt(2. * crate::math::ln(total) / *n as f64)
+                    })
+                    .collect::<Vec<_>>();
+                argmax(&ucb)
+            }
+        }
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for HyperHeuristic<F>
+where
+    <F::Ys as Fitness>::Eval: Into<f64>,
+{
+    fn init(&mut self, _ctx: &mut Ctx<F>, _rng: &mut Rng) {
+        assert!(
+            !self.heuristics.is_empty(),
+            "Hyper-heuristic should have methods"
+        );
+        let n = self.heuristics.len();
+        self.credits = (0..n)
+            .map(|_| VecDeque::with_capacity(self.window))
+            .collect();
+        self.usage = alloc::vec![0; n];
+        self.current = None;
+    }
+
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let i = self.select(rng);
+        if self.current != Some(i) {
+            self.heuristics[i].init(ctx, rng);
+            self.current = Some(i);
+        }
+        let before = ctx.best_cost();
+        self.heuristics[i].generation(ctx, rng);
+        let evals = self.heuristics[i].evals_per_gen(ctx.pop_num()).max(1);
+        let credit = (before - ctx.best_cost()).max(0.) / evals as f64;
+        let credits = &mut self.credits[i];
+        if credits.len() == self.window {
+            credits.pop_front();
+        }
+        // An infinite improvement from the infeasible solutions
+        credits.push_back(if credit.is_finite() { credit } else { f64::MAX });
+        self.usage[i] += 1;
+    }
+
+    fn name(&self) -> &'static str {
+        "Hyper-heuristic"
+    }
+
+    fn evals_per_gen(&self, pop_num: usize) -> usize {
+        (self.heuristics.iter())
+            .map(|method| method.evals_per_gen(pop_num))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        let policy = match self.policy {
+            Policy::EpsilonGreedy(eps) => ("epsilon", eps),
+            Policy::Ucb(c) => ("ucb", c),
+        };
+        alloc::vec![policy, ("window", self.window as f64)]
+    }
+}
@@ -0,0 +1,131 @@
+//! # Random Immigrants
+//!
+//! A meta-method that wraps another method, and replaces the worst
+//! individuals with the random immigrants periodically, which maintains the
+//! diversity of the population, especially for the dynamic objectives.
+use crate::{ctx::fitness_all, prelude::*};
+use alloc::vec::Vec;
+use core::iter::zip;
+
+/// Algorithm of the Random Immigrants.
+///
+/// After each generation of the inner method, the worst `k` individuals are
+/// replaced with the uniform random individuals in the effective bounds every
+/// `interval` generations. The best individual is never replaced, and the
+/// state of the inner method is kept. It can wrap either a method setting or
+/// an algorithm instance:
+///
+/// ```
+/// use metaheuristics_nature::{immigration::WithImmigration, Pso, Solver};
+/// # use metaheuristics_nature::tests::TestObj as MyFunc;
+///
+/// let method = WithImmigration::new(Pso::default()).k(5).interval(2);
+/// let s = Solver::build(method, MyFunc::new())
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .solve();
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct WithImmigration<A> {
+    inner: A,
+    k: usize,
+    interval: u64,
+}
+
+impl<A> WithImmigration<A> {
+    /// Wrap a method setting ([`AlgCfg`]) or an algorithm ([`Algorithm`]).
+    pub const fn new(inner: A) -> Self {
+        Self { inner, k: 10, interval: 1 }
+    }
+
+    impl_builders! {
+        /// Number of the immigrants.
+        ///
+        /// # Default
+        ///
+        /// By default, 10 individuals are replaced.
+        fn k(usize)
+    }
+
+    /// Interval of the generations between the immigrations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    ///
+    /// # Default
+    ///
+    /// By default, the immigration occurs in each generation.
+    pub fn interval(self, interval: u64) -> Self {
+        assert!(interval > 0, "Immigration interval should be positive");
+        Self { interval, ..self }
+    }
+
+    /// Get the inner method.
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+}
+
+impl<C: AlgCfg> AlgCfg for WithImmigration<C> {
+    type Algorithm<F: ObjFunc> = WithImmigration<C::Algorithm<F>>;
+    fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+        let Self { inner, k, interval } = self;
+        WithImmigration { inner: inner.algorithm(), k, interval }
+    }
+    fn pop_num() -> usize {
+        C::pop_num()
+    }
+    fn min_pop_num() -> usize {
+        C::min_pop_num()
+    }
+    fn max_gen(pop_num: usize, dim: usize) -> u64 {
+        C::max_gen(pop_num, dim)
+    }
+}
+
+impl<F: ObjFunc, A: Algorithm<F>> Algorithm<F> for WithImmigration<A> {
+    fn init(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        self.inner.init(ctx, rng);
+    }
+
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        self.inner.generation(ctx, rng);
+        // Keep the best individual
+        let k = self.k.min(ctx.pop_num() - 1);
+        if k == 0 || !ctx.gen.is_multiple_of(self.interval) {
+            return;
+        }
+        let mut ind = (0..ctx.pop_num()).collect::<Vec<_>>();
+        ind.sort_unstable_by(|&a, &b| ctx.cmp_fitness(&ctx.pool_y[b], &ctx.pool_y[a]));
+        ind.truncate(k);
+        let pool = (ind.iter())
+            .map(|_| {
+                (0..ctx.dim())
+                    .map(|s| rng.range(ctx.bound_range(s)))
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+        let pool_y = fitness_all(&ctx.func, &pool, ctx.eval_chunk, ctx.eval_order);
+        ctx.log_evals(&pool, &pool_y);
+        for ((i, xs), ys) in zip(zip(ind, pool), pool_y) {
+            ctx.best.update(&xs, &ys);
+            ctx.set_from(i, xs, ys);
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn evals_per_gen(&self, pop_num: usize) -> usize {
+        self.inner.evals_per_gen(pop_num) + self.k.min(pop_num.saturating_sub(1))
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        let mut params = self.inner.params();
+        params.push(("immigrants", self.k as f64));
+        params.push(("interval", self.interval as f64));
+        params
+    }
+}
@@ -0,0 +1,142 @@
+//! # Moth-Flame Optimization
+//!
+//! <https://doi.org/10.1016/j.knosys.2015.07.006>
+//!
+//! This method require exponential and cosine functions.
+use crate::{math, obj_func::repair, prelude::*};
+use alloc::vec::Vec;
+use core::f64::consts::TAU;
+
+const DEF: Mfo = Mfo { b: 1. };
+
+/// Moth-Flame Optimization settings.
+///
+/// Each moth flies around its flame along a logarithmic spiral. The flames
+/// are the best solutions found so far, sorted from the best to the worst.
+/// The number of the flames decreases linearly from the population number to
+/// one by [`Ctx::progress()`], and the rest moths share the last flame.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Mfo {
+    /// Spiral constant, the shape of the logarithmic spiral
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.b))]
+    pub b: f64,
+}
+
+impl Mfo {
+    /// Constant default value.
+    pub const fn new() -> Self {
+        DEF
+    }
+
+    /// Spiral constant.
+    pub fn b(self, b: f64) -> Self {
+        Self { b }
+    }
+
+    /// The number of the flames for the population number `pop_num` at the
+    /// progress in `0..=1`.
+    pub fn flame_num(pop_num: usize, progress: f64) -> usize {
+        let n = pop_num as f64;
+        ((n - progress * (n - 1.)).round() as usize).clamp(1, pop_num.max(1))
+    }
+}
+
+impl Default for Mfo {
+    fn default() -> Self {
+        DEF
+    }
+}
+
+impl AlgCfg for Mfo {
+    type Algorithm<F: ObjFunc> = Method<F::Ys>;
+    fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+        Method {
+            mfo: self,
+            flames: Vec::new(),
+            flames_y: Vec::new(),
+        }
+    }
+    fn pop_num() -> usize {
+        50
+    }
+}
+
+/// Algorithm of the Moth-Flame Optimization.
+pub struct Method<Y: Fitness> {
+    mfo: Mfo,
+    flames: Vec<Vec<f64>>,
+    flames_y: Vec<Y>,
+}
+
+impl<Y: Fitness> core::ops::Deref for Method<Y> {
+    type Target = Mfo;
+
+    fn deref(&self) -> &Self::Target {
+        &self.mfo
+    }
+}
+
+impl<Y: Fitness> Method<Y> {
+    // Keep the best population number of the flames and the moths, sorted
+    fn update_flames<F: ObjFunc<Ys = Y>>(&mut self, ctx: &Ctx<F>) {
+        let mut flames = core::mem::take(&mut self.flames)
+            .into_iter()
+            .zip(core::mem::take(&mut self.flames_y))
+            .chain(ctx.pool.iter().cloned().zip(ctx.pool_y.iter().cloned()))
+            .collect::<Vec<_>>();
+        flames.sort_by(|(_, a), (_, b)| ctx.cmp_fitness(a, b));
+        flames.truncate(ctx.pop_num());
+        (self.flames, self.flames_y) = flames.into_iter().unzip();
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for Method<F::Ys> {
+    fn init(&mut self, ctx: &mut Ctx<F>, _rng: &mut Rng) {
+        self.flames.clear();
+        self.flames_y.clear();
+        self.update_flames(ctx);
+    }
+
+    fn name(&self) -> &'static str {
+        "MFO"
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        alloc::vec![("b", self.b)]
+    }
+
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let progress = ctx.progress();
+        let flame_num = Mfo::flame_num(ctx.pop_num(), progress);
+        // Linearly decreased from -1 to -2
+        let a = -1. - progress;
+        let b = self.b;
+        let clamp = ctx.clamp_to_bounds();
+        let flames = &self.flames;
+        let rng = rng.stream(ctx.pop_num());
+        #[cfg(not(feature = "rayon"))]
+        let iter = rng.into_iter();
+        #[cfg(feature = "rayon")]
+        let iter = rng.into_par_iter().with_min_len(ctx.eval_chunk());
+        iter.zip(&mut ctx.pool)
+            .zip(&mut ctx.pool_y)
+            .enumerate()
+            .for_each(|(i, ((mut rng, xs), ys))| {
+                let flame = &flames[i.min(flame_num - 1)];
+                for (s, &[min, max]) in ctx.bound.iter().enumerate() {
+                    let t = (a - 1.) * rng.rand() + 1.;
+                    let d = (flame[s] - xs[s]).abs();
+                    let v = d * math::exp(b * t) * math::cos(TAU * t) + flame[s];
+                    xs[s] = if clamp { v.clamp(min, max) } else { v };
+                }
+                repair(&ctx.func, xs);
+                *ys = ctx.func.fitness(xs);
+            });
+        ctx.log_evals(&ctx.pool, &ctx.pool_y);
+        self.update_flames(ctx);
+        ctx.find_best();
+    }
+}
@@ -0,0 +1,202 @@
+//! # Non-dominated Sorting Genetic Algorithm II
+//!
+//! <https://doi.org/10.1109/4235.996017>
+//!
+//! This method require floating point power function.
+use crate::{ctx::fitness_all, math, prelude::*};
+use alloc::vec::Vec;
+use core::iter::zip;
+
+/// Algorithm of the Non-dominated Sorting Genetic Algorithm II.
+pub type Method = NsgaII;
+
+const DEF: NsgaII = NsgaII { cross: 0.9, eta_c: 20., mutate: 1., eta_m: 20. };
+
+/// Non-dominated Sorting Genetic Algorithm II settings.
+///
+/// The offspring are produced by the simulated binary crossover (SBX) and the
+/// polynomial mutation from the parents of the binary tournament. Then the
+/// parents and the offspring are combined, and the next population is
+/// selected by the non-dominated rank, and the crowding distance of the
+/// objectives ([`Fitness::objectives()`]) for the ties, see
+/// [`pareto::non_dominated_sort()`] and [`pareto::crowding_distance()`].
+///
+/// The offspring are also stored in the best set, so the final front honors
+/// [`SolverBuilder::pareto_limit()`].
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct NsgaII {
+    /// Crossover rate
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.cross))]
+    pub cross: f64,
+    /// Distribution index of the crossover
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.eta_c))]
+    pub eta_c: f64,
+    /// Expected number of the mutated variables of each offspring
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.mutate))]
+    pub mutate: f64,
+    /// Distribution index of the mutation
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.eta_m))]
+    pub eta_m: f64,
+}
+
+impl NsgaII {
+    /// Constant default value.
+    pub const fn new() -> Self {
+        DEF
+    }
+
+    impl_builders! {
+        /// Crossing probability of each pair.
+        fn cross(f64)
+        /// Distribution index of the SBX, the larger value produces the
+        /// offspring closer to the parents.
+        fn eta_c(f64)
+        /// Expected number of the mutated variables, the mutation probability
+        /// of each variable is `mutate / dim`.
+        fn mutate(f64)
+        /// Distribution index of the polynomial mutation.
+        fn eta_m(f64)
+    }
+
+    // Rank and crowding distance of each element
+    fn rank_crowding<Y: Fitness>(ys: &[Y]) -> (Vec<usize>, Vec<f64>) {
+        let mut rank = alloc::vec![0; ys.len()];
+        let mut crowding = alloc::vec![0.; ys.len()];
+        for (r, front) in pareto::non_dominated_sort(ys).into_iter().enumerate() {
+            let objectives = (front.iter())
+                .map(|&i| ys[i].objectives())
+                .collect::<Vec<_>>();
+            for (&i, d) in zip(&front, pareto::crowding_distance(&objectives)) {
+                rank[i] = r;
+                crowding[i] = d;
+            }
+        }
+        (rank, crowding)
+    }
+
+    // Simulated binary crossover of a variable
+    fn sbx(&self, rng: &mut Rng, a: f64, b: f64) -> [f64; 2] {
+        let u = rng.rand();
+        let beta = if u <= 0.5 {
+            math::powf(2. * u, 1. / (self.eta_c + 1.))
+        } else {
+            math::powf(0.5 / (1. - u), 1. / (self.eta_c + 1.))
+        };
+        let c1 = 0.5 * ((1. + beta) * a + (1. - beta) * b);
+        let c2 = 0.5 * ((1. - beta) * a + (1. + beta) * b);
+        [c1, c2]
+    }
+
+    // Polynomial mutation of a variable with the width of its bounds
+    fn polynomial(&self, rng: &mut Rng, x: f64, width: f64) -> f64 {
+        let u = rng.rand();
+        let delta = if u < 0.5 {
+            math::powf(2. * u, 1. / (self.eta_m + 1.)) - 1.
+        } else {
+            1. - math::powf(2. * (1. - u), 1. / (self.eta_m + 1.))
+        };
+        x + delta * width
+    }
+}
+
+impl Default for NsgaII {
+    fn default() -> Self {
+        DEF
+    }
+}
+
+impl AlgCfg for NsgaII {
+    type Algorithm<F: ObjFunc> = Method;
+    fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+        self
+    }
+    fn pop_num() -> usize {
+        100
+    }
+    fn min_pop_num() -> usize {
+        2
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for Method {
+    fn name(&self) -> &'static str {
+        "NSGA-II"
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        alloc::vec![
+            ("cross", self.cross),
+            ("eta_c", self.eta_c),
+            ("mutate", self.mutate),
+            ("eta_m", self.eta_m),
+        ]
+    }
+
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let pop_num = ctx.pop_num();
+        let (rank, crowding) = Self::rank_crowding(&ctx.pool_y);
+        // Binary tournament by the rank, then the crowding distance
+        let tournament = |rng: &mut Rng| {
+            let [a, b] = [rng.ub(pop_num), rng.ub(pop_num)];
+            if (rank[b], -crowding[b]) < (rank[a], -crowding[a]) {
+                b
+            } else {
+                a
+            }
+        };
+        let pm = self.mutate / ctx.dim() as f64;
+        let clamp = ctx.clamp_to_bounds();
+        let streams = rng.stream(pop_num.div_ceil(2));
+        #[cfg(not(feature = "rayon"))]
+        let iter = streams.into_iter();
+        #[cfg(feature = "rayon")]
+        let iter = streams.into_par_iter().with_min_len(ctx.eval_chunk());
+        let pairs = iter
+            .map(|mut rng| {
+                let mut c1 = ctx.pool[tournament(&mut rng)].clone();
+                let mut c2 = ctx.pool[tournament(&mut rng)].clone();
+                if rng.maybe(self.cross) {
+                    for (a, b) in zip(&mut c1, &mut c2) {
+                        if rng.maybe(0.5) && (*a - *b).abs() > f64::EPSILON {
+                            [*a, *b] = self.sbx(&mut rng, *a, *b);
+                        }
+                    }
+                }
+                for xs in [&mut c1, &mut c2] {
+                    for (x, &[min, max]) in zip(xs.iter_mut(), ctx.bound()) {
+                        if rng.maybe(pm) {
+                            *x = self.polynomial(&mut rng, *x, max - min);
+                        }
+                        if clamp {
+                            *x = x.clamp(min, max);
+                        }
+                    }
+                }
+                [c1, c2]
+            })
+            .collect::<Vec<_>>();
+        let mut offspring = pairs.into_iter().flatten().collect::<Vec<_>>();
+        offspring.truncate(pop_num);
+        let offspring_y = fitness_all(&ctx.func, &offspring, ctx.eval_chunk, ctx.eval_order);
+        ctx.log_evals(&offspring, &offspring_y);
+        ctx.best.update_all(&offspring, &offspring_y);
+        // Environmental selection of the combined population
+        let mut pool = core::mem::take(&mut ctx.pool);
+        let mut pool_y = core::mem::take(&mut ctx.pool_y);
+        pool.extend(offspring);
+        pool_y.extend(offspring_y);
+        let (rank, crowding) = Self::rank_crowding(&pool_y);
+        let mut order = (0..pool.len()).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| (rank[a].cmp(&rank[b])).then(crowding[b].total_cmp(&crowding[a])));
+        order.truncate(pop_num);
+        ctx.pool = order.iter().map(|&i| pool[i].clone()).collect();
+        ctx.pool_y = order.iter().map(|&i| pool_y[i].clone()).collect();
+    }
+}
@@ -0,0 +1,173 @@
+//! # Pattern Search
+//!
+//! <https://en.wikipedia.org/wiki/Pattern_search_(optimization)>
+//!
+//! A deterministic compass search, each individual is an independent starting
+//! point. It is suitable as a local optimizer or a refiner of the other
+//! methods.
+use crate::prelude::*;
+use alloc::vec::Vec;
+use core::mem::take;
+
+const DEF: Pattern = Pattern { init_step: 0.1, min_step: 1e-10 };
+
+/// Pattern Search settings.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Pattern {
+    /// Initial step, the fraction of the bound width
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.init_step))]
+    pub init_step: f64,
+    /// Minimum step, the search of an individual is finished below it
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.min_step))]
+    pub min_step: f64,
+}
+
+impl Pattern {
+    /// Constant default value.
+    pub const fn new() -> Self {
+        DEF
+    }
+
+    impl_builders! {
+        /// Initial step, the fraction of the bound width.
+        fn init_step(f64)
+        /// Minimum step.
+        fn min_step(f64)
+    }
+}
+
+impl Default for Pattern {
+    fn default() -> Self {
+        DEF
+    }
+}
+
+impl AlgCfg for Pattern {
+    type Algorithm<F: ObjFunc> = Method;
+    fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+        Method { pattern: self, states: Vec::new() }
+    }
+    fn pop_num() -> usize {
+        10
+    }
+    fn max_gen(_pop_num: usize, dim: usize) -> u64 {
+        // Each halving of the step costs a sweep of the directions at most
+        200 * dim as u64
+    }
+}
+
+// The search state of an individual
+#[derive(Clone)]
+struct State {
+    // The step fraction
+    step: f64,
+    // The next direction, the coordinate is `dir / 2`
+    dir: usize,
+    // The number of the failed probes since the last improvement
+    fails: usize,
+}
+
+/// Algorithm of the Pattern Search.
+///
+/// In each generation, an individual probes `±step` in a coordinate direction,
+/// and moves if it is improved. The directions are probed in turn, and the
+/// step is halved after a sweep of all directions without improvement. The
+/// individual is finished when the step is less than the minimum step.
+pub struct Method {
+    pattern: Pattern,
+    states: Vec<State>,
+}
+
+impl core::ops::Deref for Method {
+    type Target = Pattern;
+
+    fn deref(&self) -> &Self::Target {
+        &self.pattern
+    }
+}
+
+impl Method {
+    fn reset(&mut self, pop_num: usize) {
+        let state = State { step: self.init_step, dir: 0, fails: 0 };
+        self.states = alloc::vec![state; pop_num];
+    }
+
+    // Probe the next direction of an individual
+    fn probe<F: ObjFunc>(
+        &self,
+        ctx: &Ctx<F>,
+        state: &mut State,
+        xs: &mut Vec<f64>,
+        ys: &mut F::Ys,
+    ) {
+        let dim = ctx.dim();
+        let width = (0..dim).map(|s| ctx.bound_width(s)).fold(0., f64::max);
+        if state.step * width < self.min_step {
+            return;
+        }
+        let s = state.dir / 2;
+        let h = [1., -1.][state.dir % 2] * state.step * ctx.bound_width(s);
+        let v = xs[s] + h;
+        let v = if ctx.clamp_to_bounds() {
+            ctx.clamp(s, v)
+        } else {
+            v
+        };
+        if v != xs[s] {
+            let mut trial = xs.clone();
+            trial[s] = v;
+            let trial_y = ctx.fitness(&trial);
+            if ctx.is_better(&trial_y, ys) {
+                // Keep the successful direction
+                *xs = trial;
+                *ys = trial_y;
+                state.fails = 0;
+                return;
+            }
+        }
+        state.dir = (state.dir + 1) % (2 * dim);
+        state.fails += 1;
+        if state.fails >= 2 * dim {
+            state.step *= 0.5;
+            state.fails = 0;
+        }
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for Method {
+    fn name(&self) -> &'static str {
+        "Pattern"
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        alloc::vec![("init_step", self.init_step), ("min_step", self.min_step)]
+    }
+
+    fn init(&mut self, ctx: &mut Ctx<F>, _: &mut Rng) {
+        self.reset(ctx.pop_num());
+    }
+
+    fn generation(&mut self, ctx: &mut Ctx<F>, _: &mut Rng) {
+        // The population may be resized by the other components
+        if self.states.len() != ctx.pop_num() {
+            self.reset(ctx.pop_num());
+        }
+        let mut pool = take(&mut ctx.pool);
+        let mut pool_y = take(&mut ctx.pool_y);
+        let mut states = take(&mut self.states);
+        #[cfg(not(feature = "rayon"))]
+        let iter = core::iter::zip(&mut states, core::iter::zip(&mut pool, &mut pool_y));
+        #[cfg(feature = "rayon")]
+        let iter = (states.par_iter_mut())
+            .zip(pool.par_iter_mut().zip(&mut pool_y))
+            .with_min_len(ctx.eval_chunk());
+        iter.for_each(|(state, (xs, ys))| self.probe(ctx, state, xs, ys));
+        ctx.pool = pool;
+        ctx.pool_y = pool_y;
+        self.states = states;
+        ctx.find_best();
+    }
+}
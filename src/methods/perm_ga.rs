@@ -0,0 +1,305 @@
+//! # Permutation Genetic Algorithm
+//!
+//! <https://doi.org/10.1287/ijoc.6.2.154>
+//!
+//! The permutations are encoded by the random keys, see
+//! [`permutation_from_keys()`].
+use crate::prelude::*;
+use alloc::vec::Vec;
+
+/// Algorithm of the Permutation Genetic Algorithm.
+pub type Method = PermGa;
+
+const DEF: PermGa = PermGa {
+    cross: 0.9,
+    crossover: PermCross::Ox,
+    mutate: 0.3,
+    mutation: PermMutate::Swap,
+};
+
+/// The crossover operator of the permutations, see [`PermGa`].
+///
+/// A segment between two random cuts is copied from a parent, and the rest
+/// items are filled from the other parent.
+#[derive(Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PermCross {
+    /// Order crossover (OX), the rest items keep their relative order in the
+    /// other parent, starting after the segment.
+    #[default]
+    Ox,
+    /// Partially mapped crossover (PMX), the rest items keep their positions
+    /// in the other parent, and the conflicts are resolved by the mapping of
+    /// the segment.
+    Pmx,
+}
+
+impl PermCross {
+    /// Cross two parents into two children.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lengths of the parents are not the same.
+    pub fn apply(&self, rng: &mut Rng, p1: &[usize], p2: &[usize]) -> [Vec<usize>; 2] {
+        assert_eq!(p1.len(), p2.len(), "Parents should have the same length");
+        let n = p1.len();
+        let [a, b] = {
+            let mut cuts = [rng.ub(n + 1), rng.ub(n + 1)];
+            cuts.sort_unstable();
+            cuts
+        };
+        let cross = match self {
+            Self::Ox => Self::ox,
+            Self::Pmx => Self::pmx,
+        };
+        [cross(p1, p2, a, b), cross(p2, p1, a, b)]
+    }
+
+    fn ox(p1: &[usize], p2: &[usize], a: usize, b: usize) -> Vec<usize> {
+        let n = p1.len();
+        let mut child = p1.to_vec();
+        let mut used = alloc::vec![false; n];
+        p1[a..b].iter().for_each(|&v| used[v] = true);
+        let rest = (b..n).chain(0..b).map(|i| p2[i]).filter(|&v| !used[v]);
+        for (i, v) in (b..n).chain(0..a).zip(rest) {
+            child[i] = v;
+        }
+        child
+    }
+
+    fn pmx(p1: &[usize], p2: &[usize], a: usize, b: usize) -> Vec<usize> {
+        let n = p1.len();
+        let mut pos = alloc::vec![0; n];
+        p1.iter().enumerate().for_each(|(i, &v)| pos[v] = i);
+        let in_segment = |v: usize| (a..b).contains(&pos[v]);
+        let mut child = p2.to_vec();
+        child[a..b].copy_from_slice(&p1[a..b]);
+        for i in (0..a).chain(b..n) {
+            let mut v = p2[i];
+            while in_segment(v) {
+                v = p2[pos[v]];
+            }
+            child[i] = v;
+        }
+        child
+    }
+}
+
+/// The mutation operator of the permutations, see [`PermGa`].
+#[derive(Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PermMutate {
+    /// Swap two random items.
+    #[default]
+    Swap,
+    /// Move a random item to another random position.
+    Insert,
+}
+
+impl PermMutate {
+    /// Mutate the permutation in place.
+    pub fn apply(&self, rng: &mut Rng, perm: &mut Vec<usize>) {
+        if perm.len() < 2 {
+            return;
+        }
+        let [i, j] = [rng.ub(perm.len()), rng.ub(perm.len())];
+        match self {
+            Self::Swap => perm.swap(i, j),
+            Self::Insert => {
+                let v = perm.remove(i);
+                perm.insert(j, v);
+            }
+        }
+    }
+}
+
+/// Permutation Genetic Algorithm settings.
+///
+/// The design variables are regarded as the random keys of a permutation,
+/// see [`permutation_from_keys()`]. The offspring are produced by the
+/// crossover and the mutation of the decoded permutations from the parents of
+/// the binary tournament, and encoded back by [`keys_from_permutation()`].
+/// Then the parents and the offspring are combined, and the best ones are
+/// kept as the next population.
+///
+/// The bounds of the variables should be the same. Use [`Pool::Permutation`]
+/// to initialize the pool with the distinct keys.
+///
+/// An 8-city tour:
+///
+/// ```
+/// use metaheuristics_nature::{permutation_from_keys, Bounded, ObjFunc, PermGa, Pool, Solver};
+///
+/// const CITIES: [[f64; 2]; 8] = [
+///     [0., 0.],
+///     [2., 3.],
+///     [5., 1.],
+///     [1., 5.],
+///     [6., 4.],
+///     [3., 6.],
+///     [4., 2.],
+///     [7., 7.],
+/// ];
+///
+/// struct Tour;
+///
+/// impl Bounded for Tour {
+///     fn bound(&self) -> &[[f64; 2]] {
+///         &[[0., 1.]; 8]
+///     }
+/// }
+///
+/// impl ObjFunc for Tour {
+///     type Ys = f64;
+///
+///     fn fitness(&self, xs: &[f64]) -> Self::Ys {
+///         let order = permutation_from_keys(xs);
+///         let next = order.iter().cycle().skip(1);
+///         (order.iter().zip(next))
+///             .map(|(&a, &b)| {
+///                 let [x1, y1] = CITIES[a];
+///                 let [x2, y2] = CITIES[b];
+///                 (x1 - x2).hypot(y1 - y2)
+///             })
+///             .sum()
+///     }
+/// }
+///
+/// let s = Solver::build(PermGa::default(), Tour)
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 50)
+///     .init_pool(Pool::Permutation)
+///     .solve();
+/// let tour = permutation_from_keys(s.as_best_xs());
+/// println!("{tour:?}: {}", s.as_best_fit());
+/// ```
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PermGa {
+    /// Crossover rate
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.cross))]
+    pub cross: f64,
+    /// Crossover operator
+    #[cfg_attr(feature = "clap", clap(long, value_enum, default_value_t = DEF.crossover))]
+    pub crossover: PermCross,
+    /// Mutation rate of each offspring
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.mutate))]
+    pub mutate: f64,
+    /// Mutation operator
+    #[cfg_attr(feature = "clap", clap(long, value_enum, default_value_t = DEF.mutation))]
+    pub mutation: PermMutate,
+}
+
+impl PermGa {
+    /// Constant default value.
+    pub const fn new() -> Self {
+        DEF
+    }
+
+    impl_builders! {
+        /// Crossing probability of each pair.
+        fn cross(f64)
+        /// Crossover operator.
+        fn crossover(PermCross)
+        /// Mutation probability of each offspring.
+        fn mutate(f64)
+        /// Mutation operator.
+        fn mutation(PermMutate)
+    }
+}
+
+impl Default for PermGa {
+    fn default() -> Self {
+        DEF
+    }
+}
+
+impl AlgCfg for PermGa {
+    type Algorithm<F: ObjFunc> = Method;
+    fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+        self
+    }
+    fn pop_num() -> usize {
+        100
+    }
+    fn min_pop_num() -> usize {
+        2
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for Method {
+    fn name(&self) -> &'static str {
+        "PermGA"
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        alloc::vec![("cross", self.cross), ("mutate", self.mutate)]
+    }
+
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let pop_num = ctx.pop_num();
+        let perms = (ctx.pool.iter())
+            .map(|xs| permutation_from_keys(xs))
+            .collect::<Vec<_>>();
+        let shared = ctx.shared_fitness();
+        let is_better = |a: usize, b: usize| match &shared {
+            Some(f) => f[a] > f[b],
+            None => ctx.is_better(&ctx.pool_y[a], &ctx.pool_y[b]),
+        };
+        let tournament = |rng: &mut Rng| {
+            let [a, b] = [rng.ub(pop_num), rng.ub(pop_num)];
+            if is_better(b, a) {
+                b
+            } else {
+                a
+            }
+        };
+        let streams = rng.stream(pop_num.div_ceil(2));
+        #[cfg(not(feature = "rayon"))]
+        let iter = streams.into_iter();
+        #[cfg(feature = "rayon")]
+        let iter = streams.into_par_iter().with_min_len(ctx.eval_chunk());
+        let pairs = iter
+            .map(|mut rng| {
+                let p1 = &perms[tournament(&mut rng)];
+                let p2 = &perms[tournament(&mut rng)];
+                let children = if rng.maybe(self.cross) {
+                    self.crossover.apply(&mut rng, p1, p2)
+                } else {
+                    [p1.clone(), p2.clone()]
+                };
+                children.map(|mut perm| {
+                    if rng.maybe(self.mutate) {
+                        self.mutation.apply(&mut rng, &mut perm);
+                    }
+                    let xs = keys_from_permutation(&perm, ctx.bound());
+                    let ys = ctx.fitness(&xs);
+                    (xs, ys)
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut offspring = pairs.into_iter().flatten().collect::<Vec<_>>();
+        offspring.truncate(pop_num);
+        // Combine the parents and the offspring, and keep the best ones
+        let mut pool = core::mem::take(&mut ctx.pool);
+        let mut pool_y = core::mem::take(&mut ctx.pool_y);
+        for (xs, ys) in offspring {
+            ctx.best.update(&xs, &ys);
+            pool.push(xs);
+            pool_y.push(ys);
+        }
+        let mut order = (0..pool.len()).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| ctx.cmp_fitness(&pool_y[a], &pool_y[b]));
+        order.truncate(pop_num);
+        ctx.pool = order.iter().map(|&i| pool[i].clone()).collect();
+        ctx.pool_y = order.iter().map(|&i| pool_y[i].clone()).collect();
+    }
+}
@@ -0,0 +1,125 @@
+//! # Phased
+//!
+//! A meta-method that runs multiple methods in sequential phases, such as
+//! exploration then exploitation. All methods share the same context, so the
+//! population and the best set are continued between the phases.
+use super::ensemble::BoxAlg;
+use crate::prelude::*;
+use alloc::{boxed::Box, vec::Vec};
+
+/// The switch condition of a phase, returns true to move to the next phase.
+#[cfg(feature = "rayon")]
+pub type SwitchFn<F> = Box<dyn Fn(&Ctx<F>) -> bool + Send + Sync>;
+/// The switch condition of a phase, returns true to move to the next phase.
+#[cfg(not(feature = "rayon"))]
+pub type SwitchFn<F> = Box<dyn Fn(&Ctx<F>) -> bool>;
+
+/// Algorithm of the Phased.
+///
+/// Each method runs until its switch condition is met, which is checked
+/// before each generation, then the next method is initialized with the
+/// current population. The condition of the last phase is never checked. For
+/// example, DE for the first half of the budget, then TLBO:
+///
+/// ```
+/// use metaheuristics_nature::{phased::Phased, De, Solver, Tlbo};
+/// # use metaheuristics_nature::tests::TestObj as MyFunc;
+///
+/// let method = Phased::new()
+///     .add(De::default(), |ctx| ctx.gen > 10)
+///     .add(Tlbo, |_| false);
+/// let s = Solver::build_algorithm(method, MyFunc::new())
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .solve();
+/// ```
+///
+/// Please note that the population should satisfy the requirement of all
+/// methods, such as the minimum population number.
+pub struct Phased<F: ObjFunc> {
+    phases: Vec<(BoxAlg<F>, SwitchFn<F>)>,
+    current: usize,
+}
+
+impl<F: ObjFunc> Default for Phased<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: ObjFunc> Phased<F> {
+    /// Create an empty sequence.
+    pub fn new() -> Self {
+        Self { phases: Vec::new(), current: 0 }
+    }
+
+    /// Create from a list of the methods and their switch conditions.
+    pub fn from_list(phases: Vec<(BoxAlg<F>, SwitchFn<F>)>) -> Self {
+        Self { phases, current: 0 }
+    }
+
+    /// Add a method from its setting with the switch condition.
+    #[cfg(feature = "rayon")]
+    pub fn add<A, C>(self, cfg: A, switch: C) -> Self
+    where
+        A: AlgCfg,
+        C: Fn(&Ctx<F>) -> bool + Send + Sync + 'static,
+    {
+        self.add_boxed(Box::new(cfg.algorithm()), Box::new(switch))
+    }
+
+    /// Add a method from its setting with the switch condition.
+    #[cfg(not(feature = "rayon"))]
+    pub fn add<A, C>(self, cfg: A, switch: C) -> Self
+    where
+        A: AlgCfg,
+        C: Fn(&Ctx<F>) -> bool + 'static,
+    {
+        self.add_boxed(Box::new(cfg.algorithm()), Box::new(switch))
+    }
+
+    /// Add a boxed method with the switch condition.
+    pub fn add_boxed(mut self, method: BoxAlg<F>, switch: SwitchFn<F>) -> Self {
+        self.phases.push((method, switch));
+        self
+    }
+
+    /// Get the index of the current phase.
+    pub fn phase(&self) -> usize {
+        self.current
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for Phased<F> {
+    fn init(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        assert!(!self.phases.is_empty(), "Phased should have methods");
+        self.current = 0;
+        self.phases[0].0.init(ctx, rng);
+    }
+
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        while self.current + 1 < self.phases.len() && (self.phases[self.current].1)(ctx) {
+            self.current += 1;
+            self.phases[self.current].0.init(ctx, rng);
+        }
+        self.phases[self.current].0.generation(ctx, rng);
+    }
+
+    fn name(&self) -> &'static str {
+        "Phased"
+    }
+
+    fn evals_per_gen(&self, pop_num: usize) -> usize {
+        (self.phases.iter())
+            .map(|(method, _)| method.evals_per_gen(pop_num))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        // The order of the phases
+        (self.phases.iter().enumerate())
+            .map(|(i, (method, _))| (method.name(), i as f64))
+            .collect()
+    }
+}
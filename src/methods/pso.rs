@@ -1,10 +1,69 @@
 //! # Particle Swarm Optimization
 //!
 //! <https://en.wikipedia.org/wiki/Particle_swarm_optimization>
-use crate::prelude::*;
+use crate::{obj_func::repair, prelude::*};
 use alloc::vec::Vec;
 
-const DEF: Pso = Pso { cognition: 2.05, social: 2.05, velocity: 1.3 };
+const DEF: Pso = Pso {
+    cognition: 2.05,
+    social: 2.05,
+    velocity: 1.3,
+    init_velocity: InitVel::Zero,
+    neighborhood_schedule: Neighborhood::Global,
+};
+
+/// The initial velocity of the particles.
+///
+/// The velocity of each variable is scaled by the width of its bounds, and it
+/// is added to the particles in the first generation.
+#[derive(Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InitVel {
+    /// No initial velocity.
+    #[default]
+    Zero,
+    /// Uniform distribution in the range `[-fraction, fraction]` of the width.
+    Uniform(f64),
+    /// Normal distribution with zero mean and the standard deviation `std` of
+    /// the width.
+    Gaussian(f64),
+}
+
+/// The neighborhood schedule of the social term.
+///
+/// The neighbors of a particle are its adjacent particles in a ring of the
+/// population, the particle follows the best personal best of its neighbors.
+#[derive(Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Neighborhood {
+    /// Follow the global best (gbest).
+    #[default]
+    Global,
+    /// Ring topology (lbest) with a fixed radius, the number of neighbors on
+    /// each side.
+    Ring(usize),
+    /// Ring topology with the radius grown linearly from the given value to
+    /// the whole population by [`Ctx::progress()`].
+    Growing(usize),
+}
+
+impl Neighborhood {
+    /// Get the effective radius of the ring for the population number `n` and
+    /// the progress in `0..=1`.
+    ///
+    /// Returns `None` for the global best.
+    pub fn radius(&self, n: usize, progress: f64) -> Option<usize> {
+        let full = n / 2;
+        match *self {
+            Self::Global => None,
+            Self::Ring(r) => Some(r.min(full)),
+            Self::Growing(r) => {
+                let r = r.min(full) as f64;
+                Some((r + (full as f64 - r) * progress).round() as usize)
+            }
+        }
+    }
+}
 
 /// Particle Swarm Optimization settings.
 #[derive(Clone, PartialEq)]
@@ -21,6 +80,12 @@ pub struct Pso {
     /// Velocity factor
     #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.velocity))]
     pub velocity: f64,
+    /// Initial velocity
+    #[cfg_attr(feature = "clap", clap(skip = DEF.init_velocity))]
+    pub init_velocity: InitVel,
+    /// Neighborhood schedule
+    #[cfg_attr(feature = "clap", clap(skip = DEF.neighborhood_schedule))]
+    pub neighborhood_schedule: Neighborhood,
 }
 
 impl Pso {
@@ -36,6 +101,10 @@ impl Pso {
         fn social(f64)
         /// Moving velocity.
         fn velocity(f64)
+        /// Initial velocity of the particles.
+        fn init_velocity(InitVel)
+        /// Neighborhood schedule of the social term.
+        fn neighborhood_schedule(Neighborhood)
     }
 }
 
@@ -48,7 +117,12 @@ impl Default for Pso {
 impl AlgCfg for Pso {
     type Algorithm<F: ObjFunc> = Method<F::Ys>;
     fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
-        Method { pso: self, past: Vec::new(), past_y: Vec::new() }
+        Method {
+            pso: self,
+            past: Vec::new(),
+            past_y: Vec::new(),
+            vel: Vec::new(),
+        }
     }
 }
 
@@ -57,6 +131,7 @@ pub struct Method<Y: Fitness> {
     pso: Pso,
     past: Vec<Vec<f64>>,
     past_y: Vec<Y>,
+    vel: Vec<Vec<f64>>,
 }
 
 impl<Y: Fitness> core::ops::Deref for Method<Y> {
@@ -68,9 +143,33 @@ impl<Y: Fitness> core::ops::Deref for Method<Y> {
 }
 
 impl<F: ObjFunc> Algorithm<F> for Method<F::Ys> {
-    fn init(&mut self, ctx: &mut Ctx<F>, _: &mut Rng) {
+    fn init(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
         self.past = ctx.pool.clone();
         self.past_y = ctx.pool_y.clone();
+        let init_velocity = self.init_velocity;
+        let mut vel = |s| {
+            let w = ctx.bound_width(s);
+            match init_velocity {
+                InitVel::Uniform(f) if f > 0. => w * rng.range(-f..=f),
+                InitVel::Gaussian(std) if std > 0. => w * rng.normal(0., std),
+                _ => 0.,
+            }
+        };
+        self.vel = (0..ctx.pop_num())
+            .map(|_| (0..ctx.dim()).map(&mut vel).collect())
+            .collect();
+    }
+
+    fn name(&self) -> &'static str {
+        "PSO"
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        alloc::vec![
+            ("cognition", self.cognition),
+            ("social", self.social),
+            ("velocity", self.velocity),
+        ]
     }
 
     fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
@@ -78,28 +177,57 @@ impl<F: ObjFunc> Algorithm<F> for Method<F::Ys> {
         let cognition = self.cognition;
         let social = self.social;
         let velocity = self.velocity;
+        let clamp = ctx.clamp_to_bounds();
+        // The best personal best of the ring neighbors
+        let n = ctx.pop_num();
+        let lbest = (self.neighborhood_schedule.radius(n, ctx.progress())).map(|r| {
+            (0..n)
+                .map(|i| {
+                    let mut best = i;
+                    for j in (1..=r).flat_map(|d| [(i + d) % n, (i + n - d) % n]) {
+                        if ctx.compare.is_better(&self.past_y[j], &self.past_y[best]) {
+                            best = j;
+                        }
+                    }
+                    self.past[best].clone()
+                })
+                .collect::<Vec<_>>()
+        });
         #[cfg(not(feature = "rayon"))]
         let iter = rng.into_iter();
         #[cfg(feature = "rayon")]
-        let iter = rng.into_par_iter();
+        let iter = rng.into_par_iter().with_min_len(ctx.eval_chunk());
         iter.zip(&mut ctx.pool)
             .zip(&mut ctx.pool_y)
             .zip(&mut self.past)
             .zip(&mut self.past_y)
-            .for_each(|((((mut rng, xs), ys), past), past_y)| {
+            .zip(&mut self.vel)
+            .enumerate()
+            .for_each(|(i, (((((mut rng, xs), ys), past), past_y), vel))| {
                 let alpha = rng.ub(cognition);
                 let beta = rng.ub(social);
-                let best = ctx.best.sample_xs(&mut rng);
-                for s in 0..ctx.func.dim() {
+                let best = match &lbest {
+                    Some(lbest) => &lbest[i],
+                    None => ctx.best.sample_xs(&mut rng),
+                };
+                for (s, &[min, max]) in ctx.bound.iter().enumerate() {
                     let v = velocity * xs[s] + alpha * (past[s] - xs[s]) + beta * (best[s] - xs[s]);
-                    xs[s] = ctx.func.clamp(s, v);
+                    xs[s] = if clamp {
+                        (v + vel[s]).clamp(min, max)
+                    } else {
+                        v + vel[s]
+                    };
+                    // The initial velocity is only applied once
+                    vel[s] = 0.;
                 }
+                repair(&ctx.func, xs);
                 *ys = ctx.func.fitness(xs);
-                if ys.is_dominated(&*past_y) {
+                if ctx.compare.is_better(ys, past_y) {
                     *past = xs.clone();
                     *past_y = ys.clone();
                 }
             });
+        ctx.log_evals(&ctx.pool, &ctx.pool_y);
         ctx.find_best();
     }
 }
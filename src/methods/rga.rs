@@ -5,12 +5,12 @@
 //! <https://en.wikipedia.org/wiki/Genetic_algorithm>
 //!
 //! This method require floating point power function.
-use crate::prelude::*;
-use alloc::vec::Vec;
-use core::iter::zip;
-
-/// Algorithm of the Real-coded Genetic Algorithm.
-pub type Method = Rga;
+use crate::{math, obj_func::repair, prelude::*};
+use alloc::{sync::Arc, vec::Vec};
+use core::{
+    iter::zip,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 const DEF: Rga = Rga { cross: 0.95, mutate: 0.05, win: 0.95, delta: 5. };
 
@@ -61,32 +61,134 @@ impl Default for Rga {
 impl AlgCfg for Rga {
     type Algorithm<F: ObjFunc> = Method;
     fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
-        self
+        let rates = Rates::new(self.cross, self.mutate);
+        Method { rga: self, rates }
     }
     fn pop_num() -> usize {
         500
     }
+    fn min_pop_num() -> usize {
+        2
+    }
+}
+
+/// A shared handle of the crossover and mutation rates, which can be adjusted
+/// during solving, such as in the callback.
+///
+/// Obtained from [`Method::rates()`] before solving.
+///
+/// ```
+/// use metaheuristics_nature::{Rga, Solver};
+/// # use metaheuristics_nature::tests::TestObj as MyFunc;
+///
+/// let builder = Solver::build(Rga::default(), MyFunc::new());
+/// let rates = builder.algorithm().rates().clone();
+/// let s = builder
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .callback(move |ctx| {
+///         if ctx.diversity() < 1e-3 {
+///             rates.set_mutate(0.2);
+///         }
+///     })
+///     .solve();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Rates(Arc<[AtomicU64; 2]>);
+
+impl Rates {
+    fn new(cross: f64, mutate: f64) -> Self {
+        Self(Arc::new(
+            [cross, mutate].map(|v| AtomicU64::new(v.to_bits())),
+        ))
+    }
+
+    /// Get the crossing probability.
+    pub fn cross(&self) -> f64 {
+        f64::from_bits(self.0[0].load(Ordering::Relaxed))
+    }
+
+    /// Get the mutation probability.
+    pub fn mutate(&self) -> f64 {
+        f64::from_bits(self.0[1].load(Ordering::Relaxed))
+    }
+
+    /// Set the crossing probability in `[0, 1]`, applied from the next
+    /// generation.
+    pub fn set_cross(&self, cross: f64) {
+        self.0[0].store(cross.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Set the mutation probability in `[0, 1]`, applied from the next
+    /// generation.
+    pub fn set_mutate(&self, mutate: f64) {
+        self.0[1].store(mutate.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Algorithm of the Real-coded Genetic Algorithm.
+pub struct Method {
+    rga: Rga,
+    rates: Rates,
+}
+
+impl core::ops::Deref for Method {
+    type Target = Rga;
+
+    fn deref(&self) -> &Self::Target {
+        &self.rga
+    }
 }
 
 impl Method {
+    /// Get the shared handle of the crossover and mutation rates.
+    pub fn rates(&self) -> &Rates {
+        &self.rates
+    }
+
     fn get_delta(&self, gen: u64, rng: &mut Rng, y: f64) -> f64 {
         let r = if gen < 100 { gen as f64 / 100. } else { 1. };
-        rng.rand() * y * (1. - r).powf(self.delta)
+        rng.rand() * y * math::powf(1. - r, self.delta)
     }
 }
 
 impl<F: ObjFunc> Algorithm<F> for Method {
+    fn name(&self) -> &'static str {
+        "RGA"
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        alloc::vec![
+            ("cross", self.rates.cross()),
+            ("mutate", self.rates.mutate()),
+            ("win", self.win),
+            ("delta", self.delta),
+        ]
+    }
+
+    fn evals_per_gen(&self, pop_num: usize) -> usize {
+        // Three children of each pair in the crossover, and the mutation
+        3 * (pop_num / 2) + pop_num
+    }
+
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
     fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let cross = self.rates.cross();
+        let mutate = self.rates.mutate();
         // Select
+        let shared = ctx.shared_fitness();
+        let is_better = |a: usize, b: usize| match &shared {
+            Some(f) => f[a] > f[b],
+            None => ctx.is_better(&ctx.pool_y[a], &ctx.pool_y[b]),
+        };
         let mut pool = ctx.pool.clone();
         let mut pool_y = ctx.pool_y.clone();
         for (xs, ys) in zip(&mut pool, &mut pool_y) {
             let [a, b] = rng.array(0..ctx.pop_num());
-            let i = if ctx.pool_y[a].is_dominated(&ctx.pool_y[b]) {
-                a
-            } else {
-                b
-            };
+            let i = if is_better(a, b) { a } else { b };
             if rng.maybe(self.win) {
                 *xs = ctx.pool[i].clone();
                 *ys = ctx.pool_y[i].clone();
@@ -101,13 +203,13 @@ impl<F: ObjFunc> Algorithm<F> for Method {
         }
         // Crossover
         for i in (0..ctx.pop_num() - 1).step_by(2) {
-            if !rng.maybe(self.cross) {
+            if !rng.maybe(cross) {
                 continue;
             }
             #[cfg(not(feature = "rayon"))]
             let iter = rng.stream(3).into_iter();
             #[cfg(feature = "rayon")]
-            let iter = rng.stream(3).into_par_iter();
+            let iter = rng.stream(3).into_par_iter().with_min_len(ctx.eval_chunk());
             let mut ret: [_; 3] = iter
                 .enumerate()
                 .map(|(id, mut rng)| {
@@ -118,7 +220,11 @@ impl<F: ObjFunc> Algorithm<F> for Method {
                                 1 => 1.5 * a - 0.5 * b,
                                 _ => -0.5 * a + 1.5 * b,
                             };
-                            rng.clamp(v, min..=max)
+                            if ctx.clamp_to_bounds() {
+                                rng.clamp(v, min..=max)
+                            } else {
+                                v
+                            }
                         })
                         .collect::<Vec<_>>();
                     let ys = ctx.fitness(&xs);
@@ -127,25 +233,32 @@ impl<F: ObjFunc> Algorithm<F> for Method {
                 .collect::<Vec<_>>()
                 .try_into()
                 .unwrap_or_else(|_| unreachable!());
-            ret.sort_unstable_by(|(a, _), (b, _)| a.eval().partial_cmp(&b.eval()).unwrap());
+            ret.sort_unstable_by(|(a, _), (b, _)| ctx.cmp_fitness(a, b));
             let [(t1_f, t1_x), (t2_f, t2_x), ..] = ret;
             ctx.set_from(i, t1_x, t1_f);
             ctx.set_from(i + 1, t2_x, t2_f);
         }
         // Mutate
         let dim = ctx.dim();
-        for (xs, ys) in zip(&mut ctx.pool, &mut ctx.pool_y) {
-            if !rng.maybe(self.mutate) {
+        let rng = rng.mutation();
+        let mut mutated = Vec::new();
+        for (i, (xs, ys)) in zip(&mut ctx.pool, &mut ctx.pool_y).enumerate() {
+            if !rng.maybe(mutate) {
                 continue;
             }
+            mutated.push(i);
             let s = rng.ub(dim);
             if rng.maybe(0.5) {
-                xs[s] += self.get_delta(ctx.gen, rng, ctx.func.ub(s) - xs[s]);
+                xs[s] += self.get_delta(ctx.gen, rng, ctx.bound[s][1] - xs[s]);
             } else {
-                xs[s] -= self.get_delta(ctx.gen, rng, xs[s] - ctx.func.lb(s));
+                xs[s] -= self.get_delta(ctx.gen, rng, xs[s] - ctx.bound[s][0]);
             }
+            repair(&ctx.func, xs);
             *ys = ctx.func.fitness(xs);
         }
+        for i in mutated {
+            ctx.log_eval(&ctx.pool[i], &ctx.pool_y[i]);
+        }
         ctx.find_best();
     }
 }
@@ -0,0 +1,178 @@
+//! # Simulated Annealing
+//!
+//! <https://en.wikipedia.org/wiki/Simulated_annealing>
+//!
+//! This method require exponential, logarithm and power functions.
+use crate::{math, prelude::*};
+use alloc::vec::Vec;
+
+/// Algorithm of the Simulated Annealing.
+pub type Method = Sa;
+
+const DEF: Sa = Sa {
+    init_temp: 1.,
+    cooling_rate: 0.98,
+    schedule: CoolingSchedule::Geometric,
+    step: 0.1,
+};
+
+/// The cooling schedule of the temperature `T` at the generation `k`, where
+/// `T0` is the initial temperature and `r` is the cooling rate.
+#[derive(Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CoolingSchedule {
+    /// `T = T0 * max(1 - r * k, 0)`
+    Linear,
+    /// `T = T0 * r^k`, where `r` is in `(0, 1)`.
+    #[default]
+    Geometric,
+    /// `T = T0 * exp(-r * k)`
+    Exponential,
+    /// `T = T0 / (1 + r * ln(1 + k))`
+    Logarithmic,
+}
+
+/// Simulated Annealing settings.
+///
+/// The population is a set of the independent chains. In each generation,
+/// each chain moves with a Gaussian step, where the standard deviation is
+/// `step * T` of the width of the bounds, and the worse move is accepted by
+/// the Metropolis criterion with probability `exp(-dE / T)`.
+///
+/// Since the fitness values are only comparable, the energy `E` of a
+/// solution is the fraction of the current chains that are better than it,
+/// so the temperature is independent of the scale of the objective function.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Sa {
+    /// Initial temperature
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.init_temp))]
+    pub init_temp: f64,
+    /// Cooling rate of the schedule
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.cooling_rate))]
+    pub cooling_rate: f64,
+    /// Cooling schedule
+    #[cfg_attr(feature = "clap", clap(long, value_enum, default_value_t = DEF.schedule))]
+    pub schedule: CoolingSchedule,
+    /// Step size, the standard deviation of the moves relative to the width
+    /// of the bounds at the unit temperature
+    #[cfg_attr(feature = "clap", clap(long, default_value_t = DEF.step))]
+    pub step: f64,
+}
+
+impl Sa {
+    /// Constant default value.
+    pub const fn new() -> Self {
+        DEF
+    }
+
+    impl_builders! {
+        /// Initial temperature.
+        fn init_temp(f64)
+        /// Cooling rate, the meaning depends on the [`CoolingSchedule`].
+        fn cooling_rate(f64)
+        /// Cooling schedule.
+        fn schedule(CoolingSchedule)
+        /// Step size of the moves.
+        fn step(f64)
+    }
+
+    /// The temperature at the generation `gen`.
+    pub fn temperature(&self, gen: u64) -> f64 {
+        let t0 = self.init_temp;
+        let r = self.cooling_rate;
+        let k = gen as f64;
+        match self.schedule {
+            CoolingSchedule::Linear => t0 * (1. - r * k).max(0.),
+            CoolingSchedule::Geometric => t0 * math::powf(r, k),
+            CoolingSchedule::Exponential => t0 * math::exp(-r * k),
+            CoolingSchedule::Logarithmic => t0 / (1. + r * math::ln(1. + k)),
+        }
+    }
+}
+
+impl Default for Sa {
+    fn default() -> Self {
+        DEF
+    }
+}
+
+impl AlgCfg for Sa {
+    type Algorithm<F: ObjFunc> = Method;
+    fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+        self
+    }
+    fn pop_num() -> usize {
+        20
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for Method {
+    fn name(&self) -> &'static str {
+        "SA"
+    }
+
+    fn params(&self) -> Vec<(&'static str, f64)> {
+        alloc::vec![
+            ("init_temp", self.init_temp),
+            ("cooling_rate", self.cooling_rate),
+            ("step", self.step),
+        ]
+    }
+
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let temp = self.temperature(ctx.gen);
+        let std = (self.step * temp).max(0.);
+        let clamp = ctx.clamp_to_bounds();
+        let streams = rng.stream(ctx.pop_num());
+        #[cfg(not(feature = "rayon"))]
+        let iter = streams.into_iter();
+        #[cfg(feature = "rayon")]
+        let iter = streams.into_par_iter().with_min_len(ctx.eval_chunk());
+        let moves = iter
+            .zip(&ctx.pool)
+            .map(|(mut rng, xs)| {
+                let xs = (xs.iter().zip(ctx.bound()))
+                    .map(|(x, &[min, max])| {
+                        let v = x + rng.normal(0., std * (max - min));
+                        if clamp {
+                            v.clamp(min, max)
+                        } else {
+                            v
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                let ys = ctx.fitness(&xs);
+                (xs, ys)
+            })
+            .collect::<Vec<_>>();
+        // Metropolis criterion with the energies of the current chains
+        let n = ctx.pop_num() as f64;
+        let energy = |ys: &F::Ys| {
+            let better = ctx.pool_y.iter().filter(|y| ctx.is_better(y, ys)).count();
+            better as f64 / n
+        };
+        let accepted = (moves.iter().enumerate())
+            .map(|(i, (_, ys))| {
+                if ctx.is_better(ys, &ctx.pool_y[i]) {
+                    return true;
+                }
+                let de = energy(ys) - energy(&ctx.pool_y[i]);
+                temp > 0. && rng.maybe(math::exp(-de / temp))
+            })
+            .collect::<Vec<_>>();
+        for (i, ((xs, ys), accepted)) in moves.into_iter().zip(accepted).enumerate() {
+            ctx.best.update(&xs, &ys);
+            if accepted {
+                ctx.set_from(i, xs, ys);
+            }
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! # Salp Swarm Algorithm
+//!
+//! <https://doi.org/10.1016/j.advengsoft.2017.07.002>
+//!
+//! This method require exponential function.
+use crate::{ctx::fitness_all, math, prelude::*};
+
+/// Algorithm of the Salp Swarm Algorithm.
+pub type Method = Ssa;
+
+/// Salp Swarm Algorithm settings.
+///
+/// The salps form a chain in the order of the pool. The leader (the first
+/// salp) moves around the food source (the best) with a coefficient
+/// `c1 = 2 * exp(-(4 * t / T)^2)`, where `t / T` is [`Ctx::progress()`], and
+/// each follower moves to the middle of itself and the salp ahead.
+#[derive(Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ssa;
+
+impl Ssa {
+    /// Constant default value.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// The coefficient of the leader at the progress in `0..=1`, which
+    /// balances the exploration and the exploitation.
+    pub fn c1(progress: f64) -> f64 {
+        let t = 4. * progress;
+        2. * math::exp(-t * t)
+    }
+}
+
+impl AlgCfg for Ssa {
+    type Algorithm<F: ObjFunc> = Method;
+    fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+        self
+    }
+    fn pop_num() -> usize {
+        50
+    }
+}
+
+impl<F: ObjFunc> Algorithm<F> for Method {
+    fn name(&self) -> &'static str {
+        "SSA"
+    }
+
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+        let c1 = Self::c1(ctx.progress());
+        let clamp = ctx.clamp_to_bounds();
+        let food = ctx.best.sample_xs(rng).to_vec();
+        for (s, &[min, max]) in ctx.bound.iter().enumerate() {
+            // Leader
+            let step = c1 * ((max - min) * rng.rand() + min);
+            let v = if rng.maybe(0.5) {
+                food[s] + step
+            } else {
+                food[s] - step
+            };
+            ctx.pool[0][s] = if clamp { v.clamp(min, max) } else { v };
+            // Followers
+            for i in 1..ctx.pool.len() {
+                let v = (ctx.pool[i][s] + ctx.pool[i - 1][s]) * 0.5;
+                ctx.pool[i][s] = if clamp { v.clamp(min, max) } else { v };
+            }
+        }
+        ctx.pool_y = fitness_all(&ctx.func, &ctx.pool, ctx.eval_chunk, ctx.eval_order);
+        ctx.log_evals(&ctx.pool, &ctx.pool_y);
+        ctx.find_best();
+    }
+}
@@ -30,10 +30,9 @@ impl AlgCfg for Tlbo {
     }
 }
 
-fn register<F: ObjFunc>(ctx: &mut Ctx<F>, i: usize, student: Vec<f64>) {
+fn register<F: ObjFunc>(ctx: &mut Ctx<F>, rng: &mut Rng, i: usize, student: Vec<f64>) {
     let f_new = ctx.fitness(&student);
-    if f_new.is_dominated(&ctx.pool_y[i]) {
-        ctx.set_from(i, student, f_new);
+    if let Some(i) = ctx.replace(i, student, f_new, rng) {
         ctx.best.update(&ctx.pool[i], &ctx.pool_y[i]);
     }
 }
@@ -50,10 +49,15 @@ fn teaching<F: ObjFunc>(ctx: &mut Ctx<F>, rng: &mut Rng, i: usize) {
             }
             let dim = ctx.dim() as f64;
             mean /= dim;
-            (base + rng.range(1.0..dim) * (best - tf * mean)).clamp(min, max)
+            let v = base + rng.range(1.0..dim) * (best - tf * mean);
+            if ctx.clamp_to_bounds() {
+                v.clamp(min, max)
+            } else {
+                v
+            }
         })
         .collect();
-    register(ctx, i, student);
+    register(ctx, rng, i, student);
 }
 
 fn learning<F: ObjFunc>(ctx: &mut Ctx<F>, rng: &mut Rng, i: usize) {
@@ -67,18 +71,36 @@ fn learning<F: ObjFunc>(ctx: &mut Ctx<F>, rng: &mut Rng, i: usize) {
     };
     let student = zip(ctx.bound(), zip(&ctx.pool[i], &ctx.pool[j]))
         .map(|(&[min, max], (a, b))| {
-            let diff = if ctx.pool_y[j].is_dominated(&ctx.pool_y[i]) {
+            let diff = if ctx.is_better(&ctx.pool_y[j], &ctx.pool_y[i]) {
                 a - b
             } else {
                 b - a
             };
-            (a + rng.range(1.0..ctx.dim() as f64) * diff).clamp(min, max)
+            let v = a + rng.range(1.0..ctx.dim() as f64) * diff;
+            if ctx.clamp_to_bounds() {
+                v.clamp(min, max)
+            } else {
+                v
+            }
         })
         .collect();
-    register(ctx, i, student);
+    register(ctx, rng, i, student);
 }
 
 impl<F: ObjFunc> Algorithm<F> for Method {
+    fn name(&self) -> &'static str {
+        "TLBO"
+    }
+
+    fn evals_per_gen(&self, pop_num: usize) -> usize {
+        // Teaching and learning phases
+        2 * pop_num
+    }
+
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
     fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
         for i in 0..ctx.pop_num() {
             teaching(ctx, rng, i);
@@ -1,4 +1,5 @@
-use crate::prelude::*;
+use crate::{math, prelude::*};
+use alloc::{borrow::Cow, vec::Vec};
 
 /// A problem is well bounded.
 ///
@@ -56,6 +57,36 @@ pub trait Bounded: MaybeParallel {
     }
 }
 
+/// The kind of a variable, see [`ObjFunc::var_kind()`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VarKind {
+    /// A real number.
+    #[default]
+    Continuous,
+    /// An integer, which is rounded from the real number.
+    Integer,
+    /// One of the values, which is the nearest one of the real number.
+    ///
+    /// The values should be in the bounds of the variable.
+    Choice(Vec<f64>),
+}
+
+impl VarKind {
+    /// Snap the value to the nearest valid value of this kind.
+    ///
+    /// The empty choice is regarded as continuous.
+    pub fn snap(&self, v: f64) -> f64 {
+        match self {
+            Self::Continuous => v,
+            Self::Integer => math::round(v),
+            Self::Choice(values) => (values.iter())
+                .min_by(|a, b| (*a - v).abs().total_cmp(&(*b - v).abs()))
+                .map_or(v, |c| *c),
+        }
+    }
+}
+
 /// A trait for the objective function.
 ///
 /// ```
@@ -133,4 +164,256 @@ pub trait ObjFunc: Bounded {
     /// with [`std::cell::Cell`] but not recommended. Please use the adaptive
     /// value from the algorithm, not from the objective function.
     fn fitness(&self, xs: &[f64]) -> Self::Ys;
+
+    /// Return fitness with the early abort of the dominated candidates.
+    ///
+    /// For the expensive multi-objective functions, the objectives can be
+    /// computed one by one. After each objective, call `is_dominated` with
+    /// the computed objectives. If it returns true, the candidate is dominated
+    /// by the current best elements whatever its rest objectives are, so the
+    /// function can skip them and return the worst fitness value.
+    ///
+    /// The methods evaluate the candidates with this function through
+    /// [`Ctx::fitness()`]. The partial comparison is decided by
+    /// [`Fitness::dominates_prefix()`]. By default, it calls
+    /// [`ObjFunc::fitness()`] directly.
+    fn fitness_partial(&self, xs: &[f64], is_dominated: &dyn Fn(&[f64]) -> bool) -> Self::Ys {
+        let _ = is_dominated;
+        self.fitness(xs)
+    }
+
+    /// Return the gradient of the evaluation value, or `None` if it is not
+    /// available.
+    ///
+    /// The gradient is used by the local refinement
+    /// [`Solver::refine_lbfgs()`] at the accepted points, which falls back to
+    /// the finite differences if this function returns `None`. By default, it
+    /// returns `None`.
+    fn gradient(&self, xs: &[f64]) -> Option<Vec<f64>> {
+        let _ = xs;
+        None
+    }
+
+    /// Return the fitness value and the gradient of the evaluation value
+    /// together.
+    ///
+    /// The gradient-based refiners call this function instead of
+    /// [`ObjFunc::fitness()`] and [`ObjFunc::gradient()`] separately if
+    /// [`ObjFunc::is_joint_grad()`] returns true. Override them together if
+    /// the joint computation is cheaper, such as the adjoint methods that
+    /// share a simulation run. By default, it calls the two functions.
+    fn fitness_and_grad(&self, xs: &[f64]) -> (Self::Ys, Option<Vec<f64>>) {
+        (self.fitness(xs), self.gradient(xs))
+    }
+
+    /// Return true if [`ObjFunc::fitness_and_grad()`] is overridden with a
+    /// joint computation.
+    ///
+    /// Otherwise, the gradient-based refiners evaluate the trial points by
+    /// [`ObjFunc::fitness()`] only, and compute the gradient at the accepted
+    /// points. By default, it returns false.
+    fn is_joint_grad(&self) -> bool {
+        false
+    }
+
+    /// Return the kind of the variable `s`, see [`VarKind`].
+    ///
+    /// The methods search in the continuous space, and the solver snaps the
+    /// design variables to the nearest valid values before evaluating them,
+    /// so this function always receives the valid values. The pool and the
+    /// best elements are also snapped after each generation, see
+    /// [`Ctx::repair()`]. By default, all variables are continuous.
+    ///
+    /// This function should be cheap.
+    fn var_kind(&self, s: usize) -> VarKind {
+        let _ = s;
+        VarKind::Continuous
+    }
+
+    /// Return true if the objective function only supports minimization,
+    /// such as the failed evaluations are mapped to positive infinity.
+    ///
+    /// The solver rejects [`SolverBuilder::maximize()`] with
+    /// [`BuildError::MinimizeOnly`] if it returns true. By default, it
+    /// returns false.
+    fn is_minimize_only(&self) -> bool {
+        false
+    }
+}
+
+// Return true if any variable of the objective function is not continuous,
+// where the dimension is given to avoid calling the bounds.
+pub(crate) fn is_discrete<F: ObjFunc>(func: &F, dim: usize) -> bool {
+    (0..dim).any(|s| func.var_kind(s) != VarKind::Continuous)
+}
+
+// Snap the design variables to the kinds of the objective function.
+pub(crate) fn repair<F: ObjFunc>(func: &F, xs: &mut [f64]) {
+    for (s, x) in xs.iter_mut().enumerate() {
+        *x = func.var_kind(s).snap(*x);
+    }
+}
+
+// Return the snapped design variables, without copying the continuous ones.
+pub(crate) fn repaired<'a, F: ObjFunc>(func: &F, xs: &'a [f64]) -> Cow<'a, [f64]> {
+    if !is_discrete(func, xs.len()) {
+        return Cow::Borrowed(xs);
+    }
+    let mut xs = xs.to_vec();
+    repair(func, &mut xs);
+    Cow::Owned(xs)
+}
+
+/// A trait for the side metrics of the objective function.
+///
+/// The metrics are the auxiliary diagnostics of a solution, such as the
+/// breakdown of the sub-costs, which are not used in the optimization. Use
+/// [`Solver::best_metrics()`] to recompute them for the best solution once,
+/// instead of carrying them in the fitness value of every evaluation.
+///
+/// ```
+/// use metaheuristics_nature::{Bounded, ObjFunc, ObjMetrics};
+///
+/// struct MyFunc;
+///
+/// impl Bounded for MyFunc {
+///     fn bound(&self) -> &[[f64; 2]] {
+///         &[[0., 50.]; 3]
+///     }
+/// }
+///
+/// impl ObjFunc for MyFunc {
+///     type Ys = f64;
+///
+///     fn fitness(&self, x: &[f64]) -> Self::Ys {
+///         x[0] * x[0] + x[1] * x[1] + x[2] * x[2]
+///     }
+/// }
+///
+/// impl ObjMetrics for MyFunc {
+///     // The sub-costs of each variable
+///     type Metrics = [f64; 3];
+///
+///     fn metrics(&self, x: &[f64]) -> Self::Metrics {
+///         [x[0] * x[0], x[1] * x[1], x[2] * x[2]]
+///     }
+/// }
+/// ```
+///
+/// The objective functions without metrics do not need to implement this
+/// trait.
+pub trait ObjMetrics: ObjFunc {
+    /// Type of the side metrics.
+    type Metrics;
+
+    /// Return the side metrics of the design variables.
+    fn metrics(&self, xs: &[f64]) -> Self::Metrics;
+}
+
+/// A trait for the constraints of the objective function.
+///
+/// The constraints are usually handled by the penalty in
+/// [`ObjFunc::fitness()`], this trait reports whether the design variables
+/// satisfy all of them, see [`Ctx::feasible_ratio()`].
+///
+/// ```
+/// use metaheuristics_nature::{Bounded, Constrained, ObjFunc};
+///
+/// struct MyFunc;
+///
+/// impl Bounded for MyFunc {
+///     fn bound(&self) -> &[[f64; 2]] {
+///         &[[-50., 50.]; 2]
+///     }
+/// }
+///
+/// impl ObjFunc for MyFunc {
+///     type Ys = f64;
+///
+///     fn fitness(&self, x: &[f64]) -> Self::Ys {
+///         // Penalty of x[0] >= 1
+///         x[0] * x[0] + x[1] * x[1] + 1e3 * (1. - x[0]).max(0.)
+///     }
+/// }
+///
+/// impl Constrained for MyFunc {
+///     fn is_feasible(&self, x: &[f64]) -> bool {
+///         x[0] >= 1.
+///     }
+/// }
+/// ```
+pub trait Constrained: ObjFunc {
+    /// Return true if the design variables satisfy all constraints.
+    ///
+    /// This function should be cheap.
+    fn is_feasible(&self, xs: &[f64]) -> bool;
+}
+
+/// Define a named objective function type in a few lines.
+///
+/// It generates the struct, the [`Bounded`] implementation from a constant
+/// bound array, and the [`ObjFunc`] implementation from the `fitness`
+/// function. The optional `metrics` function generates the [`ObjMetrics`]
+/// implementation. Unlike [`Fx`], the struct can carry the state fields.
+///
+/// ```
+/// use core::sync::atomic::{AtomicUsize, Ordering};
+/// use metaheuristics_nature::{obj_func, De, Solver};
+///
+/// obj_func! {
+///     /// A shifted sphere that counts its evaluations.
+///     #[derive(Default)]
+///     pub struct Shifted {
+///         pub shift: f64,
+///         pub evals: AtomicUsize,
+///     }
+///     bound = [[-10., 10.]; 3];
+///     fn fitness(&self, xs) -> f64 {
+///         self.evals.fetch_add(1, Ordering::Relaxed);
+///         xs.iter().map(|x| (x - self.shift) * (x - self.shift)).sum()
+///     }
+///     fn metrics(&self, xs) -> Vec<f64> {
+///         xs.iter().map(|x| x - self.shift).collect()
+///     }
+/// }
+///
+/// let f = Shifted { shift: 1., ..Default::default() };
+/// let s = Solver::build(De::default(), f)
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .solve();
+/// println!("{} evaluations", s.func().evals.load(Ordering::Relaxed));
+/// println!("{:?}", s.best_metrics());
+/// ```
+#[macro_export]
+macro_rules! obj_func {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident { $($fields:tt)* }
+        bound = $bound:expr;
+        fn fitness(&$self:ident, $xs:ident) -> $ys:ty $fitness:block
+        $(fn metrics(&$self_m:ident, $xs_m:ident) -> $metrics:ty $metrics_body:block)?
+    ) => {
+        $(#[$meta])*
+        $vis struct $name { $($fields)* }
+
+        impl $crate::Bounded for $name {
+            fn bound(&self) -> &[[f64; 2]] {
+                const BOUND: &[[f64; 2]] = &$bound;
+                BOUND
+            }
+        }
+
+        impl $crate::ObjFunc for $name {
+            type Ys = $ys;
+
+            fn fitness(&$self, $xs: &[f64]) -> Self::Ys $fitness
+        }
+
+        $(impl $crate::ObjMetrics for $name {
+            type Metrics = $metrics;
+
+            fn metrics(&$self_m, $xs_m: &[f64]) -> Self::Metrics $metrics_body
+        })?
+    };
 }
@@ -1,6 +1,6 @@
 //! Single/Multi-objective best containers.
 use crate::prelude::*;
-use alloc::vec::Vec;
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use core::iter::zip;
 
 /// Single best element container.
@@ -8,6 +8,7 @@ use core::iter::zip;
 pub struct SingleBest<T: Fitness> {
     xs: Option<Vec<f64>>,
     ys: Option<T>,
+    compare: Comparator<T>,
 }
 
 impl<T: Fitness> SingleBest<T> {
@@ -28,6 +29,9 @@ pub struct Pareto<T: Fitness> {
     xs: Vec<Vec<f64>>,
     ys: Vec<T>,
     limit: usize,
+    archive: Option<Box<Self>>,
+    revision: u64,
+    truncation: Option<Arc<dyn Truncation>>,
 }
 
 impl<T: Fitness> Pareto<T> {
@@ -56,7 +60,77 @@ impl<T: Fitness> Pareto<T> {
         &self.ys
     }
 
-    fn update_no_limit(&mut self, xs: &[f64], ys: &T) {
+    /// The hypervolume of the front bounded by the `reference` point, for the
+    /// minimization, where the objectives are obtained from
+    /// [`Fitness::objectives()`].
+    ///
+    /// It is exact by [`hypervolume()`] for up to 5 objectives, otherwise, it
+    /// is estimated by [`hypervolume_mc()`] with 100000 samples and a fixed
+    /// seed, so the same front has the same value. It is cheap enough to be
+    /// recorded in the callback for the small fronts.
+    ///
+    /// The objectives are required, since the default [`Fitness::objectives()`]
+    /// is empty.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestMO as MyFunc;
+    ///
+    /// let mut history = Vec::new();
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .callback(|ctx| history.push(ctx.best.hypervolume(&[100., 100.])))
+    ///     .solve();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of the objectives is not the same as the reference
+    /// point.
+    pub fn hypervolume(&self, reference: &[f64]) -> f64 {
+        let front = self.ys.iter().map(T::objectives).collect::<Vec<_>>();
+        if reference.len() <= 5 {
+            hypervolume(&front, reference)
+        } else {
+            hypervolume_mc(&front, reference, 100000, &mut Rng::new(SeedOpt::U64(0)))
+        }
+    }
+
+    /// Get the unbounded archive of all non-dominated elements ever seen, see
+    /// [`SolverBuilder::full_archive()`].
+    pub fn archive(&self) -> Option<&Self> {
+        self.archive.as_deref()
+    }
+
+    /// Get the revision number of the front, which is increased when the
+    /// front is changed.
+    ///
+    /// The revision may be increased without a visible change if the new
+    /// elements are all pruned by the limit in a batch update.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    // The index of the element to be pruned.
+    fn worst(&self) -> usize {
+        if let Some(truncation) = &self.truncation {
+            let front = self.ys.iter().map(T::objectives).collect::<Vec<_>>();
+            if front.iter().all(|ys| !ys.is_empty()) {
+                return truncation.worst(&front);
+            }
+        }
+        let (i, _) = (self.ys.iter().map(T::eval).enumerate())
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+            .unwrap();
+        i
+    }
+
+    // Return true if the front is changed.
+    fn update_no_limit(&mut self, xs: &[f64], ys: &T) -> bool {
+        if let Some(archive) = &mut self.archive {
+            archive.update_no_limit(xs, ys);
+        }
         // Remove dominated solutions
         let mut has_dominated = false;
         for i in (0..self.xs.len()).rev() {
@@ -66,12 +140,13 @@ impl<T: Fitness> Pareto<T> {
                 self.xs.swap_remove(i);
                 self.ys.swap_remove(i);
             } else if !has_dominated && ys_curr.is_dominated(ys) {
-                return;
+                return false;
             }
         }
         // Add the new solution
         self.xs.push(xs.to_vec());
         self.ys.push(ys.clone());
+        true
     }
 }
 
@@ -92,6 +167,46 @@ pub trait Best: MaybeParallel {
     type Item: Fitness;
     /// Create a new best element container.
     fn from_limit(limit: usize) -> Self;
+    /// Set the comparison of the fitness values before updating, see
+    /// [`SolverBuilder::maximize()`] and [`SolverBuilder::compare()`].
+    ///
+    /// It does nothing by default, the multi-objective containers keep
+    /// [`Fitness::is_dominated()`] as the dominance.
+    fn set_compare(&mut self, compare: Comparator<Self::Item>) {
+        let _ = compare;
+    }
+    /// Keep all non-dominated elements ever seen in an unbounded archive
+    /// before updating, see [`SolverBuilder::full_archive()`].
+    ///
+    /// It does nothing by default, the single-objective containers have no
+    /// front.
+    fn set_archive(&mut self, archive: bool) {
+        let _ = archive;
+    }
+    /// Set the strategy to prune the elements over the limit before updating,
+    /// see [`SolverBuilder::truncation()`].
+    ///
+    /// It does nothing by default, the single-objective containers have no
+    /// front.
+    fn set_truncation(&mut self, truncation: Arc<dyn Truncation>) {
+        let _ = truncation;
+    }
+    /// Return true if any best element dominates the candidate by its first
+    /// objectives, see [`Fitness::dominates_prefix()`].
+    ///
+    /// It returns false by default, the single-objective containers have no
+    /// partial comparison.
+    fn dominates_prefix(&self, prefix: &[f64]) -> bool {
+        let _ = prefix;
+        false
+    }
+    /// Snap the design variables of the best elements in place, see
+    /// [`ObjFunc::var_kind()`].
+    ///
+    /// It does nothing by default.
+    fn repair(&mut self, repair: &dyn Fn(&mut [f64])) {
+        let _ = repair;
+    }
     /// Update the best element.
     fn update(&mut self, xs: &[f64], ys: &Self::Item);
     /// Update the best elements from a batch.
@@ -114,6 +229,12 @@ pub trait Best: MaybeParallel {
     }
     /// Get the current best element.
     fn as_result(&self) -> (&[f64], &Self::Item);
+    /// Get all best elements, such as the elements of the front.
+    ///
+    /// It returns the current best element ([`Best::as_result()`]) by default.
+    fn elements(&self) -> Vec<(&[f64], &Self::Item)> {
+        alloc::vec![self.as_result()]
+    }
     /// Get the current best fitness value.
     fn as_result_fit(&self) -> &Self::Item {
         self.as_result().1
@@ -136,12 +257,22 @@ impl<T: Fitness> Best for SingleBest<T> {
     type Item = T;
 
     fn from_limit(_limit: usize) -> Self {
-        Self { xs: None, ys: None }
+        Self { xs: None, ys: None, compare: Comparator::default() }
+    }
+
+    fn set_compare(&mut self, compare: Comparator<Self::Item>) {
+        self.compare = compare;
+    }
+
+    fn repair(&mut self, repair: &dyn Fn(&mut [f64])) {
+        if let Some(xs) = &mut self.xs {
+            repair(xs);
+        }
     }
 
     fn update(&mut self, xs: &[f64], ys: &Self::Item) {
         if let (Some(best), Some(best_f)) = (&mut self.xs, &mut self.ys) {
-            if ys.is_dominated(best_f) {
+            if self.compare.is_better(ys, best_f) {
                 *best = xs.to_vec();
                 *best_f = ys.clone();
             }
@@ -170,21 +301,52 @@ impl<T: Fitness> Best for Pareto<T> {
     type Item = T;
 
     fn from_limit(limit: usize) -> Self {
-        let xs = Vec::with_capacity(limit + 1);
-        let ys = Vec::with_capacity(limit + 1);
-        Self { xs, ys, limit }
+        // No reservation for the unbounded front
+        let cap = if limit == usize::MAX { 0 } else { limit + 1 };
+        let xs = Vec::with_capacity(cap);
+        let ys = Vec::with_capacity(cap);
+        Self {
+            xs,
+            ys,
+            limit,
+            archive: None,
+            revision: 0,
+            truncation: None,
+        }
+    }
+
+    fn set_archive(&mut self, archive: bool) {
+        self.archive = archive.then(|| Box::new(Self::from_limit(usize::MAX)));
+    }
+
+    fn set_truncation(&mut self, truncation: Arc<dyn Truncation>) {
+        self.truncation = Some(truncation);
+    }
+
+    fn dominates_prefix(&self, prefix: &[f64]) -> bool {
+        self.ys.iter().any(|ys| ys.dominates_prefix(prefix))
+    }
+
+    fn repair(&mut self, repair: &dyn Fn(&mut [f64])) {
+        self.xs.iter_mut().for_each(|xs| repair(xs));
+        if let Some(archive) = &mut self.archive {
+            archive.repair(repair);
+        }
     }
 
     fn update(&mut self, xs: &[f64], ys: &Self::Item) {
-        self.update_no_limit(xs, ys);
+        let mut changed = self.update_no_limit(xs, ys);
         // Prune the solution set
         if self.xs.len() > self.limit {
-            let (i, _) = (self.ys.iter().map(T::eval).enumerate())
-                .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
-                .unwrap();
+            let i = self.worst();
+            // The new solution is the worst one
+            changed &= i != self.xs.len() - 1;
             self.xs.swap_remove(i);
             self.ys.swap_remove(i);
         }
+        if changed {
+            self.revision += 1;
+        }
     }
 
     fn update_all<'a, Ix, Iy>(&mut self, pool: Ix, pool_y: Iy)
@@ -192,12 +354,25 @@ impl<T: Fitness> Best for Pareto<T> {
         Ix: IntoIterator<Item = &'a Vec<f64>>,
         Iy: IntoIterator<Item = &'a Self::Item>,
     {
+        let mut changed = false;
         for (xs, ys) in zip(pool, pool_y) {
-            self.update_no_limit(xs, ys);
+            changed |= self.update_no_limit(xs, ys);
+        }
+        if changed {
+            self.revision += 1;
         }
         if self.xs.len() <= self.limit {
             return;
         }
+        // Prune one by one, the strategies are changed by each removal
+        if self.truncation.is_some() {
+            while self.xs.len() > self.limit {
+                let i = self.worst();
+                self.xs.swap_remove(i);
+                self.ys.swap_remove(i);
+            }
+            return;
+        }
         // Prune the solution set
         let mut ind = (0..self.xs.len()).collect::<Vec<_>>();
         #[cfg(not(feature = "rayon"))]
@@ -241,6 +416,12 @@ impl<T: Fitness> Best for Pareto<T> {
         }
     }
 
+    fn elements(&self) -> Vec<(&[f64], &Self::Item)> {
+        zip(&self.xs, &self.ys)
+            .map(|(xs, ys)| (xs.as_slice(), ys))
+            .collect()
+    }
+
     fn into_result_fit(self) -> Self::Item {
         (self.ys.into_iter())
             .map(|ys| (ys.eval(), ys))
@@ -249,3 +430,421 @@ impl<T: Fitness> Best for Pareto<T> {
             .expect("No best element available")
     }
 }
+
+/// Fast non-dominated sorting of the fitness values.
+///
+/// Return the indices of each front, from the non-dominated front to the
+/// worst. The indices in a front are in ascending order. The elements that
+/// dominate each other (equal) are in the same front.
+///
+/// The pairwise comparisons are computed in parallel if the `rayon` feature is
+/// enabled, and the result is the same as the serial one.
+///
+/// ```
+/// use metaheuristics_nature::pareto::non_dominated_sort;
+///
+/// let fronts = non_dominated_sort(&[3., 1., 2., 1.]);
+/// assert_eq!(fronts, [vec![1, 3], vec![2], vec![0]]);
+/// ```
+pub fn non_dominated_sort<T: Fitness>(ys: &[T]) -> Vec<Vec<usize>> {
+    #[cfg(not(feature = "rayon"))]
+    let iter = 0..ys.len();
+    #[cfg(feature = "rayon")]
+    let iter = (0..ys.len()).into_par_iter();
+    // The number of the dominating elements, and the dominated indices
+    let (mut count, dominated): (Vec<_>, Vec<_>) = iter
+        .map(|i| {
+            let mut count = 0;
+            let mut dominated = Vec::new();
+            for (j, y) in ys.iter().enumerate() {
+                match (ys[i].is_dominated(y), y.is_dominated(&ys[i])) {
+                    (true, false) => dominated.push(j),
+                    (false, true) => count += 1,
+                    _ => (),
+                }
+            }
+            (count, dominated)
+        })
+        .unzip();
+    let mut fronts = Vec::new();
+    let mut front = (0..ys.len()).filter(|i| count[*i] == 0).collect::<Vec<_>>();
+    while !front.is_empty() {
+        let mut next = Vec::new();
+        for &i in &front {
+            for &j in &dominated[i] {
+                count[j] -= 1;
+                if count[j] == 0 {
+                    next.push(j);
+                }
+            }
+        }
+        next.sort_unstable();
+        fronts.push(core::mem::replace(&mut front, next));
+    }
+    fronts
+}
+
+/// A strategy to prune the Pareto front to the limit, see
+/// [`SolverBuilder::truncation()`].
+///
+/// The strategies work on the objective values from
+/// [`Fitness::objectives()`], the elements are removed one by one.
+pub trait Truncation: MaybeParallel + core::fmt::Debug {
+    /// Return the index of the element to be removed from the front.
+    ///
+    /// The front is not empty, and all objective vectors have the same length.
+    fn worst(&self, front: &[Vec<f64>]) -> usize;
+}
+
+/// Remove the element with the smallest [`crowding_distance()`], as NSGA-II.
+///
+/// The boundary elements of each objective have infinite distance.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CrowdingDistance;
+
+impl Truncation for CrowdingDistance {
+    fn worst(&self, front: &[Vec<f64>]) -> usize {
+        argmin(&crowding_distance(front))
+    }
+}
+
+/// Remove the element with the smallest exclusive hypervolume contribution.
+///
+/// The extreme elements of each objective are always kept. The reference
+/// point is beyond the worst values of the front by 10% of its range. It
+/// yields better-spread fronts than [`CrowdingDistance`], but computing the
+/// exact hypervolume is expensive for many objectives.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HypervolumeContribution;
+
+impl Truncation for HypervolumeContribution {
+    fn worst(&self, front: &[Vec<f64>]) -> usize {
+        let reference = (0..front[0].len())
+            .map(|k| {
+                let (min, max) = front
+                    .iter()
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), ys| {
+                        (a.min(ys[k]), b.max(ys[k]))
+                    });
+                max + if max > min { 0.1 * (max - min) } else { 1. }
+            })
+            .collect::<Vec<_>>();
+        let extremes = extremes(front);
+        let total = hypervolume(front, &reference);
+        let contrib = (0..front.len())
+            .map(|i| {
+                if extremes.contains(&i) {
+                    return f64::INFINITY;
+                }
+                let rest = (front.iter().enumerate())
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, ys)| ys.clone())
+                    .collect::<Vec<_>>();
+                total - hypervolume(&rest, &reference)
+            })
+            .collect::<Vec<_>>();
+        argmin(&contrib)
+    }
+}
+
+/// Remove the element closest to its neighbor, which improves the spacing
+/// (uniformity) of the front.
+///
+/// The distances are normalized by the range of each objective. Between the
+/// closest pair, the one closer to its second neighbor is removed. The extreme
+/// elements of each objective are always kept.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Spacing;
+
+impl Truncation for Spacing {
+    fn worst(&self, front: &[Vec<f64>]) -> usize {
+        let n = front.len();
+        let m = front[0].len();
+        let width = (0..m)
+            .map(|k| {
+                let (min, max) = front
+                    .iter()
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), ys| {
+                        (a.min(ys[k]), b.max(ys[k]))
+                    });
+                if max > min {
+                    (max - min).recip()
+                } else {
+                    0.
+                }
+            })
+            .collect::<Vec<_>>();
+        let extremes = extremes(front);
+        // The nearest and the second nearest distances
+        let near = (0..n)
+            .map(|i| {
+                if extremes.contains(&i) {
+                    return [f64::INFINITY; 2];
+                }
+                (0..n)
+                    .filter(|j| *j != i)
+                    .fold([f64::INFINITY; 2], |[d1, d2], j| {
+                        let d = crate::math::dist2(&front[i], &front[j], Some(&width));
+                        if d < d1 {
+                            [d, d1]
+                        } else {
+                            [d1, d.min(d2)]
+                        }
+                    })
+            })
+            .collect::<Vec<_>>();
+        (0..n)
+            .min_by(|a, b| {
+                let [a1, a2] = near[*a];
+                let [b1, b2] = near[*b];
+                a1.total_cmp(&b1).then(a2.total_cmp(&b2))
+            })
+            .unwrap()
+    }
+}
+
+// The index of the smallest value, the first one if tied.
+fn argmin(v: &[f64]) -> usize {
+    (0..v.len()).min_by(|a, b| v[*a].total_cmp(&v[*b])).unwrap()
+}
+
+// The indices of the best element of each objective.
+fn extremes(front: &[Vec<f64>]) -> Vec<usize> {
+    (0..front[0].len())
+        .map(|k| {
+            (0..front.len())
+                .min_by(|a, b| front[*a][k].total_cmp(&front[*b][k]))
+                .unwrap()
+        })
+        .collect()
+}
+
+/// The crowding distance of each element of the front, the sum of the
+/// normalized side lengths of the cuboid formed by its nearest neighbors of
+/// each objective, as NSGA-II.
+///
+/// The boundary elements of each objective have infinite distance.
+///
+/// ```
+/// use metaheuristics_nature::pareto::crowding_distance;
+///
+/// let front = [vec![0., 4.], vec![1., 1.], vec![4., 0.]];
+/// assert_eq!(crowding_distance(&front), [f64::INFINITY, 2., f64::INFINITY]);
+/// ```
+pub fn crowding_distance(front: &[Vec<f64>]) -> Vec<f64> {
+    let n = front.len();
+    let mut dist = alloc::vec![0.; n];
+    let Some(first) = front.first() else {
+        return dist;
+    };
+    let mut ind = (0..n).collect::<Vec<_>>();
+    for k in 0..first.len() {
+        let v = front.iter().map(|ys| ys[k]).collect::<Vec<_>>();
+        ind.sort_unstable_by(|a, b| v[*a].total_cmp(&v[*b]));
+        let width = v[ind[n - 1]] - v[ind[0]];
+        dist[ind[0]] = f64::INFINITY;
+        dist[ind[n - 1]] = f64::INFINITY;
+        if width > 0. {
+            for w in ind.windows(3) {
+                dist[w[1]] += (v[w[2]] - v[w[0]]) / width;
+            }
+        }
+    }
+    dist
+}
+
+/// The hypervolume dominated by the front and bounded by the reference point,
+/// for the minimization.
+///
+/// The elements beyond the reference point are ignored in that objective. The
+/// exact computation slices the objectives recursively, which is exponential
+/// to the number of the objectives.
+///
+/// ```
+/// use metaheuristics_nature::pareto::hypervolume;
+///
+/// let front = [vec![1., 3.], vec![2., 2.], vec![3., 1.]];
+/// assert_eq!(hypervolume(&front, &[4., 4.]), 6.);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the length of any element is not the same as the reference
+/// point.
+pub fn hypervolume(front: &[Vec<f64>], reference: &[f64]) -> f64 {
+    check_objectives(front, reference);
+    let front = front.iter().map(Vec::as_slice).collect::<Vec<_>>();
+    hypervolume_slices(front, reference)
+}
+
+/// The Monte Carlo estimation of the [`hypervolume()`] by `samples` uniform
+/// points in the box between the best values of the front and the reference
+/// point.
+///
+/// The error of the estimation is about `1 / sqrt(samples)` of the box
+/// volume, but the cost is linear to the number of the objectives, which is
+/// suitable for many objectives.
+///
+/// ```
+/// use metaheuristics_nature::{pareto::hypervolume_mc, random::Rng};
+///
+/// let front = [vec![1., 3.], vec![2., 2.], vec![3., 1.]];
+/// let hv = hypervolume_mc(&front, &[4., 4.], 100000, &mut Rng::new(0.into()));
+/// assert!((hv - 6.).abs() < 0.1);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the length of any element is not the same as the reference
+/// point.
+pub fn hypervolume_mc(front: &[Vec<f64>], reference: &[f64], samples: usize, rng: &mut Rng) -> f64 {
+    check_objectives(front, reference);
+    let lower = (0..reference.len())
+        .map(|k| front.iter().map(|ys| ys[k]).fold(reference[k], f64::min))
+        .collect::<Vec<_>>();
+    let volume = zip(&lower, reference).map(|(a, b)| b - a).product::<f64>();
+    if front.is_empty() || samples == 0 || volume <= 0. {
+        return 0.;
+    }
+    let mut p = alloc::vec![0.; reference.len()];
+    let hits = (0..samples)
+        .filter(|_| {
+            for (p, (a, b)) in zip(&mut p, zip(&lower, reference)) {
+                *p = rng.range(*a..*b);
+            }
+            (front.iter()).any(|ys| zip(ys, &p).all(|(y, p)| y <= p))
+        })
+        .count();
+    volume * hits as f64 / samples as f64
+}
+
+/// The reference point of the scalarization, see [`Ctx::tchebycheff()`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RefPoint {
+    /// A fixed point given by the user
+    Fixed(Vec<f64>),
+    /// The ideal point, updated as the search discovers better values, see
+    /// [`Ctx::ideal_point()`]
+    #[default]
+    Adaptive,
+}
+
+impl RefPoint {
+    /// Get the reference point with the current ideal point.
+    pub fn resolve<'a>(&'a self, ideal: &'a [f64]) -> &'a [f64] {
+        match self {
+            Self::Fixed(point) => point,
+            Self::Adaptive => ideal,
+        }
+    }
+}
+
+/// The weighted Tchebycheff scalarization of the objectives, the maximum
+/// weighted distance to the reference point.
+///
+/// A decomposition-based method minimizes it for each weight vector to
+/// approach a different part of the front.
+///
+/// ```
+/// use metaheuristics_nature::pareto::tchebycheff;
+///
+/// assert_eq!(tchebycheff(&[2., 3.], &[0.5, 0.5], &[0., 0.]), 1.5);
+/// ```
+pub fn tchebycheff(objectives: &[f64], weights: &[f64], reference: &[f64]) -> f64 {
+    zip(zip(objectives, weights), reference)
+        .map(|((y, w), z)| w * (y - z).abs())
+        .fold(0., f64::max)
+}
+
+/// Schott's spacing metric of the front, the standard deviation of the
+/// Manhattan distances between each element and its nearest neighbor.
+///
+/// The zero value means the elements are evenly spaced. Return zero if the
+/// front has less than two elements.
+///
+/// ```
+/// use metaheuristics_nature::pareto::spacing;
+///
+/// let front = [vec![0., 2.], vec![1., 1.], vec![2., 0.]];
+/// assert_eq!(spacing(&front), 0.);
+/// ```
+pub fn spacing(front: &[Vec<f64>]) -> f64 {
+    let n = front.len();
+    if n < 2 {
+        return 0.;
+    }
+    let d = (0..n)
+        .map(|i| {
+            (0..n)
+                .filter(|j| *j != i)
+                .map(|j| zip(&front[i], &front[j]).map(|(a, b)| (a - b).abs()).sum())
+                .fold(f64::INFINITY, f64::min)
+        })
+        .collect::<Vec<_>>();
+    let mean = d.iter().sum::<f64>() / n as f64;
+    let var = d.iter().map(|d| (d - mean) * (d - mean)).sum::<f64>() / (n - 1) as f64;
+    crate::math::sqrt(var)
+}
+
+/// The maximum spread (extent) of the front, the diagonal length of the
+/// bounding box of the objectives.
+///
+/// Return zero if the front is empty.
+///
+/// ```
+/// use metaheuristics_nature::pareto::spread;
+///
+/// let front = [vec![0., 4.], vec![1., 1.], vec![3., 0.]];
+/// assert_eq!(spread(&front), 5.);
+/// ```
+pub fn spread(front: &[Vec<f64>]) -> f64 {
+    let Some(first) = front.first() else {
+        return 0.;
+    };
+    let sum = (0..first.len())
+        .map(|k| {
+            let (min, max) = (front.iter())
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), ys| {
+                    (a.min(ys[k]), b.max(ys[k]))
+                });
+            (max - min) * (max - min)
+        })
+        .sum();
+    crate::math::sqrt(sum)
+}
+
+fn check_objectives(front: &[Vec<f64>], reference: &[f64]) {
+    assert!(
+        front.iter().all(|ys| ys.len() == reference.len()),
+        "The objectives should have the same length as the reference point, \
+         see `Fitness::objectives()`"
+    );
+}
+
+fn hypervolume_slices(mut front: Vec<&[f64]>, reference: &[f64]) -> f64 {
+    let Some((&r, rest)) = reference.split_last() else {
+        return 0.;
+    };
+    front.retain(|ys| ys[rest.len()] < r);
+    if front.is_empty() {
+        return 0.;
+    }
+    if rest.is_empty() {
+        let min = front.iter().map(|ys| ys[0]).fold(f64::INFINITY, f64::min);
+        return r - min;
+    }
+    // Slice by the last objective
+    let k = rest.len();
+    front.sort_unstable_by(|a, b| a[k].total_cmp(&b[k]));
+    let mut volume = 0.;
+    for i in 0..front.len() {
+        let upper = front.get(i + 1).map_or(r, |ys| ys[k]);
+        let depth = upper - front[i][k];
+        if depth > 0. {
+            let slice = front[..=i].iter().map(|ys| &ys[..k]).collect();
+            volume += depth * hypervolume_slices(slice, rest);
+        }
+    }
+    volume
+}
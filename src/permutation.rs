@@ -0,0 +1,64 @@
+//! Random-key encoding of the permutation problems.
+use alloc::vec::Vec;
+
+/// Decode the random keys into a permutation, which is the indices of the
+/// keys in ascending order.
+///
+/// The permutation problems (such as the traveling salesman problem) are
+/// encoded by the random keys, where each variable is the key of an item, so
+/// [`ObjFunc::fitness()`](crate::ObjFunc::fitness) can interpret its design
+/// variables as an order. The bounds of the variables should be the same,
+/// such as `[[0., 1.]; n]`.
+///
+/// ```
+/// use metaheuristics_nature::permutation_from_keys;
+///
+/// assert_eq!(permutation_from_keys(&[0.3, 0.1, 0.7, 0.2]), [1, 3, 0, 2]);
+/// ```
+///
+/// See also [`keys_from_permutation()`], [`Pool::Permutation`], and
+/// [`PermGa`].
+///
+/// [`Pool::Permutation`]: crate::Pool::Permutation
+/// [`PermGa`]: crate::PermGa
+pub fn permutation_from_keys(keys: &[f64]) -> Vec<usize> {
+    let mut perm = (0..keys.len()).collect::<Vec<_>>();
+    perm.sort_by(|&a, &b| keys[a].total_cmp(&keys[b]));
+    perm
+}
+
+/// Encode a permutation into the random keys, which is the inverse of
+/// [`permutation_from_keys()`].
+///
+/// The keys are evenly spaced in the bounds by the positions of the items.
+///
+/// ```
+/// use metaheuristics_nature::{keys_from_permutation, permutation_from_keys};
+///
+/// let keys = keys_from_permutation(&[2, 0, 1], &[[0., 1.]; 3]);
+/// assert_eq!(permutation_from_keys(&keys), [2, 0, 1]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the lengths of the permutation and the bounds are not the same,
+/// or the bounds are not the same, since the keys of the different bounds
+/// are not comparable.
+pub fn keys_from_permutation(perm: &[usize], bound: &[[f64; 2]]) -> Vec<f64> {
+    assert_eq!(
+        perm.len(),
+        bound.len(),
+        "Permutation should match the dimension"
+    );
+    assert!(
+        bound.windows(2).all(|w| w[0] == w[1]),
+        "Permutation variables should have the same bounds"
+    );
+    let n = perm.len() as f64;
+    let mut keys = alloc::vec![0.; perm.len()];
+    for (k, &i) in perm.iter().enumerate() {
+        let [min, max] = bound[i];
+        keys[i] = min + (k as f64 + 0.5) / n * (max - min);
+    }
+    keys
+}
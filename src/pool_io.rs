@@ -0,0 +1,177 @@
+//! Loading the initial pool from the files, see [`Pool::from_csv()`] and
+//! [`Pool::from_npy()`].
+use crate::prelude::*;
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read, Result};
+
+fn invalid(msg: impl Into<String>) -> Error {
+    Error::new(ErrorKind::InvalidData, msg.into())
+}
+
+// Check the shape of the rows, the number of columns should be the same
+fn check_shape(pool: &[Vec<f64>]) -> Result<()> {
+    let Some(dim) = pool.first().map(Vec::len) else {
+        return Err(invalid("Pool should not be empty"));
+    };
+    match pool.iter().position(|xs| xs.len() != dim) {
+        Some(i) => Err(invalid(format!("Row {i} should have {dim} columns"))),
+        None => Ok(()),
+    }
+}
+
+impl<F: ObjFunc> Pool<'_, F> {
+    /// Load the pool from the CSV format, each row is an individual, as a
+    /// [`Pool::ReadyParams`].
+    ///
+    /// The values are separated by commas. The empty lines and the lines
+    /// starting with `#` are skipped, and the first line is skipped as the
+    /// header if it is not numeric, so the outputs of `numpy.savetxt()` and
+    /// `pandas.DataFrame.to_csv(index=False)` can be read directly.
+    ///
+    /// The rows should have the same length, otherwise an error of
+    /// [`ErrorKind::InvalidData`] is returned. The population size is decided
+    /// by the rows, and the dimension is checked in the initialization, see
+    /// [`Pool::ReadyParams`].
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Pool, Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let csv = "x0,x1,x2,x3\n1,2,3,4\n-4,-3,-2,-1\n";
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .init_pool(Pool::from_csv(csv.as_bytes()).unwrap())
+    ///     .solve();
+    /// ```
+    pub fn from_csv(reader: impl Read) -> Result<Self> {
+        let mut pool = Vec::new();
+        for (i, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let xs = line
+                .split(',')
+                .map(|v| v.trim().parse::<f64>())
+                .collect::<core::result::Result<Vec<_>, _>>();
+            match xs {
+                Ok(xs) => pool.push(xs),
+                // Header
+                Err(_) if pool.is_empty() && i == 0 => (),
+                Err(e) => return Err(invalid(format!("Line {}: {e}", i + 1))),
+            }
+        }
+        check_shape(&pool)?;
+        Ok(Self::ReadyParams(pool))
+    }
+
+    /// Load the pool from the NumPy `.npy` format, a 2D array of the shape
+    /// `(pop_num, dim)`, as a [`Pool::ReadyParams`].
+    ///
+    /// The data type should be `float64` or `float32` in any byte order, and
+    /// both the C and Fortran orders are supported. An error of
+    /// [`ErrorKind::InvalidData`] is returned for the other formats.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Pool, Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// // Saved by `numpy.save()`
+    /// # let file = {
+    /// #     let header = "{'descr': '<f8', 'fortran_order': False, 'shape': (2, 4), }";
+    /// #     let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    /// #     bytes.extend((header.len() as u16).to_le_bytes());
+    /// #     bytes.extend(header.bytes());
+    /// #     [1f64, 2., 3., 4., -4., -3., -2., -1.].iter().for_each(|x| bytes.extend(x.to_le_bytes()));
+    /// #     std::io::Cursor::new(bytes)
+    /// # };
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .init_pool(Pool::from_npy(file).unwrap())
+    ///     .solve();
+    /// ```
+    pub fn from_npy(mut reader: impl Read) -> Result<Self> {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic[..6] != b"\x93NUMPY" {
+            return Err(invalid("Magic string should be NumPy format"));
+        }
+        // The length of the header is u16 in version 1, otherwise u32
+        let len = if magic[6] == 1 {
+            let mut len = [0; 2];
+            reader.read_exact(&mut len)?;
+            u16::from_le_bytes(len) as usize
+        } else {
+            let mut len = [0; 4];
+            reader.read_exact(&mut len)?;
+            u32::from_le_bytes(len) as usize
+        };
+        let mut header = alloc::vec![0; len];
+        reader.read_exact(&mut header)?;
+        let header = String::from_utf8(header).map_err(|e| invalid(e.to_string()))?;
+        // A Python dict literal
+        let field = |key: &str| {
+            let start = header.find(&format!("'{key}'"))? + key.len() + 2;
+            let rest = header[start..].trim_start().strip_prefix(':')?.trim_start();
+            let end = if rest.starts_with('(') {
+                rest.find(')')? + 1
+            } else {
+                rest.find([',', '}'])?
+            };
+            Some(rest[..end].trim())
+        };
+        let descr = field("descr").ok_or_else(|| invalid("Header should have 'descr'"))?;
+        let (size, big) = match descr.trim_matches(['\'', '"']) {
+            "<f8" | "=f8" => (8, false),
+            ">f8" => (8, true),
+            "<f4" | "=f4" => (4, false),
+            ">f4" => (4, true),
+            descr => return Err(invalid(format!("Data type {descr} should be float"))),
+        };
+        let fortran = field("fortran_order") == Some("True");
+        let shape = (field("shape").ok_or_else(|| invalid("Header should have 'shape'"))?)
+            .trim_matches(['(', ')'])
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(|v| v.parse::<usize>().map_err(|e| invalid(e.to_string())))
+            .collect::<Result<Vec<_>>>()?;
+        let &[pop_num, dim] = shape.as_slice() else {
+            return Err(invalid("Array should be 2D"));
+        };
+        // The shape is untrusted, so the buffer grows with the actual data
+        let len = (pop_num.checked_mul(dim))
+            .and_then(|n| n.checked_mul(size))
+            .ok_or_else(|| invalid("Shape is too large"))?;
+        let mut data = Vec::new();
+        reader.take(len as u64).read_to_end(&mut data)?;
+        if data.len() != len {
+            return Err(invalid(format!("Data should have {len} bytes")));
+        }
+        let data = data
+            .chunks_exact(size)
+            .map(|b| match (size, big) {
+                (8, false) => f64::from_le_bytes(b.try_into().unwrap()),
+                (8, true) => f64::from_be_bytes(b.try_into().unwrap()),
+                (_, false) => f32::from_le_bytes(b.try_into().unwrap()) as f64,
+                (_, true) => f32::from_be_bytes(b.try_into().unwrap()) as f64,
+            })
+            .collect::<Vec<_>>();
+        let pool = (0..pop_num)
+            .map(|i| {
+                let index = |s| {
+                    if fortran {
+                        s * pop_num + i
+                    } else {
+                        i * dim + s
+                    }
+                };
+                (0..dim).map(|s| data[index(s)]).collect()
+            })
+            .collect::<Vec<_>>();
+        check_shape(&pool)?;
+        Ok(Self::ReadyParams(pool))
+    }
+}
@@ -12,6 +12,14 @@ use rand_chacha::ChaCha8Rng as ChaCha;
 /// The seed type of the ChaCha algorithm.
 pub type Seed = [u8; 32];
 
+/// The version of the random draw sequence.
+///
+/// A fixed seed only guarantees the same results in the same version, which is
+/// increased if the order or the usage of the random draws is changed. All
+/// draws are made through the methods of [`Rng`], so the sequence can be
+/// audited there. Use [`SeedOpt::Versioned`] to pin the version.
+pub const RNG_VERSION: u32 = 2;
+
 /// The seed option.
 ///
 /// Can be converted from `Option<u64>`, `u64`, and [`Seed`].
@@ -21,8 +29,26 @@ pub enum SeedOpt {
     U64(u64),
     /// Crypto seed series (32 bytes)
     Seed(Seed),
+    /// Seed series from u128, the little-endian bytes are the first half
+    U128(u128),
     /// Auto-decided crypto seed
     Entropy,
+    /// Seed from non-crypto u64 with the version of the draw sequence, see
+    /// [`RNG_VERSION`]
+    ///
+    /// It is an error if the version is not supported, instead of giving
+    /// different results silently.
+    Versioned(u64, u32),
+}
+
+impl SeedOpt {
+    /// Return true if the version of the draw sequence is supported.
+    pub fn is_supported(&self) -> bool {
+        match self {
+            Self::Versioned(_, v) => *v == RNG_VERSION,
+            _ => true,
+        }
+    }
 }
 
 impl From<Option<u64>> for SeedOpt {
@@ -46,22 +72,127 @@ impl From<Seed> for SeedOpt {
     }
 }
 
+/// The seeds of the independent random streams, see
+/// [`SolverBuilder::stream_seeds()`].
+///
+/// A `None` stream is drawn from the generator of the solver's seed, so the
+/// default value is the same as not separated.
+///
+/// [`SolverBuilder::stream_seeds()`]: crate::SolverBuilder::stream_seeds
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamSeeds {
+    /// Seed of the initial pool
+    pub init: Option<u64>,
+    /// Seed of the selection, which includes all draws of the methods except
+    /// the mutation
+    pub select: Option<u64>,
+    /// Seed of the mutation, see [`Rng::mutation()`]
+    ///
+    /// Only [`Rga`] draws its mutation from this stream. The other methods,
+    /// including the mutation of [`NsgaII`], [`PermGa`], and [`De`], draw from
+    /// the selection stream, so this seed has no effect on them.
+    ///
+    /// [`Rga`]: crate::Rga
+    /// [`NsgaII`]: crate::NsgaII
+    /// [`PermGa`]: crate::PermGa
+    /// [`De`]: crate::De
+    pub mutate: Option<u64>,
+}
+
 /// An uniformed random number generator.
+///
+/// The generator can be serialized with its exact position of the draw
+/// sequence if the `serde` feature is enabled, see [`SolverState`].
+///
+/// [`SolverState`]: crate::SolverState
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "RngState", into = "RngState"))]
 pub struct Rng {
     rng: ChaCha,
+    // A separated stream of the mutation
+    mutate: Option<alloc::boxed::Box<Self>>,
+}
+
+// The position of the draw sequence of the generator
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct RngState {
+    seed: Seed,
+    stream: u64,
+    word_pos: u128,
+    mutate: Option<alloc::boxed::Box<Self>>,
+}
+
+#[cfg(feature = "serde")]
+impl From<Rng> for RngState {
+    fn from(rng: Rng) -> Self {
+        Self {
+            seed: rng.rng.get_seed(),
+            stream: rng.rng.get_stream(),
+            word_pos: rng.rng.get_word_pos(),
+            mutate: rng
+                .mutate
+                .map(|rng| alloc::boxed::Box::new(Self::from(*rng))),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<RngState> for Rng {
+    fn from(state: RngState) -> Self {
+        let mut rng = ChaCha::from_seed(state.seed);
+        rng.set_stream(state.stream);
+        rng.set_word_pos(state.word_pos);
+        let mutate = state
+            .mutate
+            .map(|state| alloc::boxed::Box::new(Self::from(*state)));
+        Self { rng, mutate }
+    }
 }
 
 impl Rng {
     /// Create generator by a given seed.
     /// If none, create the seed from CPU random function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the version of [`SeedOpt::Versioned`] is not supported.
     pub fn new(seed: SeedOpt) -> Self {
+        assert!(seed.is_supported(), "Unsupported RNG version");
         let rng = match seed {
             SeedOpt::Seed(seed) => ChaCha::from_seed(seed),
-            SeedOpt::U64(seed) => ChaCha::seed_from_u64(seed),
+            SeedOpt::U128(seed) => {
+                let mut s = Seed::default();
+                s[..16].copy_from_slice(&seed.to_le_bytes());
+                ChaCha::from_seed(s)
+            }
+            SeedOpt::U64(seed) | SeedOpt::Versioned(seed, _) => ChaCha::seed_from_u64(seed),
             SeedOpt::Entropy => ChaCha::from_entropy(),
         };
-        Self { rng }
+        Self { rng, mutate: None }
+    }
+
+    /// Separate the stream of the mutation decisions by a seed, see
+    /// [`Rng::mutation()`].
+    pub fn with_mutation(self, seed: SeedOpt) -> Self {
+        let mutate = Some(alloc::boxed::Box::new(Self::new(seed)));
+        Self { mutate, ..self }
+    }
+
+    /// The generator of the mutation decisions, which is itself unless it is
+    /// separated by [`Rng::with_mutation()`].
+    ///
+    /// The methods draw the mutation decisions from it, so the sensitivity of
+    /// the results to the mutation can be isolated from the other draws. It
+    /// is only used by [`Rga`](crate::Rga) currently.
+    pub fn mutation(&mut self) -> &mut Self {
+        if self.mutate.is_some() {
+            self.mutate.as_deref_mut().unwrap()
+        } else {
+            self
+        }
     }
 
     /// Seed of this generator.
@@ -77,10 +208,15 @@ impl Rng {
         // Needs to "run" the RNG to avoid constantly opening new branches
         let stream = self.rng.get_stream();
         self.rng.set_stream(stream.wrapping_add(n as _));
+        // The mutation stream is forked as well
+        let mut mutate = (self.mutate.as_mut()).map(|rng| rng.stream(n).into_iter());
         (0..n)
             .map(|i| {
-                let mut rng = self.clone();
+                let mut rng = Self { rng: self.rng.clone(), mutate: None };
                 rng.rng.set_stream(stream.wrapping_add(i as _));
+                rng.mutate = (mutate.as_mut())
+                    .and_then(|it| it.next())
+                    .map(alloc::boxed::Box::new);
                 rng
             })
             .collect()
@@ -0,0 +1,70 @@
+//! The plot-ready data of the solver, see [`ReportExt`].
+use crate::prelude::*;
+use alloc::vec::Vec;
+
+/// The plot-ready series of the recorded data, which can be passed to the
+/// plotting libraries directly, such as `plotters` and `plotly`.
+///
+/// ```
+/// use metaheuristics_nature::{De, ReportExt, Solver};
+/// # use metaheuristics_nature::tests::TestObj as MyFunc;
+///
+/// let s = Solver::build(De::default(), MyFunc::new())
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .record_best(true)
+///     .solve();
+/// let (x, y) = s.convergence_series();
+/// assert_eq!(x.len(), y.len());
+/// ```
+pub trait ReportExt {
+    /// The objective function.
+    type Func: ObjFunc;
+
+    /// The convergence series of the best evaluation values at each
+    /// improvement, where the x-axis is the generations, or the numbers of
+    /// the evaluations if [`SolverBuilder::record_by_evals()`] is enabled.
+    ///
+    /// The series is empty if the best history is not recorded, see
+    /// [`SolverBuilder::record_best()`]. Use the step line to plot it.
+    fn convergence_series(&self) -> (Vec<f64>, Vec<f64>)
+    where
+        <<Self::Func as ObjFunc>::Ys as Fitness>::Eval: Into<f64>;
+
+    /// The first two objectives of the Pareto front, sorted by the first
+    /// objective, for the 2D front plot.
+    ///
+    /// The objectives are obtained from [`Fitness::objectives()`], and the
+    /// elements with fewer than two objectives are skipped.
+    fn front_series(&self) -> Vec<(f64, f64)>
+    where
+        <Self::Func as ObjFunc>::Ys:
+            Fitness<Best<<Self::Func as ObjFunc>::Ys> = Pareto<<Self::Func as ObjFunc>::Ys>>;
+}
+
+impl<F: ObjFunc> ReportExt for Solver<F> {
+    type Func = F;
+
+    fn convergence_series(&self) -> (Vec<f64>, Vec<f64>)
+    where
+        <F::Ys as Fitness>::Eval: Into<f64>,
+    {
+        (self.best_history().into_iter())
+            .map(|(key, y)| (key as f64, y))
+            .unzip()
+    }
+
+    fn front_series(&self) -> Vec<(f64, f64)>
+    where
+        F::Ys: Fitness<Best<F::Ys> = Pareto<F::Ys>>,
+    {
+        let mut series = (self.front_objectives().into_iter())
+            .filter_map(|obj| match obj[..] {
+                [a, b, ..] => Some((a, b)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        series.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        series
+    }
+}
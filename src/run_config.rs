@@ -0,0 +1,27 @@
+use crate::prelude::*;
+use alloc::{string::String, vec::Vec};
+
+/// A reproducible description of the solver configuration, see
+/// [`SolverBuilder::run_config()`].
+///
+/// It can be serialized with the `serde` feature for the experiment
+/// provenance.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct RunConfig {
+    /// Method name, see [`Algorithm::name()`]
+    pub method: String,
+    /// Hyperparameters of the method, see [`Algorithm::params()`]
+    pub params: Vec<(String, f64)>,
+    /// Population number
+    pub pop_num: usize,
+    /// Seed of the random number generator, `None` if auto-decided
+    pub seed: Option<Seed>,
+    /// Pareto front limit
+    pub pareto_limit: usize,
+    /// Label of the pool option
+    pub pool: String,
+    /// Labels of the termination conditions, the task is the first one
+    pub termination: Vec<String>,
+}
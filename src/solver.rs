@@ -1,5 +1,9 @@
-use crate::prelude::*;
+use crate::{
+    obj_func::{repair, repaired},
+    prelude::*,
+};
 use alloc::vec::Vec;
+use core::iter::zip;
 
 /// A public API for using optimization methods.
 ///
@@ -16,15 +20,27 @@ use alloc::vec::Vec;
 /// The settings are defined in the [`SolverBuilder`] type.
 #[must_use = "please call `Solver::best_parameters()` or other methods to get the answer"]
 pub struct Solver<F: ObjFunc> {
-    ctx: Ctx<F>,
-    seed: Seed,
+    pub(crate) ctx: Ctx<F>,
+    // The random number generator at the end, see `Solver::save_state()`
+    pub(crate) rng: Rng,
+    pub(crate) seed: Seed,
+    pub(crate) name: &'static str,
+    pub(crate) params: Vec<(&'static str, f64)>,
+    pub(crate) restarts: u64,
+    pub(crate) tracked: Vec<Vec<f64>>,
+    pub(crate) tracking_stopped: Option<u64>,
+    // The generations and the numbers of the evaluations of the improvements
+    pub(crate) best_history: Vec<([u64; 2], Vec<f64>, F::Ys)>,
+    // Whether the best history is recorded, and if it is by the evaluations
+    pub(crate) record_by_evals: Option<bool>,
+    pub(crate) diversity_history: Vec<(u64, f64)>,
+    #[cfg(feature = "std")]
+    pub(crate) gen_times: Vec<std::time::Duration>,
+    #[cfg(feature = "std")]
+    pub(crate) eval_log: Vec<(Vec<f64>, F::Ys)>,
 }
 
 impl<F: ObjFunc> Solver<F> {
-    pub(crate) fn new(ctx: Ctx<F>, seed: Seed) -> Self {
-        Self { ctx, seed }
-    }
-
     /// Get the reference of the objective function.
     ///
     /// It's useful when you need to get the preprocessed data from the
@@ -33,6 +49,46 @@ impl<F: ObjFunc> Solver<F> {
         &self.ctx.func
     }
 
+    /// Evaluate arbitrary variables with the objective function, such as the
+    /// neighbors of the best parameters or a user-specified design.
+    ///
+    /// The variables are repaired into the bounds of the objective function
+    /// as the moves of the methods if [`SolverBuilder::clamp_to_bounds()`] is
+    /// enabled, otherwise they are evaluated as is. The variables are snapped
+    /// as well, see [`ObjFunc::var_kind()`].
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve();
+    /// let mut xs = s.as_best_xs().to_vec();
+    /// xs[0] += 1e-3;
+    /// let ys = s.evaluate(&xs);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `xs` is not the dimension.
+    pub fn evaluate(&self, xs: &[f64]) -> F::Ys {
+        assert_eq!(
+            xs.len(),
+            self.ctx.dim(),
+            "Variables should match the dimension"
+        );
+        if !self.ctx.clamp_to_bounds() {
+            return self.ctx.func.fitness(&repaired(&self.ctx.func, xs));
+        }
+        let mut xs = zip(xs, &self.ctx.func_bound)
+            .map(|(x, [lb, ub])| x.clamp(*lb, *ub))
+            .collect::<Vec<_>>();
+        repair(&self.ctx.func, &mut xs);
+        self.ctx.func.fitness(&xs)
+    }
+
     /// Get the reference of the best set.
     ///
     /// Use [`Solver::as_best()`] to get the best parameters and the fitness
@@ -41,6 +97,16 @@ impl<F: ObjFunc> Solver<F> {
         &self.ctx.best
     }
 
+    /// Get the unbounded archive of all non-dominated solutions ever seen, or
+    /// `None` if the archive is disabled, see
+    /// [`SolverBuilder::full_archive()`].
+    pub fn full_front(&self) -> Option<&Pareto<F::Ys>>
+    where
+        F::Ys: Fitness<Best<F::Ys> = Pareto<F::Ys>>,
+    {
+        self.ctx.best.archive()
+    }
+
     /// Get the reference of the best parameters and the fitness value.
     pub fn as_best(&self) -> (&[f64], &F::Ys) {
         self.ctx.best.as_result()
@@ -61,6 +127,15 @@ impl<F: ObjFunc> Solver<F> {
         self.as_best_fit().eval()
     }
 
+    /// Recompute the side metrics of the best parameters, see
+    /// [`ObjMetrics`].
+    pub fn best_metrics(&self) -> F::Metrics
+    where
+        F: ObjMetrics,
+    {
+        self.ctx.func.metrics(self.as_best_xs())
+    }
+
     /// Get the final best element.
     pub fn into_result<P, Fit: Fitness>(self) -> P
     where
@@ -90,13 +165,373 @@ impl<F: ObjFunc> Solver<F> {
         (f, p, self.ctx.func)
     }
 
+    /// Get the final results of all elements on the Pareto front, in the
+    /// order of [`Pareto::as_pareto()`].
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestMO as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve();
+    /// // The decoded design of each trade-off
+    /// let products = s.front_results();
+    /// assert_eq!(products.len(), s.as_best_set().len());
+    /// ```
+    pub fn front_results<P, Fit: Fitness>(&self) -> Vec<P>
+    where
+        F: ObjFunc<Ys = WithProduct<Fit, P>>,
+        F::Ys: Fitness<Best<F::Ys> = Pareto<F::Ys>>,
+        P: MaybeParallel + Clone + 'static,
+    {
+        (self.ctx.best.as_pareto().iter())
+            .map(|ys| ys.as_result().clone())
+            .collect()
+    }
+
+    /// Schott's spacing metric of the Pareto front, see
+    /// [`pareto::spacing()`].
+    ///
+    /// The objectives are obtained from [`Fitness::objectives()`].
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestMO as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve();
+    /// println!("spacing: {}, spread: {}", s.front_spacing(), s.front_spread());
+    /// ```
+    pub fn front_spacing(&self) -> f64
+    where
+        F::Ys: Fitness<Best<F::Ys> = Pareto<F::Ys>>,
+    {
+        pareto::spacing(&self.front_objectives())
+    }
+
+    /// The maximum spread of the Pareto front, see [`pareto::spread()`].
+    ///
+    /// The objectives are obtained from [`Fitness::objectives()`].
+    pub fn front_spread(&self) -> f64
+    where
+        F::Ys: Fitness<Best<F::Ys> = Pareto<F::Ys>>,
+    {
+        pareto::spread(&self.front_objectives())
+    }
+
+    /// The crowding distance of each element of the Pareto front, in the order
+    /// of [`Pareto::as_pareto()`], see [`pareto::crowding_distance()`].
+    ///
+    /// The boundary elements of each objective have infinite distance, and
+    /// the objectives are obtained from [`Fitness::objectives()`].
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestMO as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve();
+    /// let crowding = s.pareto_crowding();
+    /// assert_eq!(crowding.len(), s.as_best_set().len());
+    /// ```
+    pub fn pareto_crowding(&self) -> Vec<f64>
+    where
+        F::Ys: Fitness<Best<F::Ys> = Pareto<F::Ys>>,
+    {
+        pareto::crowding_distance(&self.front_objectives())
+    }
+
+    /// The hypervolume of the Pareto front bounded by the `reference` point,
+    /// see [`Pareto::hypervolume()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of the objectives is not the same as the reference
+    /// point.
+    pub fn hypervolume(&self, reference: &[f64]) -> f64
+    where
+        F::Ys: Fitness<Best<F::Ys> = Pareto<F::Ys>>,
+    {
+        self.ctx.best.hypervolume(reference)
+    }
+
+    /// The non-domination front index of each individual of the final pool,
+    /// where 0 is the first (non-dominated) front, see
+    /// [`pareto::non_dominated_sort()`].
+    ///
+    /// The ranks are in the order of [`Solver::pool()`].
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestMO as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve();
+    /// let ranks = s.nondomination_ranks();
+    /// let first = ranks.iter().filter(|rank| **rank == 0).count();
+    /// println!("{first} individuals in the first front");
+    /// ```
+    pub fn nondomination_ranks(&self) -> Vec<usize> {
+        let mut ranks = alloc::vec![0; self.ctx.pop_num()];
+        for (rank, front) in pareto::non_dominated_sort(&self.ctx.pool_y)
+            .into_iter()
+            .enumerate()
+        {
+            front.into_iter().for_each(|i| ranks[i] = rank);
+        }
+        ranks
+    }
+
+    pub(crate) fn front_objectives(&self) -> Vec<Vec<f64>>
+    where
+        F::Ys: Fitness<Best<F::Ys> = Pareto<F::Ys>>,
+    {
+        (self.ctx.best.as_pareto().iter())
+            .map(Fitness::objectives)
+            .collect()
+    }
+
     /// Seed of the random number generator.
     pub fn seed(&self) -> Seed {
         self.seed
     }
 
+    /// Name of the algorithm, see [`Algorithm::name()`].
+    pub fn algorithm_name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Hyperparameters of the algorithm before solving, see
+    /// [`Algorithm::params()`].
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve();
+    /// // Record the metadata
+    /// println!(
+    ///     "{} {:?} {:?}",
+    ///     s.algorithm_name(),
+    ///     s.algorithm_params(),
+    ///     s.seed()
+    /// );
+    /// ```
+    pub fn algorithm_params(&self) -> &[(&'static str, f64)] {
+        &self.params
+    }
+
+    /// Number of the restarts, see [`SolverBuilder::restart()`].
+    pub fn restarts(&self) -> u64 {
+        self.restarts
+    }
+
+    /// Trajectory of the tracked individual, one entry per generation, see
+    /// [`SolverBuilder::track_individual()`].
+    pub fn tracked(&self) -> &[Vec<f64>] {
+        &self.tracked
+    }
+
+    /// The generation where the tracked individual went out of the
+    /// population, or `None` if the tracking was never stopped, see
+    /// [`SolverBuilder::track_individual()`].
+    pub fn tracking_stopped(&self) -> Option<u64> {
+        self.tracking_stopped
+    }
+
+    /// The best parameters and the evaluation value that were valid at the
+    /// generation `gen`, see [`SolverBuilder::record_best()`].
+    ///
+    /// The `gen` is the number of the evaluations if
+    /// [`SolverBuilder::record_by_evals()`] is enabled. Return `None` before
+    /// the first improvement, or if not recorded.
+    pub fn best_at(&self, gen: u64) -> Option<(&[f64], f64)>
+    where
+        <F::Ys as Fitness>::Eval: Into<f64>,
+    {
+        let key = self.history_key();
+        let i = (self.best_history).partition_point(|(keys, ..)| keys[key] <= gen);
+        let (_, xs, ys) = self.best_history.get(i.checked_sub(1)?)?;
+        Some((xs, ys.eval().into()))
+    }
+
+    /// The generations (or the numbers of the evaluations) and the best
+    /// evaluation values at each improvement, empty if not recorded, see
+    /// [`SolverBuilder::record_best()`] and
+    /// [`SolverBuilder::record_by_evals()`].
+    pub fn best_history(&self) -> Vec<(u64, f64)>
+    where
+        <F::Ys as Fitness>::Eval: Into<f64>,
+    {
+        let key = self.history_key();
+        (self.best_history.iter())
+            .map(|(keys, _, ys)| (keys[key], ys.eval().into()))
+            .collect()
+    }
+
+    // The index of the key of the best history
+    fn history_key(&self) -> usize {
+        usize::from(self.record_by_evals == Some(true))
+    }
+
+    /// Get the number of the objective function evaluations of the solving,
+    /// including the initial pool and the refinements, see
+    /// [`Ctx::eval_count()`].
+    pub fn eval_count(&self) -> u64 {
+        self.ctx.eval_count()
+    }
+
+    /// The generations and the diversity of the population, empty if not
+    /// recorded, see [`SolverBuilder::record_diversity()`].
+    pub fn diversity_history(&self) -> &[(u64, f64)] {
+        &self.diversity_history
+    }
+
+    /// Wall-clock time of each generation, empty if not recorded, see
+    /// [`SolverBuilder::record_gen_times()`].
+    #[cfg(feature = "std")]
+    pub fn gen_times(&self) -> &[std::time::Duration] {
+        &self.gen_times
+    }
+
+    /// The evaluated variables and their fitness values in the evaluation
+    /// order, empty if not recorded, see [`SolverBuilder::record_evals()`].
+    #[cfg(feature = "std")]
+    pub fn eval_log(&self) -> &[(Vec<f64>, F::Ys)] {
+        &self.eval_log
+    }
+
+    /// Sensitivity of the best solution for each variable.
+    ///
+    /// Each variable of the best solution is perturbed by `±step` (clamped in
+    /// the bounds), and the magnitude of the fitness change is the mean of the
+    /// absolute finite differences of the two sides. The values are comparable
+    /// between the variables to tell which one matters most at the optimum.
+    ///
+    /// This function evaluates the objective function `2 * dim` times.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve();
+    /// assert_eq!(s.sensitivity(1e-3).len(), 4);
+    /// ```
+    pub fn sensitivity(&self, step: f64) -> Vec<f64>
+    where
+        <F::Ys as Fitness>::Eval: Into<f64>,
+    {
+        let f = |xs: &[f64]| -> f64 { self.ctx.func.fitness(xs).eval().into() };
+        let (best, ys) = self.as_best();
+        let y0 = ys.eval().into();
+        let mut xs = best.to_vec();
+        (0..xs.len())
+            .map(|s| {
+                let [lb, ub] = self.ctx.func_bound[s];
+                let diff = [step, -step].map(|step| {
+                    xs[s] = (best[s] + step).clamp(lb, ub);
+                    (f(&xs) - y0).abs()
+                });
+                xs[s] = best[s];
+                0.5 * (diff[0] + diff[1])
+            })
+            .collect()
+    }
+
+    /// Refine the best solution with L-BFGS, a quasi-Newton local search.
+    ///
+    /// The gradient is provided by [`ObjFunc::gradient()`] or
+    /// [`ObjFunc::fitness_and_grad()`], or computed by the finite differences. The variables are projected into the bounds.
+    /// It converges much faster than the metaheuristics on the smooth
+    /// problems, so it is suitable to polish the result of a global search.
+    /// The best solution is replaced if the refinement is better.
+    ///
+    /// The search follows the direction of [`SolverBuilder::maximize()`], a
+    /// custom comparator is only used to decide the replacement.
+    ///
+    /// Return the number of the evaluations.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let mut s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve();
+    /// let evals = s.refine_lbfgs(100);
+    /// ```
+    pub fn refine_lbfgs(&mut self, max_iter: usize) -> usize
+    where
+        <F::Ys as Fitness>::Eval: Into<f64>,
+    {
+        let (xs, ys, evals) = crate::local::lbfgs(
+            &self.ctx.func,
+            &self.ctx.func_bound,
+            self.as_best_xs(),
+            max_iter,
+            self.ctx.compare.is_maximize(),
+        );
+        (self.ctx.evals).fetch_add(evals as u64, core::sync::atomic::Ordering::Relaxed);
+        if self.ctx.is_better(&ys, self.as_best_fit()) {
+            self.ctx.best.update(&xs, &ys);
+        }
+        evals
+    }
+
     /// Get the pool from the last status.
+    ///
+    /// The pool is in the internal order of the algorithm, use
+    /// [`Solver::sorted_pool()`] to get the best-to-worst order.
     pub fn pool(&self) -> &[Vec<f64>] {
         &self.ctx.pool
     }
+
+    /// Get the variables and the fitness values of the pool from the last
+    /// status, sorted from the best to the worst.
+    ///
+    /// The first element is the best of the last pool, which may not be the
+    /// best ever found if the algorithm does not keep it in the pool.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve();
+    /// let (xs, _) = s.sorted_pool()[0];
+    /// assert_eq!(xs, s.as_best_xs());
+    /// ```
+    pub fn sorted_pool(&self) -> Vec<(&[f64], &F::Ys)> {
+        let mut pool = core::iter::zip(&self.ctx.pool, &self.ctx.pool_y)
+            .map(|(xs, ys)| (xs.as_slice(), ys))
+            .collect::<Vec<_>>();
+        pool.sort_by(|(_, a), (_, b)| self.ctx.cmp_fitness(a, b));
+        pool
+    }
+}
+
+/// Evaluate the recorded variables of [`Solver::eval_log()`] in order with
+/// an objective function, such as a new implementation.
+///
+/// Compare the results with the recorded fitness values to check whether the
+/// objective function is changed.
+pub fn replay<F: ObjFunc, Y>(func: &F, log: &[(Vec<f64>, Y)]) -> Vec<F::Ys> {
+    log.iter().map(|(xs, _)| func.fitness(xs)).collect()
 }
@@ -1,11 +1,20 @@
-use crate::prelude::*;
-use alloc::{boxed::Box, vec::Vec};
+use crate::{ctx::fitness_all, math, prelude::*};
+use alloc::{
+    boxed::Box,
+    collections::VecDeque,
+    format,
+    string::{String, ToString as _},
+    sync::Arc,
+    vec::Vec,
+};
+use core::iter::zip;
 
 /// A [`SolverBuilder`] that use a boxed algorithm.
 ///
 /// Generated by [`Solver::build_boxed()`] method.
 pub type SolverBox<'a, F> = SolverBuilder<'a, maybe_send_box!(Algorithm<F>), F>;
 
+pub(crate) type StopFunc<'a, F> = maybe_send_box!(FnMut(&Ctx<F>) -> bool + 'a);
 type PoolFunc<'a> =
     maybe_send_box!(Fn(usize, core::ops::RangeInclusive<f64>, &mut Rng) -> f64 + 'a);
 
@@ -20,12 +29,92 @@ pub enum Pool<'a, F: ObjFunc> {
         /// Fitness values
         pool_y: Vec<F::Ys>,
     },
+    /// A ready-made pool without the fitness values, which are evaluated in
+    /// the initialization.
+    ///
+    /// The population size is decided by the pool. The pool should not be
+    /// empty ([`BuildError::EmptyPool`]), and each individual should have the
+    /// same dimension as the objective function
+    /// ([`BuildError::PoolDimMismatched`]).
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Pool, Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// // For example, loaded from a file
+    /// let pool = vec![vec![1., 2., 3., 4.], vec![-4., -3., -2., -1.]];
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .init_pool(Pool::ReadyParams(pool))
+    ///     .solve();
+    /// ```
+    ReadyParams(Vec<Vec<f64>>),
     /// Generate the pool uniformly with a filter function to check the
     /// validity.
     ///
     /// This filter function returns true if the design variables are valid.
-    #[allow(clippy::type_complexity)]
-    UniformBy(maybe_send_box!(Fn(&[f64]) -> bool + 'a)),
+    /// If the pool is not filled after `max_tries` total draws,
+    /// [`BuildError::InfeasibleInit`] is returned. Use `u64::MAX` to draw
+    /// without a limit.
+    ///
+    /// The candidates are drawn and filtered one by one in a fixed serial
+    /// order, even if the `rayon` feature is enabled, so the same seed and
+    /// filter always give the same pool. The filter should be deterministic.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Pool, Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let pool = Pool::UniformBy {
+    ///     filter: Box::new(|xs| xs[0] > 0.),
+    ///     max_tries: 10000,
+    /// };
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .init_pool(pool)
+    ///     .solve();
+    /// ```
+    UniformBy {
+        /// Filter function
+        #[allow(clippy::type_complexity)]
+        filter: maybe_send_box!(Fn(&[f64]) -> bool + 'a),
+        /// Maximum number of the draws
+        max_tries: u64,
+    },
+    /// Generate the pool with a filter function like [`Pool::UniformBy`], but
+    /// seek around the feasible points for the tiny feasible regions.
+    ///
+    /// The candidates are drawn uniformly until a feasible point is found and
+    /// the draws exceed the population size, then they are drawn from a
+    /// Gaussian distribution around a random feasible point found so far. The
+    /// standard deviation starts from 10% of the bound widths, and is halved
+    /// after 10 consecutive rejections. If the pool is not filled after
+    /// `max_tries` total draws, [`BuildError::InfeasibleInit`] is returned.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Pool, Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// // A thin shell
+    /// let pool = Pool::FeasibleSeek {
+    ///     filter: Box::new(|xs| (xs.iter().map(|x| x * x).sum::<f64>() - 400.).abs() < 1.),
+    ///     max_tries: 100000,
+    /// };
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .init_pool(pool)
+    ///     .solve();
+    /// ```
+    FeasibleSeek {
+        /// Filter function
+        #[allow(clippy::type_complexity)]
+        filter: maybe_send_box!(Fn(&[f64]) -> bool + 'a),
+        /// Maximum number of the draws
+        max_tries: u64,
+    },
     /// Generate the pool with a specific function.
     ///
     /// The function signature is `fn(s, min..max, &rng) -> value`
@@ -36,148 +125,1547 @@ pub enum Pool<'a, F: ObjFunc> {
     /// Two examples are [`uniform_pool()`] and [`gaussian_pool()`].
     ///
     /// ```
-    /// use metaheuristics_nature::{gaussian_pool, Pool, Rga, Solver};
-    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    /// use metaheuristics_nature::{gaussian_pool, Pool, Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let pool = Pool::Func(Box::new(gaussian_pool(&[0.; 4], &[1.; 4])));
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .init_pool(pool)
+    ///     .solve();
+    /// ```
+    Func(PoolFunc<'a>),
+    /// Generate the pool with a distribution for each variable.
+    ///
+    /// The length should be the same as the dimension, otherwise
+    /// [`BuildError::PoolDimMismatched`] is returned.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{DimDist, Pool, Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let pool = Pool::PerDim(vec![
+    ///     DimDist::Uniform,
+    ///     DimDist::Gaussian { mean: 0., std: 1. },
+    ///     DimDist::Uniform,
+    ///     DimDist::Uniform,
+    /// ]);
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .init_pool(pool)
+    ///     .solve();
+    /// ```
+    PerDim(Vec<DimDist>),
+    /// Generate the pool as the random permutations, which are encoded by the
+    /// distinct random keys, see [`permutation_from_keys()`].
+    ///
+    /// The keys of each individual are drawn in the evenly divided slots of
+    /// the bounds, so they are never tied. The bounds of the variables should
+    /// be the same, otherwise [`BuildError::UnequalBounds`] is returned.
+    ///
+    /// See [`PermGa`] for an example.
+    Permutation,
+}
+
+/// The distribution of a variable, see [`Pool::PerDim`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DimDist {
+    /// Uniform distribution in the bounds.
+    Uniform,
+    /// Gaussian distribution.
+    Gaussian {
+        /// Mean value
+        mean: f64,
+        /// Standard deviation
+        std: f64,
+    },
+    /// Uniform distribution in the logarithm of the bounds, such as the
+    /// learning rate.
+    ///
+    /// The lower bound should be positive, otherwise
+    /// [`BuildError::InvalidDimDist`] is returned.
+    LogUniform,
+}
+
+impl DimDist {
+    fn sample(&self, range: core::ops::RangeInclusive<f64>, rng: &mut Rng) -> f64 {
+        match *self {
+            Self::Uniform => rng.range(range),
+            Self::Gaussian { mean, std } => rng.normal(mean, std),
+            Self::LogUniform => {
+                let (min, max) = range.into_inner();
+                math::exp(rng.range(math::ln(min)..=math::ln(max)))
+            }
+        }
+    }
+}
+
+/// Error of the invalid configurations.
+///
+/// Returned by [`SolverBuilder::try_solve()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// The dimension size is zero.
+    ZeroDim,
+    /// The lower bound is greater than the upper bound.
+    InvalidBound,
+    /// The initial pool is empty.
+    EmptyPool,
+    /// The pool size is not consistent with its fitness values.
+    PoolSizeMismatched,
+    /// The pool dimension is not consistent with the objective function.
+    PoolDimMismatched,
+    /// The variable groups are empty or out of the dimension.
+    InvalidGroups,
+    /// The filter of [`Pool::UniformBy`] or [`Pool::FeasibleSeek`] rejects
+    /// too many draws.
+    InfeasibleInit,
+    /// The version of the random draw sequence is not supported.
+    UnsupportedRngVersion,
+    /// The bounds are invalid for the distribution of [`Pool::PerDim`].
+    InvalidDimDist,
+    /// The bounds of the variables are not the same for
+    /// [`Pool::Permutation`].
+    UnequalBounds,
+    /// The method is not stateless for [`SolverBuilder::resume_from()`], see
+    /// [`Algorithm::is_stateless()`].
+    StatefulResume,
+    /// The objective function does not support [`SolverBuilder::maximize()`],
+    /// see [`ObjFunc::is_minimize_only()`].
+    MinimizeOnly,
+}
+
+impl core::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let msg = match self {
+            Self::ZeroDim => "Dimension should be greater than 0",
+            Self::InvalidBound => "Lower bound should be less than upper bound",
+            Self::EmptyPool => "Population should be greater than 0",
+            Self::PoolSizeMismatched => "Pool size mismatched",
+            Self::PoolDimMismatched => "Pool dimension mismatched",
+            Self::InvalidGroups => "Variable groups should be non-empty and in the dimension",
+            Self::InfeasibleInit => "Pool filter should accept enough individuals",
+            Self::UnsupportedRngVersion => "RNG version should be supported",
+            Self::InvalidDimDist => "Log-uniform variable should have positive bounds",
+            Self::UnequalBounds => "Permutation variables should have the same bounds",
+            Self::StatefulResume => "Method should be stateless to resume",
+            Self::MinimizeOnly => "Objective function should support maximization",
+        };
+        f.write_str(msg)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BuildError {}
+
+/// Collect configuration and build the solver.
+///
+/// This type is created by [`Solver::build()`] method.
+///
+/// + First, setting a fixed seed with [`SolverBuilder::seed()`] method to get a
+///   determined result is highly recommended.
+/// + Next is [`SolverBuilder::task()`] method with a termination condition.
+/// + Finally, call [`SolverBuilder::solve()`] method to start the algorithm.
+#[allow(clippy::type_complexity)]
+#[must_use = "solver builder do nothing unless call the \"solve\" method"]
+pub struct SolverBuilder<'a, A: Algorithm<F>, F: ObjFunc> {
+    func: F,
+    algorithm: A,
+    pop_num: usize,
+    // The default and the minimum population number of the algorithm
+    pop_num_hint: [usize; 2],
+    max_gen: Option<u64>,
+    max_gen_hint: fn(usize, usize) -> u64,
+    pareto_limit: usize,
+    full_archive: bool,
+    truncation: Option<Arc<dyn Truncation>>,
+    maximize: bool,
+    compare: Option<CompareFn<F::Ys>>,
+    seed: SeedOpt,
+    stream_seeds: StreamSeeds,
+    pool: Pool<'a, F>,
+    incumbent: Option<Vec<f64>>,
+    resume: Option<SolverState<F::Ys>>,
+    groups: Vec<Vec<usize>>,
+    restart: Option<u64>,
+    max_restarts: u64,
+    shrink: Option<f64>,
+    dedup: Option<f64>,
+    anti_saturation: Option<f64>,
+    replacement: Replacement,
+    sharing: Option<(f64, f64)>,
+    eval_chunk: usize,
+    eval_parallel: bool,
+    reproducible: bool,
+    eval_order: EvalOrder,
+    clamp: bool,
+    track: Option<usize>,
+    record_best: bool,
+    record_by_evals: bool,
+    diversity_stride: Option<u64>,
+    #[cfg(feature = "std")]
+    gen_times: bool,
+    #[cfg(feature = "std")]
+    eval_log: bool,
+    #[cfg(feature = "std")]
+    commands: Option<std::sync::mpsc::Receiver<SolverCmd>>,
+    task: maybe_send_box!(FnMut(&Ctx<F>) -> bool + 'a),
+    stop: Vec<StopFunc<'a, F>>,
+    // Best-effort labels of the termination conditions
+    task_label: String,
+    stop_labels: Vec<String>,
+    callback: maybe_send_box!(FnMut(&Ctx<F>) + 'a),
+    archive_update: Option<maybe_send_box!(FnMut(&Ctx<F>) + 'a)>,
+    immigrant: Option<maybe_send_box!(FnMut() -> Option<Vec<f64>> + 'a)>,
+    clock: Option<maybe_send_box!(Clock + 'a)>,
+}
+
+impl<'a, A: Algorithm<F>, F: ObjFunc> SolverBuilder<'a, A, F> {
+    impl_builders! {
+        /// Population number.
+        ///
+        /// # Default
+        ///
+        /// If not changed by the algorithm setting, the default number is 200.
+        fn pop_num(usize)
+    }
+
+    /// Population number as a factor of the default population number of the
+    /// algorithm, such as `2.` for twice the default.
+    ///
+    /// The number is rounded to the nearest integer and is at least the
+    /// minimum population number of the algorithm ([`AlgCfg::min_pop_num()`]),
+    /// so it scales with the algorithm default.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .pop_num_factor(0.1)
+    ///     .solve();
+    /// assert_eq!(s.pool().len(), 40);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is not positive or not finite.
+    pub fn pop_num_factor(self, factor: f64) -> Self {
+        assert!(
+            factor > 0. && factor.is_finite(),
+            "Population factor should be positive"
+        );
+        let [default, min] = self.pop_num_hint;
+        let pop_num = ((default as f64 * factor).round() as usize).max(min);
+        Self { pop_num, ..self }
+    }
+
+    /// Pareto front limit.
+    ///
+    /// It is not working for single-objective optimization.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestMO as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .pareto_limit(10)
+    ///     .solve();
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, there is no limit. The limit is set to `usize::MAX`.
+    pub fn pareto_limit(self, pareto_limit: usize) -> Self
+    where
+        F::Ys: Fitness<Best<F::Ys> = Pareto<F::Ys>>,
+    {
+        Self { pareto_limit, ..self }
+    }
+
+    /// Keep all non-dominated solutions ever seen in an unbounded archive,
+    /// which can be obtained from [`Solver::full_front()`].
+    ///
+    /// The archive is not truncated by [`SolverBuilder::pareto_limit()`], so
+    /// it may take a lot of memory for a long run. It is not working for
+    /// single-objective optimization.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestMO as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .pareto_limit(10)
+    ///     .full_archive(true)
+    ///     .solve();
+    /// let front = s.full_front().unwrap();
+    /// assert!(front.len() >= s.as_best_set().len());
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the archive is disabled.
+    pub fn full_archive(self, full_archive: bool) -> Self
+    where
+        F::Ys: Fitness<Best<F::Ys> = Pareto<F::Ys>>,
+    {
+        Self { full_archive, ..self }
+    }
+
+    /// Set the strategy to prune the Pareto front to the limit of
+    /// [`SolverBuilder::pareto_limit()`], see [`Truncation`].
+    ///
+    /// The strategies need the objective values from
+    /// [`Fitness::objectives()`], otherwise the default policy is used. It is
+    /// not working for single-objective optimization.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{pareto::HypervolumeContribution, Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestMO as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .pareto_limit(10)
+    ///     .truncation(HypervolumeContribution)
+    ///     .solve();
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the elements with the worst [`Fitness::eval()`] are
+    /// removed.
+    pub fn truncation<T>(self, truncation: T) -> Self
+    where
+        T: Truncation + 'static,
+        F::Ys: Fitness<Best<F::Ys> = Pareto<F::Ys>>,
+    {
+        Self { truncation: Some(Arc::new(truncation)), ..self }
+    }
+
+    /// Maximize the fitness value instead of minimizing it.
+    ///
+    /// All the comparisons of the methods and the best element are reversed,
+    /// and the float utilities such as [`SolverBuilder::stop_on_slope()`] are
+    /// oriented with the direction. The reported fitness value keeps the true
+    /// sign.
+    ///
+    /// It is not working for multi-objective optimization, please use the
+    /// [`Maximize`] wrapper on each objective instead.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Fx, Rga, Solver};
+    ///
+    /// let f = Fx::new(&[[-5., 5.]], |&[x]: &[f64; 1]| 1. - x * x);
+    /// let s = Solver::build(Rga::default(), f)
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .maximize()
+    ///     .solve();
+    /// assert!((1. - s.get_best_eval()).abs() < 1e-4);
+    /// ```
+    ///
+    /// The objective functions that only support minimization
+    /// ([`ObjFunc::is_minimize_only()`]), such as [`FxResult`], return
+    /// [`BuildError::MinimizeOnly`].
+    ///
+    /// # Default
+    ///
+    /// By default, the fitness value is minimized.
+    pub fn maximize(self) -> Self
+    where
+        F::Ys: Fitness<Best<F::Ys> = SingleBest<F::Ys>>,
+    {
+        Self { maximize: true, ..self }
+    }
+
+    /// Set a comparator of the fitness values, which returns
+    /// [`Ordering::Less`](core::cmp::Ordering::Less) if the first value is
+    /// better.
+    ///
+    /// The comparator overrides the default ordering in the selection of the
+    /// methods and the best finding, see [`Ctx::is_better()`] and
+    /// [`Ctx::cmp_fitness()`]. It also overrides the ordering of
+    /// [`SolverBuilder::maximize()`], but the float utilities still follow its
+    /// direction. The [`Pareto`] front keeps [`Fitness::is_dominated()`] as the
+    /// dominance.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Fx, Rga, Solver};
+    ///
+    /// // Prefer the fitness value closest to 1
+    /// let f = Fx::new(&[[-5., 5.]], |&[x]: &[f64; 1]| x * x);
+    /// let s = Solver::build(Rga::default(), f)
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .compare(|a, b| (a - 1.).abs().total_cmp(&(b - 1.).abs()))
+    ///     .solve();
+    /// assert!((s.get_best_eval() - 1.).abs() < 1e-4);
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the fitness values are compared by
+    /// [`Fitness::is_dominated()`] and sorted by [`Fitness::eval()`], which is
+    /// [`PartialOrd`] for the single objective types.
+    pub fn compare<C>(self, compare: C) -> Self
+    where
+        C: Fn(&F::Ys, &F::Ys) -> core::cmp::Ordering + Send + Sync + 'static,
+    {
+        Self { compare: Some(Arc::new(compare)), ..self }
+    }
+
+    /// Set a fixed random seed to get a determined result.
+    ///
+    /// The result is only reproducible in the same [`RNG_VERSION`], use
+    /// [`SeedOpt::Versioned`] to pin it.
+    ///
+    /// # Default
+    ///
+    /// By default, the random seed is auto-decided so you cannot reproduce the
+    /// result. Please print the seed via [`Solver::seed()`] method to get the
+    /// seed that used in the algorithm.
+    pub fn seed(self, seed: impl Into<SeedOpt>) -> Self {
+        Self { seed: seed.into(), ..self }
+    }
+
+    /// Separate the random streams of the initialization, the selection, and
+    /// the mutation with their own seeds, see [`StreamSeeds`].
+    ///
+    /// Varying one seed with the others fixed isolates the stochastic
+    /// component that drives the variance of the results. The mutation
+    /// stream is used by the methods with the mutation through
+    /// [`Rng::mutation()`], which is only [`Rga`] currently. If the selection
+    /// stream is separated, [`Solver::seed()`] is its seed.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{random::StreamSeeds, Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let seeds = StreamSeeds { select: Some(1), mutate: Some(2), ..Default::default() };
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .stream_seeds(seeds)
+    ///     .solve();
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, all draws are made from the generator of the seed.
+    pub fn stream_seeds(self, stream_seeds: StreamSeeds) -> Self {
+        Self { stream_seeds, ..self }
+    }
+
+    /// Initialize the pool with the pool option.
+    ///
+    /// # Default
+    ///
+    /// By default, the pool is generated by the uniform distribution
+    /// [`uniform_pool()`].
+    pub fn init_pool(self, pool: Pool<'a, F>) -> Self {
+        Self { pool, ..self }
+    }
+
+    /// A known solution (incumbent) that is evaluated once in the
+    /// initialization, and is set as the best if it is better than the
+    /// initial pool.
+    ///
+    /// The incumbent is not a member of the pool, so the population is
+    /// unchanged. Since the best is never replaced by a worse one, the result
+    /// is at least as good as the incumbent. The dimension should be the same
+    /// as the objective function, otherwise
+    /// [`BuildError::PoolDimMismatched`] is returned. The variables are
+    /// clamped to the bounds if [`SolverBuilder::clamp_to_bounds()`] is
+    /// enabled.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .incumbent(vec![0.; 4])
+    ///     .solve();
+    /// assert_eq!(s.as_best_xs(), [0.; 4]);
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, there is no incumbent.
+    pub fn incumbent(self, xs: Vec<f64>) -> Self {
+        Self { incumbent: Some(xs), ..self }
+    }
+
+    /// Resume from a snapshot saved by [`Solver::save_state()`], see
+    /// [`SolverState`].
+    ///
+    /// The saved pool is used without the evaluations instead of
+    /// [`SolverBuilder::init_pool()`], and the saved random number generator
+    /// replaces [`SolverBuilder::seed()`] and
+    /// [`SolverBuilder::stream_seeds()`]. The generation counter and the
+    /// number of the evaluations continue from the saved values, so the task
+    /// should be set with the total generations.
+    ///
+    /// The internal states of the method are not saved, so the method should
+    /// be stateless ([`Algorithm::is_stateless()`]), such as [`De`] and
+    /// [`Rga`], otherwise [`BuildError::StatefulResume`] is returned.
+    ///
+    /// # Default
+    ///
+    /// By default, the solving starts from the initial pool.
+    pub fn resume_from(self, state: SolverState<F::Ys>) -> Self {
+        Self { resume: Some(state), ..self }
+    }
+
+    /// Cooperative coevolution, optimize the variable groups in turn.
+    ///
+    /// Each generation optimizes one group of the variables in order, where
+    /// the other variables are frozen at the current best (the context
+    /// vector). Each individual keeps its own values of all groups as the
+    /// subpopulations. At the beginning of the generation, the pool is
+    /// combined with the context vector and re-evaluated, so each generation
+    /// costs an additional population number of evaluations.
+    ///
+    /// This decomposition is efficient for the large-scale problems that are
+    /// separable between groups, with the methods that modify each variable
+    /// independently, such as [`De`] with the *c2* strategies. Use
+    /// [`differential_grouping()`] to detect the groups automatically.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .cooperative(vec![vec![0, 1], vec![2, 3]])
+    ///     .solve();
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, all variables are optimized together, same as passing
+    /// an empty list.
+    pub fn cooperative(self, groups: Vec<Vec<usize>>) -> Self {
+        Self { groups, ..self }
+    }
+
+    /// Restart the pool if the best fitness is not improved in `patience`
+    /// generations.
+    ///
+    /// The pool is regenerated uniformly and re-evaluated, and then the
+    /// algorithm is initialized again. The best set is kept. The number of
+    /// restarts can be obtained from [`Solver::restarts()`].
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 50)
+    ///     .restart(10)
+    ///     .max_restarts(2)
+    ///     .solve();
+    /// assert!(s.restarts() <= 2);
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the restart is disabled.
+    pub fn restart(self, patience: u64) -> Self {
+        Self { restart: Some(patience), ..self }
+    }
+
+    impl_builders! {
+        /// Maximum number of the restarts. The restart is disabled for the
+        /// remainder of the run once the cap is hit. See
+        /// [`SolverBuilder::restart()`].
+        ///
+        /// # Default
+        ///
+        /// By default, there is no limit. The limit is set to `u64::MAX`.
+        fn max_restarts(u64)
+    }
+
+    /// Shrink the search bounds toward the current best by `rate` in each
+    /// generation, which focuses the search for the refinement.
+    ///
+    /// The range of each variable is contracted as `[lb + rate * (best - lb),
+    /// ub - rate * (ub - best)]`, but not shrunk below a tiny fraction of the
+    /// original range. The effective bounds can be obtained from the
+    /// [`Bounded`] methods of the [`Ctx`]. This option has no effect on the
+    /// optimized group of [`SolverBuilder::cooperative()`], since their bounds
+    /// are restored in each generation.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .shrink_bounds(0.05)
+    ///     .solve();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is not in the range `[0, 1)`.
+    ///
+    /// # Default
+    ///
+    /// By default, the bounds are not shrunk.
+    pub fn shrink_bounds(self, rate: f64) -> Self {
+        assert!((0. ..1.).contains(&rate), "Shrink rate should be in [0, 1)");
+        Self { shrink: Some(rate), ..self }
+    }
+
+    /// Regenerate the duplicate individuals after each generation, which
+    /// maintains the diversity of a collapsed population.
+    ///
+    /// Two individuals are duplicates if all their variables are within
+    /// `tolerance`. The better one is kept, and the others are regenerated
+    /// uniformly in the effective bounds and re-evaluated. The best set is
+    /// kept.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Pso, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Pso::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .dedup(1e-6)
+    ///     .solve();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tolerance` is negative.
+    ///
+    /// # Default
+    ///
+    /// By default, the duplicates are kept.
+    pub fn dedup(self, tolerance: f64) -> Self {
+        assert!(tolerance >= 0., "Tolerance should be non-negative");
+        Self { dedup: Some(tolerance), ..self }
+    }
+
+    /// Move the variables saturated at the bounds back into the interior
+    /// after each generation, which restores the diversity lost to the
+    /// clamping of [`SolverBuilder::clamp_to_bounds()`].
+    ///
+    /// Each variable that is at its effective bound is moved inward by a
+    /// uniform random amount up to half of the width with the probability
+    /// `prob`. The moved individuals are re-evaluated, and the best set is
+    /// kept. The methods that re-sample the out-of-bounds variables, such as
+    /// [`De`] and [`Rga`], are rarely saturated.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Pso, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Pso::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .anti_saturation(0.1)
+    ///     .solve();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prob` is not in the range `[0, 1]`.
+    ///
+    /// # Default
+    ///
+    /// By default, the saturated variables are kept.
+    pub fn anti_saturation(self, prob: f64) -> Self {
+        assert!(
+            (0. ..=1.).contains(&prob),
+            "Anti-saturation probability should be in [0, 1]"
+        );
+        Self { anti_saturation: Some(prob), ..self }
+    }
+
+    impl_builders! {
+        /// Replacement policy of the offspring, see [`Replacement`].
+        ///
+        /// It works for the methods replacing the individuals one by one,
+        /// including [`De`] and [`Tlbo`], through [`Ctx::replace()`].
+        ///
+        /// ```
+        /// use metaheuristics_nature::{De, Replacement, Solver};
+        /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+        ///
+        /// let s = Solver::build(De::default(), MyFunc::new())
+        ///     .seed(0)
+        ///     .task(|ctx| ctx.gen == 20)
+        ///     .replacement(Replacement::Worst)
+        ///     .solve();
+        /// ```
+        ///
+        /// # Default
+        ///
+        /// By default, the offspring replaces its parent.
+        fn replacement(Replacement)
+    }
+
+    /// Select the individuals by the shared fitness to maintain the niches
+    /// around multiple optima, see [`Ctx::shared_fitness()`].
+    ///
+    /// The fitness of the crowded individuals is penalized by the number of
+    /// the neighbors within the niche `radius`, which is the distance
+    /// normalized by the bounds, so the subpopulations around the distinct
+    /// peaks are kept. The `alpha` controls the shape of the sharing
+    /// function, 1 is the triangular shape.
+    ///
+    /// It only affects the selections of [`Rga`], [`PermGa`], and [`Abc`].
+    /// [`NsgaII`] keeps its crowding distance for the diversity, and the
+    /// other methods have no fitness-based selection, so they ignore it.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .fitness_sharing(0.1, 1.)
+    ///     .solve();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radius` or `alpha` is not positive.
+    ///
+    /// # Default
+    ///
+    /// By default, the raw fitness is used.
+    pub fn fitness_sharing(self, radius: f64, alpha: f64) -> Self {
+        assert!(radius > 0., "Niche radius should be positive");
+        assert!(alpha > 0., "Sharing shape should be positive");
+        Self { sharing: Some((radius, alpha)), ..self }
+    }
+
+    /// Repair the moves of the methods into the bounds.
+    ///
+    /// If disabled, the bounds are only used to initialize the pool, and the
+    /// search can explore beyond them. It is for the objective functions that
+    /// are well-behaved outside the bounds. See also
+    /// [`Ctx::clamp_to_bounds()`].
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .clamp_to_bounds(false)
+    ///     .solve();
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the moves are repaired.
+    pub fn clamp_to_bounds(self, clamp: bool) -> Self {
+        Self { clamp, ..self }
+    }
+
+    /// The minimum number of the objective function evaluations in a parallel
+    /// job, can be obtained from [`Ctx::eval_chunk()`]. See also
+    /// [`SolverBuilder::eval_parallel()`].
+    ///
+    /// A cheap objective function can be batched into larger chunks to reduce
+    /// the scheduling overhead of rayon. The chunk size does not change the
+    /// results. This option has no effect without the `rayon` feature.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .eval_chunk(16)
+    ///     .solve();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk` is zero.
+    ///
+    /// # Default
+    ///
+    /// By default, each evaluation is a parallel job.
+    pub fn eval_chunk(self, chunk: usize) -> Self {
+        assert!(chunk > 0, "Chunk size should be positive");
+        Self { eval_chunk: chunk, ..self }
+    }
+
+    /// Evaluate the objective function in parallel within the solver.
+    ///
+    /// Disable it if the solvers are already run in parallel, such as
+    /// multiple runs or independent islands in the rayon jobs, which prevents
+    /// the nested parallelism from oversubscribing the threads. The
+    /// evaluations are run on the thread of the solver, as one chunk of
+    /// [`SolverBuilder::eval_chunk()`]. This option has no effect without the
+    /// `rayon` feature.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .eval_parallel(false)
+    ///     .solve();
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the evaluations are parallel.
+    pub fn eval_parallel(self, eval_parallel: bool) -> Self {
+        Self { eval_parallel, ..self }
+    }
+
+    /// Guarantee the identical results regardless of the number of threads.
+    ///
+    /// The provided methods fork a sub-RNG for each individual
+    /// ([`Rng::stream()`]) before the parallel evaluations, and the best set
+    /// is updated serially, so the results of a pure objective function only
+    /// depend on the seed. This option extends the guarantee to the
+    /// evaluation order, the evaluations are run in the serial order as
+    /// [`SolverBuilder::eval_parallel()`] is disabled, so the stateful
+    /// objective functions (such as the caches and the surrogates), the
+    /// evaluation log ([`SolverBuilder::record_evals()`]), and the
+    /// [`EvalOrder`] are reproduced as well.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .reproducible(true)
+    ///     .solve();
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the evaluation order is not guaranteed with the `rayon`
+    /// feature.
+    pub fn reproducible(self, reproducible: bool) -> Self {
+        Self { reproducible, ..self }
+    }
+
+    /// Order of the batch evaluation, see [`EvalOrder`].
+    ///
+    /// It is applied when the whole pool is evaluated at once, including the
+    /// initialization, the restarts, and the regenerated duplicates. The
+    /// evaluation order is only followed in the serial evaluation, since the
+    /// `rayon` feature evaluates the chunks in parallel.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, EvalOrder, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .eval_order(EvalOrder::NearestNeighbor)
+    ///     .solve();
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the pool is evaluated in its order.
+    pub fn eval_order(self, eval_order: EvalOrder) -> Self {
+        Self { eval_order, ..self }
+    }
+
+    /// Record the variables of the individual at `index` of the pool after
+    /// each generation, which can be obtained from [`Solver::tracked()`].
+    ///
+    /// If the index is out of the population, the tracking is stopped, and the
+    /// generation is recorded in [`Solver::tracking_stopped()`]. The trajectory
+    /// before that generation is kept.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Pso, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Pso::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .track_individual(0)
+    ///     .solve();
+    /// assert_eq!(s.tracked().len(), 20);
+    /// assert_eq!(s.tracking_stopped(), None);
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, no individual is tracked.
+    pub fn track_individual(self, index: usize) -> Self {
+        Self { track: Some(index), ..self }
+    }
+
+    /// Record the best parameters at each improvement, which can be queried
+    /// by the generation from [`Solver::best_at()`].
+    ///
+    /// The best element is checked after each generation, and only the
+    /// improvements are stored to bound the memory.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 200)
+    ///     .record_best(true)
+    ///     .solve();
+    /// let (xs, eval) = s.best_at(100).unwrap();
+    /// println!("{xs:?}: {eval}");
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the best parameters are not recorded.
+    pub fn record_best(self, record: bool) -> Self {
+        Self { record_best: record, ..self }
+    }
+
+    /// Record the best parameters at each improvement by the number of the
+    /// evaluations ([`Ctx::eval_count()`]) instead of the generation, which
+    /// enables [`SolverBuilder::record_best()`].
+    ///
+    /// The history from [`Solver::best_history()`] can be plotted against the
+    /// evaluations, which is fairer to compare the methods with different
+    /// evaluation costs per generation. The [`Solver::best_at()`] is queried
+    /// by the number of the evaluations as well.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .record_by_evals(true)
+    ///     .solve();
+    /// for (evals, best) in s.best_history() {
+    ///     println!("{evals}: {best}");
+    /// }
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the history is recorded by the generation.
+    pub fn record_by_evals(self, by_evals: bool) -> Self {
+        Self { record_by_evals: by_evals, ..self }
+    }
+
+    /// Record the diversity of the population ([`Ctx::diversity()`]) every
+    /// `stride` generations, which can be obtained from
+    /// [`Solver::diversity_history()`].
+    ///
+    /// The initial population is recorded as the generation 0. A fast drop of
+    /// the diversity before the best value is settled indicates the premature
+    /// convergence.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 100)
+    ///     .record_diversity(10)
+    ///     .solve();
+    /// for (gen, diversity) in s.diversity_history() {
+    ///     println!("{gen}: {diversity}");
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero.
+    ///
+    /// # Default
+    ///
+    /// By default, the diversity is not recorded.
+    pub fn record_diversity(self, stride: u64) -> Self {
+        assert!(stride > 0, "Stride should be positive");
+        Self { diversity_stride: Some(stride), ..self }
+    }
+
+    /// Record the wall-clock time of each generation, which can be obtained
+    /// from [`Solver::gen_times()`].
+    ///
+    /// The time covers the generation of the algorithm and the post-processes
+    /// of the solver, such as the shrinking and the restarting, but not the
+    /// callbacks and the termination conditions.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .record_gen_times(true)
+    ///     .solve();
+    /// let slowest = s.gen_times().iter().max().unwrap();
+    /// println!("{slowest:?}");
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the time is not recorded.
+    #[cfg(feature = "std")]
+    pub fn record_gen_times(self, record: bool) -> Self {
+        Self { gen_times: record, ..self }
+    }
+
+    /// Record the exact sequence of the evaluated variables and their fitness
+    /// values, which can be obtained from [`Solver::eval_log()`].
+    ///
+    /// The log can be fed to [`replay()`] to compare a new implementation of
+    /// the objective function with the old one, which isolates the behavior
+    /// changes of the objective function from the algorithm. Please note that
+    /// the order of the parallel evaluations is not determined with the
+    /// `rayon` feature.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{replay, De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .record_evals(true)
+    ///     .solve();
+    /// let new_func = MyFunc::new();
+    /// let replayed = replay(&new_func, s.eval_log());
+    /// assert_eq!(replayed.len(), s.eval_log().len());
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the evaluations are not recorded.
+    #[cfg(feature = "std")]
+    pub fn record_evals(self, record: bool) -> Self {
+        Self { eval_log: record, ..self }
+    }
+
+    /// Receive the commands from a channel to control the solver, see
+    /// [`SolverCmd`].
+    ///
+    /// The commands are processed after the callback of each generation, see
+    /// [`SolverBuilder::callback()`]. While paused, the solver blocks on the
+    /// receiver without busy-waiting, until a [`SolverCmd::Resume`] or
+    /// [`SolverCmd::Stop`] is received, or the senders are dropped.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver, SolverCmd};
+    /// use std::sync::mpsc::channel;
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let (tx, rx) = channel();
+    /// // For example, from a GUI thread
+    /// tx.send(SolverCmd::Stop).unwrap();
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .commands(rx)
+    ///     .solve();
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, there is no command channel.
+    #[cfg(feature = "std")]
+    pub fn commands(self, commands: std::sync::mpsc::Receiver<SolverCmd>) -> Self {
+        Self { commands: Some(commands), ..self }
+    }
+
+    /// Termination condition.
+    ///
+    /// The task function will be check each iteration, breaks if the return is
+    /// true.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve();
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the algorithm will iterate [`AlgCfg::max_gen()`]
+    /// generations, which is 200 for the provided methods. See also
+    /// [`SolverBuilder::max_gen()`].
+    pub fn task<'b, C>(self, task: C) -> SolverBuilder<'b, A, F>
+    where
+        'a: 'b,
+        C: FnMut(&Ctx<F>) -> bool + Send + 'b,
+    {
+        SolverBuilder {
+            task: Box::new(task),
+            task_label: "task".to_string(),
+            ..self
+        }
+    }
+
+    /// Terminate at the maximum generation.
+    ///
+    /// This is a typed convenience of `.task(|ctx| ctx.gen == max_gen)`, and
+    /// the value is also a hint for [`Ctx::progress()`].
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .max_gen(20)
+    ///     .solve();
+    /// ```
+    ///
+    /// # Default
+    ///
+    /// By default, the algorithm will iterate [`AlgCfg::max_gen()`]
+    /// generations, which is 200 for the provided methods.
+    pub fn max_gen(self, max_gen: u64) -> Self {
+        Self {
+            max_gen: Some(max_gen),
+            task: Box::new(|ctx| ctx.gen >= ctx.max_gen),
+            task_label: format!("max_gen({max_gen})"),
+            ..self
+        }
+    }
+
+    /// Stop when the convergence rate of the best fitness is flattened.
+    ///
+    /// A linear regression is fitted over the best fitness values of the last
+    /// `window` generations, and the algorithm breaks if the magnitude of the
+    /// slope is less than `slope_eps`. This condition is checked alongside the
+    /// [`SolverBuilder::task()`], any of them can terminate the algorithm.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .stop_on_slope(10, 1e-8)
+    ///     .solve();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is less than 2.
+    pub fn stop_on_slope(mut self, window: usize, slope_eps: f64) -> Self
+    where
+        <F::Ys as Fitness>::Eval: Into<f64>,
+    {
+        assert!(window >= 2, "Window size should be at least 2");
+        let mut buf = VecDeque::with_capacity(window);
+        self.stop.push(Box::new(move |ctx: &Ctx<F>| {
+            if buf.len() == window {
+                buf.pop_front();
+            }
+            buf.push_back(ctx.best_cost());
+            buf.len() == window && slope(&buf).abs() < slope_eps
+        }));
+        (self.stop_labels).push(format!("stop_on_slope({window}, {slope_eps})"));
+        self
+    }
+
+    /// Stop when the moving average of the best fitness reaches the target.
+    ///
+    /// The mean of the best fitness values of the last `window` generations is
+    /// compared with `target`, and the algorithm breaks if the mean is better
+    /// (lower, or higher for the maximization) than the target. It smooths out
+    /// a single lucky evaluation of the noisy objective functions. This
+    /// condition is checked alongside the [`SolverBuilder::task()`], any of
+    /// them can terminate the algorithm.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .stop_on_moving_average(10, 1e-4)
+    ///     .solve();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is zero.
+    pub fn stop_on_moving_average(mut self, window: usize, target: f64) -> Self
+    where
+        <F::Ys as Fitness>::Eval: Into<f64>,
+    {
+        assert!(window > 0, "Window size should be positive");
+        let mut buf = VecDeque::with_capacity(window);
+        self.stop.push(Box::new(move |ctx: &Ctx<F>| {
+            if buf.len() == window {
+                buf.pop_front();
+            }
+            buf.push_back(ctx.best_cost());
+            buf.len() == window
+                && buf.iter().sum::<f64>() / (window as f64) < ctx.compare.cost(target)
+        }));
+        (self.stop_labels).push(format!("stop_on_moving_average({window}, {target})"));
+        self
+    }
+
+    /// Stop when the population is converged in the parameter space.
+    ///
+    /// The algorithm breaks if the spread of the pool in each variable is less
+    /// than `tol` times the width of its bounds, see [`Ctx::max_spread()`].
+    /// It is the natural stopping rule for the noisy objective functions
+    /// with a well-defined solution, where the fitness is not reliable. This
+    /// condition is checked alongside the [`SolverBuilder::task()`], any of
+    /// them can terminate the algorithm.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let s = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .stop_on_param_convergence(1e-6)
+    ///     .solve();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tol` is negative.
+    pub fn stop_on_param_convergence(mut self, tol: f64) -> Self {
+        assert!(tol >= 0., "Tolerance should be non-negative");
+        (self.stop).push(Box::new(move |ctx: &Ctx<F>| ctx.max_spread() < tol));
+        (self.stop_labels).push(format!("stop_on_param_convergence({tol})"));
+        self
+    }
+
+    /// Stop when the hypervolume of the Pareto front is stagnated.
+    ///
+    /// The hypervolume bounded by the `reference` point (see
+    /// [`pareto::hypervolume()`]) is computed after each generation, from the
+    /// full archive if enabled ([`SolverBuilder::full_archive()`]). The
+    /// algorithm breaks if it has not improved by more than `eps` over the last
+    /// `patience` generations. This condition is checked alongside the
+    /// [`SolverBuilder::task()`], any of them can terminate the algorithm.
+    ///
+    /// The objectives are obtained from [`Fitness::objectives()`], which is
+    /// required to be implemented.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestMO as MyFunc;
     ///
-    /// let pool = Pool::Func(Box::new(gaussian_pool(&[0.; 4], &[1.; 4])));
     /// let s = Solver::build(Rga::default(), MyFunc::new())
     ///     .seed(0)
-    ///     .task(|ctx| ctx.gen == 20)
-    ///     .init_pool(pool)
+    ///     .stop_on_hypervolume(vec![100., 100.], 10, 1e-6)
     ///     .solve();
     /// ```
-    Func(PoolFunc<'a>),
-}
-
-/// Collect configuration and build the solver.
-///
-/// This type is created by [`Solver::build()`] method.
-///
-/// + First, setting a fixed seed with [`SolverBuilder::seed()`] method to get a
-///   determined result is highly recommended.
-/// + Next is [`SolverBuilder::task()`] method with a termination condition.
-/// + Finally, call [`SolverBuilder::solve()`] method to start the algorithm.
-#[allow(clippy::type_complexity)]
-#[must_use = "solver builder do nothing unless call the \"solve\" method"]
-pub struct SolverBuilder<'a, A: Algorithm<F>, F: ObjFunc> {
-    func: F,
-    algorithm: A,
-    pop_num: usize,
-    pareto_limit: usize,
-    seed: SeedOpt,
-    pool: Pool<'a, F>,
-    task: maybe_send_box!(FnMut(&Ctx<F>) -> bool + 'a),
-    callback: maybe_send_box!(FnMut(&Ctx<F>) + 'a),
-}
-
-impl<'a, A: Algorithm<F>, F: ObjFunc> SolverBuilder<'a, A, F> {
-    impl_builders! {
-        /// Population number.
-        ///
-        /// # Default
-        ///
-        /// If not changed by the algorithm setting, the default number is 200.
-        fn pop_num(usize)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `patience` is zero, or the `reference` point is empty. The
+    /// solving panics if the number of the objectives is not the same as the
+    /// `reference` point.
+    pub fn stop_on_hypervolume(mut self, reference: Vec<f64>, patience: u64, eps: f64) -> Self
+    where
+        F::Ys: Fitness<Best<F::Ys> = Pareto<F::Ys>>,
+    {
+        assert!(patience > 0, "Patience should be positive");
+        assert!(
+            !reference.is_empty(),
+            "The reference point should not be empty"
+        );
+        let label = format!("stop_on_hypervolume({reference:?}, {patience}, {eps})");
+        let window = patience as usize + 1;
+        let mut buf = VecDeque::with_capacity(window);
+        self.stop.push(Box::new(move |ctx: &Ctx<F>| {
+            let front = ctx.best.archive().unwrap_or(&ctx.best);
+            let front = (front.as_pareto().iter())
+                .map(Fitness::objectives)
+                .collect::<Vec<_>>();
+            if buf.len() == window {
+                buf.pop_front();
+            }
+            buf.push_back(pareto::hypervolume(&front, &reference));
+            buf.len() == window && buf[window - 1] - buf[0] <= eps
+        }));
+        self.stop_labels.push(label);
+        self
     }
 
-    /// Pareto front limit.
+    /// Set callback function.
     ///
-    /// It is not working for single-objective optimization.
+    /// Callback function allows to change an outer mutable variable in each
+    /// iteration.
     ///
     /// ```
     /// use metaheuristics_nature::{Rga, Solver};
-    /// # use metaheuristics_nature::tests::TestMO as MyFunc;
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
     ///
+    /// let mut report = Vec::with_capacity(20);
     /// let s = Solver::build(Rga::default(), MyFunc::new())
     ///     .seed(0)
     ///     .task(|ctx| ctx.gen == 20)
-    ///     .pareto_limit(10)
+    ///     .callback(|ctx| report.push(ctx.best.get_eval()))
     ///     .solve();
     /// ```
     ///
     /// # Default
     ///
-    /// By default, there is no limit. The limit is set to `usize::MAX`.
-    pub fn pareto_limit(self, pareto_limit: usize) -> Self
+    /// By default, this function does nothing.
+    pub fn callback<'b, C>(self, callback: C) -> SolverBuilder<'b, A, F>
     where
-        F::Ys: Fitness<Best<F::Ys> = Pareto<F::Ys>>,
+        'a: 'b,
+        C: FnMut(&Ctx<F>) + Send + 'b,
     {
-        Self { pareto_limit, ..self }
+        SolverBuilder { callback: Box::new(callback), ..self }
     }
 
-    /// Set a fixed random seed to get a determined result.
+    /// Set the clock to measure [`Ctx::elapsed()`], for the time-based
+    /// termination such as [`SolverBuilder::time_limit()`].
+    ///
+    /// ```
+    /// use core::time::Duration;
+    /// use metaheuristics_nature::{Clock, Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// // A fake clock advancing 1 ms on each reading
+    /// struct Fake(u64);
+    ///
+    /// impl Clock for Fake {
+    ///     fn now(&mut self) -> Duration {
+    ///         self.0 += 1;
+    ///         Duration::from_millis(self.0)
+    ///     }
+    /// }
+    ///
+    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .clock(Fake(0))
+    ///     .time_limit(Duration::from_millis(20))
+    ///     .solve();
+    /// ```
     ///
     /// # Default
     ///
-    /// By default, the random seed is auto-decided so you cannot reproduce the
-    /// result. Please print the seed via [`Solver::seed()`] method to get the
-    /// seed that used in the algorithm.
-    pub fn seed(self, seed: impl Into<SeedOpt>) -> Self {
-        Self { seed: seed.into(), ..self }
+    /// By default, the clock is [`std::time::Instant`] if the `std` feature
+    /// is enabled, otherwise, there is no clock.
+    pub fn clock<'b, C>(self, clock: C) -> SolverBuilder<'b, A, F>
+    where
+        'a: 'b,
+        C: Clock + Send + 'b,
+    {
+        SolverBuilder { clock: Some(Box::new(clock)), ..self }
     }
 
-    /// Initialize the pool with the pool option.
-    ///
-    /// # Default
+    /// Terminate if the elapsed time of the clock reaches the limit, see
+    /// [`SolverBuilder::clock()`].
     ///
-    /// By default, the pool is generated by the uniform distribution
-    /// [`uniform_pool()`].
-    pub fn init_pool(self, pool: Pool<'a, F>) -> Self {
-        Self { pool, ..self }
+    /// This condition is combined with the task, the solver stops when any of
+    /// them is true. It never fires without a clock.
+    pub fn time_limit(mut self, limit: core::time::Duration) -> Self {
+        self.stop.push(Box::new(move |ctx: &Ctx<F>| {
+            ctx.elapsed().is_some_and(|t| t >= limit)
+        }));
+        (self.stop_labels).push(format!("time_limit({limit:?})"));
+        self
     }
 
-    /// Termination condition.
+    /// Set the callback function of the Pareto front changes.
     ///
-    /// The task function will be check each iteration, breaks if the return is
-    /// true.
+    /// The function is called with the current front before the next
+    /// generation, only if the front is changed since the last call, see
+    /// [`Pareto::revision()`]. The first call is the initial front.
     ///
     /// ```
     /// use metaheuristics_nature::{Rga, Solver};
-    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    /// # use metaheuristics_nature::tests::TestMO as MyFunc;
     ///
+    /// let mut sizes = Vec::new();
     /// let s = Solver::build(Rga::default(), MyFunc::new())
     ///     .seed(0)
     ///     .task(|ctx| ctx.gen == 20)
+    ///     .on_archive_update(|front| sizes.push(front.len()))
     ///     .solve();
     /// ```
     ///
     /// # Default
     ///
-    /// By default, the algorithm will iterate 200 generation.
-    pub fn task<'b, C>(self, task: C) -> SolverBuilder<'b, A, F>
+    /// By default, this function does nothing.
+    pub fn on_archive_update<'b, C>(self, mut callback: C) -> SolverBuilder<'b, A, F>
     where
         'a: 'b,
-        C: FnMut(&Ctx<F>) -> bool + Send + 'b,
+        C: FnMut(&[F::Ys]) + Send + 'b,
+        F::Ys: Fitness<Best<F::Ys> = Pareto<F::Ys>>,
     {
-        SolverBuilder { task: Box::new(task), ..self }
+        let mut revision = None;
+        let f = move |ctx: &Ctx<F>| {
+            let curr = ctx.best.revision();
+            if revision != Some(curr) {
+                revision = Some(curr);
+                callback(ctx.best.as_pareto());
+            }
+        };
+        SolverBuilder { archive_update: Some(Box::new(f)), ..self }
     }
 
-    /// Set callback function.
+    /// Set the source of the external candidates, which is polled after each
+    /// generation.
     ///
-    /// Callback function allows to change an outer mutable variable in each
-    /// iteration.
+    /// The returned candidate is evaluated, and replaces the worst individual
+    /// if it is better. It is used to feed the good candidates computed
+    /// elsewhere, such as another solver in a distributed optimization.
     ///
     /// ```
-    /// use metaheuristics_nature::{Rga, Solver};
+    /// use metaheuristics_nature::{De, Solver};
+    /// use std::sync::mpsc::channel;
     /// # use metaheuristics_nature::tests::TestObj as MyFunc;
     ///
-    /// let mut report = Vec::with_capacity(20);
-    /// let s = Solver::build(Rga::default(), MyFunc::new())
+    /// let (tx, rx) = channel();
+    /// // For example, from another solver
+    /// tx.send(vec![0.; 4]).unwrap();
+    /// let s = Solver::build(De::default(), MyFunc::new())
     ///     .seed(0)
     ///     .task(|ctx| ctx.gen == 20)
-    ///     .callback(|ctx| report.push(ctx.best.get_eval()))
+    ///     .immigrant_source(move || rx.try_recv().ok())
     ///     .solve();
     /// ```
     ///
+    /// # Panics
+    ///
+    /// Panics if the dimension of the candidate is not consistent with the
+    /// objective function.
+    ///
     /// # Default
     ///
-    /// By default, this function does nothing.
-    pub fn callback<'b, C>(self, callback: C) -> SolverBuilder<'b, A, F>
+    /// By default, there is no external candidate.
+    pub fn immigrant_source<'b, C>(self, source: C) -> SolverBuilder<'b, A, F>
     where
         'a: 'b,
-        C: FnMut(&Ctx<F>) + Send + 'b,
+        C: FnMut() -> Option<Vec<f64>> + Send + 'b,
     {
-        SolverBuilder { callback: Box::new(callback), ..self }
+        SolverBuilder { immigrant: Some(Box::new(source)), ..self }
+    }
+
+    /// Get the algorithm (the "method" type), such as the handles to adjust
+    /// its state during solving.
+    ///
+    /// See also [`rga::Rates`].
+    pub fn algorithm(&self) -> &A {
+        &self.algorithm
+    }
+
+    /// Export the configuration as a [`RunConfig`] for the experiment
+    /// provenance.
+    ///
+    /// The closures are described by the best-effort labels. The seed is
+    /// `None` if it is auto-decided, use [`Solver::seed()`] after solving to
+    /// complete it.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let builder = Solver::build(De::default(), MyFunc::new()).max_gen(20);
+    /// let mut config = builder.run_config();
+    /// let s = builder.solve();
+    /// config.seed = Some(s.seed());
+    /// assert_eq!(config.method, "DE");
+    /// assert_eq!(config.termination, ["max_gen(20)"]);
+    /// ```
+    pub fn run_config(&self) -> RunConfig {
+        let pool = match &self.pool {
+            Pool::Ready { .. } => "Ready".to_string(),
+            Pool::ReadyParams(_) => "ReadyParams".to_string(),
+            Pool::UniformBy { max_tries, .. } => format!("UniformBy({max_tries})"),
+            Pool::FeasibleSeek { max_tries, .. } => format!("FeasibleSeek({max_tries})"),
+            Pool::Func(_) => "Func".to_string(),
+            Pool::PerDim(dist) => format!("PerDim({dist:?})"),
+            Pool::Permutation => "Permutation".to_string(),
+        };
+        let seed = match self.seed {
+            SeedOpt::Entropy => None,
+            seed if !seed.is_supported() => None,
+            seed => Some(Rng::new(seed).seed()),
+        };
+        let termination = core::iter::once(&self.task_label)
+            .chain(&self.stop_labels)
+            .cloned()
+            .collect();
+        RunConfig {
+            method: self.algorithm.name().to_string(),
+            params: (self.algorithm.params().into_iter())
+                .map(|(name, v)| (name.to_string(), v))
+                .collect(),
+            pop_num: self.pop_size(),
+            seed,
+            pareto_limit: self.pareto_limit,
+            pool,
+            termination,
+        }
+    }
+
+    // The population number decided by the pool option
+    fn pop_size(&self) -> usize {
+        match &self.pool {
+            Pool::Ready { pool, .. } | Pool::ReadyParams(pool) => pool.len(),
+            _ => self.pop_num,
+        }
+    }
+
+    /// The (maximum) number of the objective function evaluations in each
+    /// generation, see [`Algorithm::evals_per_gen()`].
+    ///
+    /// The re-evaluation of [`SolverBuilder::cooperative()`] and the
+    /// candidate of [`SolverBuilder::immigrant_source()`] are included. The
+    /// initialization and the restarts cost additional population number of
+    /// evaluations.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Fa, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let builder = Solver::build(Fa::default(), MyFunc::new()).pop_num(20);
+    /// assert_eq!(builder.evals_per_gen(), 20 * 19 / 2);
+    /// ```
+    pub fn evals_per_gen(&self) -> usize {
+        let pop_num = self.pop_size();
+        let coop = if self.groups.is_empty() { 0 } else { pop_num };
+        let immigrant = usize::from(self.immigrant.is_some());
+        self.algorithm.evals_per_gen(pop_num) + coop + immigrant
     }
 
     /// Create the task and run the algorithm, which may takes a lot of time.
@@ -186,73 +1674,413 @@ impl<'a, A: Algorithm<F>, F: ObjFunc> SolverBuilder<'a, A, F> {
     ///
     /// # Panics
     ///
-    /// Panics before starting the algorithm if the following conditions are
-    /// met:
-    /// + The dimension size is zero.
+    /// Panics before starting the algorithm if the configuration is invalid.
+    /// Please see [`SolverBuilder::try_solve()`] for the conditions.
+    pub fn solve(self) -> Solver<F> {
+        self.try_solve().unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Create the task and run the algorithm, and return an error if the
+    /// configuration is invalid.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{BuildError, Fx, Rga, Solver};
+    ///
+    /// let f = Fx::new(&[], |&[]: &[f64; 0]| 0.);
+    /// let e = Solver::build(Rga::default(), f).try_solve().err();
+    /// assert_eq!(e, Some(BuildError::ZeroDim));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns error before starting the algorithm if the following conditions
+    /// are met:
+    /// + The dimension size is zero. ([`BuildError::ZeroDim`])
     /// + The lower bound is greater than the upper bound.
+    ///   ([`BuildError::InvalidBound`])
+    /// + The population number is zero, or using the [`Pool::Ready`] or
+    ///   [`Pool::ReadyParams`] option with an empty pool.
+    ///   ([`BuildError::EmptyPool`])
     /// + Using the [`Pool::Ready`] option and the pool size or dimension size
-    ///   is not consistent.
-    pub fn solve(self) -> Solver<F> {
+    ///   is not consistent. ([`BuildError::PoolSizeMismatched`] and
+    ///   [`BuildError::PoolDimMismatched`])
+    /// + Using the [`Pool::ReadyParams`] option and the dimension size is not
+    ///   consistent. ([`BuildError::PoolDimMismatched`])
+    /// + The variable groups of [`SolverBuilder::cooperative()`] are empty or
+    ///   out of the dimension. ([`BuildError::InvalidGroups`])
+    /// + Using the [`Pool::UniformBy`] or [`Pool::FeasibleSeek`] option and
+    ///   the pool is not filled in the maximum number of the draws.
+    ///   ([`BuildError::InfeasibleInit`])
+    /// + Using the [`SeedOpt::Versioned`] option with an unsupported version.
+    ///   ([`BuildError::UnsupportedRngVersion`])
+    /// + Using the [`Pool::PerDim`] option with a length other than the
+    ///   dimension size ([`BuildError::PoolDimMismatched`]), or a log-uniform
+    ///   variable with a non-positive lower bound.
+    ///   ([`BuildError::InvalidDimDist`])
+    /// + Using the [`Pool::Permutation`] option with the different bounds.
+    ///   ([`BuildError::UnequalBounds`])
+    /// + Using the [`SolverBuilder::resume_from()`] option with a method that
+    ///   is not stateless. ([`BuildError::StatefulResume`])
+    /// + Using the [`SolverBuilder::maximize()`] option with an objective
+    ///   function that only supports minimization.
+    ///   ([`BuildError::MinimizeOnly`])
+    /// + The dimension of the [`SolverBuilder::incumbent()`] is not
+    ///   consistent. ([`BuildError::PoolDimMismatched`])
+    pub fn try_solve(self) -> Result<Solver<F>, BuildError> {
+        let mut iter = self.build()?;
+        while iter.step() {}
+        Ok(iter.into_solver())
+    }
+
+    /// Create the task and return an iterator that runs one generation per
+    /// [`Iterator::next()`] call, yielding a [`GenerationSnapshot`].
+    ///
+    /// The termination conditions and the callback still apply, the iterator
+    /// ends when the task fires. Use [`SolverIter::into_solver()`] to get the
+    /// result after the iteration.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let mut iter = Solver::build(Rga::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .iter();
+    /// for snapshot in iter.by_ref().take_while(|s| s.diversity > 1e-3) {
+    ///     println!("{} {}", snapshot.gen, snapshot.best);
+    /// }
+    /// let s = iter.into_solver();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics before starting the algorithm if the configuration is invalid.
+    /// Please see [`SolverBuilder::try_solve()`] for the conditions.
+    pub fn iter(self) -> SolverIter<'a, A, F> {
+        self.build().unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn build(self) -> Result<SolverIter<'a, A, F>, BuildError> {
         let Self {
             func,
             mut algorithm,
             pop_num,
+            pop_num_hint: _,
+            max_gen,
+            max_gen_hint,
             pareto_limit,
+            full_archive,
+            truncation,
+            maximize,
+            compare,
             seed,
+            stream_seeds,
             pool,
-            mut task,
-            mut callback,
+            incumbent,
+            resume,
+            groups,
+            restart,
+            max_restarts,
+            shrink,
+            dedup,
+            anti_saturation,
+            replacement,
+            sharing,
+            eval_chunk,
+            eval_parallel,
+            reproducible,
+            eval_order,
+            clamp,
+            track,
+            record_best,
+            record_by_evals,
+            diversity_stride,
+            #[cfg(feature = "std")]
+            gen_times,
+            #[cfg(feature = "std")]
+            eval_log,
+            #[cfg(feature = "std")]
+            commands,
+            task,
+            stop,
+            task_label: _,
+            stop_labels: _,
+            callback,
+            archive_update,
+            immigrant,
+            clock,
         } = self;
-        assert!(func.dim() != 0, "Dimension should be greater than 0");
-        assert!(
-            func.bound().iter().all(|[lb, ub]| lb <= ub),
-            "Lower bound should be less than upper bound"
-        );
-        let mut rng = Rng::new(seed);
-        let mut ctx = match pool {
+        // A single chunk is run on the current thread
+        let eval_chunk = if eval_parallel && !reproducible {
+            eval_chunk
+        } else {
+            usize::MAX
+        };
+        // Snapshot the bounds, the objective function may compute them
+        let bound = func.bound().to_vec();
+        let dim = bound.len();
+        if dim == 0 {
+            return Err(BuildError::ZeroDim);
+        }
+        if !bound.iter().all(|[lb, ub]| lb <= ub) {
+            return Err(BuildError::InvalidBound);
+        }
+        if (groups.iter()).any(|g| g.is_empty() || g.iter().any(|s| *s >= dim)) {
+            return Err(BuildError::InvalidGroups);
+        }
+        if !seed.is_supported() {
+            return Err(BuildError::UnsupportedRngVersion);
+        }
+        if incumbent.as_ref().is_some_and(|xs| xs.len() != dim) {
+            return Err(BuildError::PoolDimMismatched);
+        }
+        if resume.is_some() && !algorithm.is_stateless() {
+            return Err(BuildError::StatefulResume);
+        }
+        if maximize && func.is_minimize_only() {
+            return Err(BuildError::MinimizeOnly);
+        }
+        let range = |s: usize| bound[s][0]..=bound[s][1];
+        let mut rng = Rng::new(stream_seeds.init.map_or(seed, SeedOpt::U64));
+        // The saved pool is evaluated
+        let mut resume = resume;
+        let pool = match &mut resume {
+            Some(state) => Pool::Ready {
+                pool: core::mem::take(&mut state.pool),
+                pool_y: core::mem::take(&mut state.pool_y),
+            },
+            None => pool,
+        };
+        let evaluated = !matches!(pool, Pool::Ready { .. });
+        let (pool, pool_y) = match pool {
             Pool::Ready { pool, pool_y } => {
-                assert_eq!(pool.len(), pool_y.len(), "Pool size mismatched");
-                let dim = func.dim();
-                pool.iter()
-                    .for_each(|xs| assert!(xs.len() == dim, "Pool dimension mismatched"));
-                Ctx::from_parts(func, pareto_limit, pool, pool_y)
+                if pool.is_empty() {
+                    return Err(BuildError::EmptyPool);
+                }
+                if pool.len() != pool_y.len() {
+                    return Err(BuildError::PoolSizeMismatched);
+                }
+                if pool.iter().any(|xs| xs.len() != dim) {
+                    return Err(BuildError::PoolDimMismatched);
+                }
+                (pool, pool_y)
+            }
+            Pool::ReadyParams(pool) => {
+                if pool.is_empty() {
+                    return Err(BuildError::EmptyPool);
+                }
+                if pool.iter().any(|xs| xs.len() != dim) {
+                    return Err(BuildError::PoolDimMismatched);
+                }
+                let pool_y = fitness_all(&func, &pool, eval_chunk, eval_order);
+                (pool, pool_y)
             }
-            Pool::UniformBy(filter) => {
-                let dim = func.dim();
+            _ if pop_num == 0 => return Err(BuildError::EmptyPool),
+            Pool::UniformBy { filter, max_tries } => {
+                // Keep the draws serial, the number of draws depends on the filter
                 let mut pool = Vec::with_capacity(pop_num);
                 let rand_f = uniform_pool();
+                let mut tries = 0;
                 while pool.len() < pop_num {
+                    if tries == max_tries {
+                        return Err(BuildError::InfeasibleInit);
+                    }
+                    tries += 1;
                     let xs = (0..dim)
-                        .map(|s| rand_f(s, func.bound_range(s), &mut rng))
+                        .map(|s| rand_f(s, range(s), &mut rng))
                         .collect::<Vec<_>>();
                     if filter(&xs) {
                         pool.push(xs);
                     }
                 }
-                Ctx::from_pool(func, pareto_limit, pool)
+                let pool_y = fitness_all(&func, &pool, eval_chunk, eval_order);
+                (pool, pool_y)
+            }
+            Pool::FeasibleSeek { filter, max_tries } => {
+                // Keep the draws serial, the number of draws depends on the filter
+                let mut pool = Vec::<Vec<f64>>::with_capacity(pop_num);
+                let rand_f = uniform_pool();
+                let (mut tries, mut fails, mut std) = (0, 0, 0.1);
+                while pool.len() < pop_num {
+                    if tries == max_tries {
+                        return Err(BuildError::InfeasibleInit);
+                    }
+                    tries += 1;
+                    let local = !pool.is_empty() && tries > pop_num as u64;
+                    let xs = if local {
+                        let center = &pool[rng.ub(pool.len())];
+                        (0..dim)
+                            .map(|s| {
+                                let [lb, ub] = bound[s];
+                                rng.normal(center[s], std * (ub - lb)).clamp(lb, ub)
+                            })
+                            .collect::<Vec<_>>()
+                    } else {
+                        (0..dim)
+                            .map(|s| rand_f(s, range(s), &mut rng))
+                            .collect::<Vec<_>>()
+                    };
+                    if filter(&xs) {
+                        pool.push(xs);
+                    } else if local {
+                        fails += 1;
+                        if fails == 10 {
+                            std *= 0.5;
+                            fails = 0;
+                        }
+                    }
+                }
+                let pool_y = fitness_all(&func, &pool, eval_chunk, eval_order);
+                (pool, pool_y)
             }
             Pool::Func(f) => {
-                let dim = func.dim();
+                let pool = (0..pop_num)
+                    .map(|_| (0..dim).map(|s| f(s, range(s), &mut rng)).collect())
+                    .collect::<Vec<_>>();
+                let pool_y = fitness_all(&func, &pool, eval_chunk, eval_order);
+                (pool, pool_y)
+            }
+            Pool::PerDim(dist) => {
+                if dist.len() != dim {
+                    return Err(BuildError::PoolDimMismatched);
+                }
+                let invalid = |(d, [lb, _]): (&DimDist, &[f64; 2])| {
+                    matches!(d, DimDist::LogUniform) && *lb <= 0.
+                };
+                if zip(&dist, &bound).any(invalid) {
+                    return Err(BuildError::InvalidDimDist);
+                }
                 let pool = (0..pop_num)
                     .map(|_| {
                         (0..dim)
-                            .map(|s| f(s, func.bound_range(s), &mut rng))
+                            .map(|s| dist[s].sample(range(s), &mut rng))
                             .collect()
                     })
-                    .collect();
-                Ctx::from_pool(func, pareto_limit, pool)
+                    .collect::<Vec<_>>();
+                let pool_y = fitness_all(&func, &pool, eval_chunk, eval_order);
+                (pool, pool_y)
+            }
+            Pool::Permutation => {
+                if bound.windows(2).any(|w| w[0] != w[1]) {
+                    return Err(BuildError::UnequalBounds);
+                }
+                let n = dim as f64;
+                let pool = (0..pop_num)
+                    .map(|_| {
+                        let mut perm = (0..dim).collect::<Vec<_>>();
+                        rng.shuffle(perm.as_mut_slice());
+                        let mut xs = alloc::vec![0.; dim];
+                        for (k, &s) in perm.iter().enumerate() {
+                            let [lb, ub] = bound[s];
+                            xs[s] = lb + (k as f64 + rng.rand()) / n * (ub - lb);
+                        }
+                        xs
+                    })
+                    .collect::<Vec<_>>();
+                let pool_y = fitness_all(&func, &pool, eval_chunk, eval_order);
+                (pool, pool_y)
             }
         };
-        algorithm.init(&mut ctx, &mut rng);
-        loop {
-            callback(&ctx);
-            if task(&ctx) {
-                break;
+        // Switch to the streams of the methods
+        if stream_seeds.init.is_some() {
+            rng = Rng::new(seed);
+        }
+        if let Some(select) = stream_seeds.select {
+            rng = Rng::new(SeedOpt::U64(select));
+        }
+        if let Some(mutate) = stream_seeds.mutate {
+            rng = rng.with_mutation(SeedOpt::U64(mutate));
+        }
+        if let Some(state) = &resume {
+            rng = state.rng.clone();
+        }
+        let pop_num = pool.len();
+        let compare = Comparator::new(maximize, compare);
+        let mut best = BestCon::<F::Ys>::from_limit(pareto_limit);
+        best.set_archive(full_archive);
+        if let Some(truncation) = truncation {
+            best.set_truncation(truncation);
+        }
+        let mut ctx = Ctx::new(func, bound, best, pool, pool_y, compare);
+        ctx.max_gen = max_gen.unwrap_or_else(|| max_gen_hint(pop_num, dim));
+        ctx.replacement = replacement;
+        ctx.sharing = sharing;
+        ctx.eval_chunk = eval_chunk;
+        ctx.eval_order = eval_order;
+        ctx.clamp = clamp;
+        #[cfg(feature = "std")]
+        if eval_log {
+            ctx.eval_log = Some(Default::default());
+        }
+        if evaluated {
+            ctx.log_evals(&ctx.pool, &ctx.pool_y);
+        }
+        if let Some(state) = resume {
+            ctx.gen = state.gen;
+            ctx.evals = state.evals.into();
+            let (xs, ys): (Vec<_>, Vec<_>) = state.best.into_iter().unzip();
+            ctx.best.update_all(&xs, &ys);
+        }
+        if let Some(mut xs) = incumbent {
+            if ctx.clamp_to_bounds() {
+                zip(&mut xs, &ctx.bound).for_each(|(x, [min, max])| *x = x.clamp(*min, *max));
             }
-            ctx.gen += 1;
-            algorithm.generation(&mut ctx, &mut rng);
+            let ys = ctx.fitness(&xs);
+            ctx.best.update(&xs, &ys);
         }
-        Solver::new(ctx, rng.seed())
+        ctx.repair_all();
+        let name = algorithm.name();
+        let params = algorithm.params();
+        // The variables of the subpopulations
+        let stash = (!groups.is_empty()).then(|| ctx.pool.clone());
+        algorithm.init(&mut ctx, &mut rng);
+        let last_best = restart.map(|_| ctx.best.as_result_fit().clone());
+        let diversity_history = (diversity_stride.iter())
+            .map(|_| (ctx.gen, ctx.diversity()))
+            .collect();
+        #[cfg(feature = "std")]
+        let clock = Some(clock.unwrap_or_else(|| Box::new(std::time::Instant::now())));
+        let clock = clock.map(|mut clock| {
+            let start = clock.now();
+            (clock, start)
+        });
+        Ok(SolverIter {
+            algorithm,
+            ctx,
+            rng,
+            name,
+            params,
+            groups,
+            stash,
+            restart,
+            max_restarts,
+            restarts: 0,
+            stall: 0,
+            last_best,
+            shrink,
+            dedup,
+            anti_saturation,
+            track,
+            tracked: Vec::new(),
+            tracking_stopped: None,
+            best_history: (record_best || record_by_evals).then(Vec::new),
+            record_by_evals,
+            diversity_stride,
+            diversity_history,
+            #[cfg(feature = "std")]
+            gen_times: gen_times.then(Vec::new),
+            #[cfg(feature = "std")]
+            commands,
+            task,
+            stop,
+            callback,
+            archive_update,
+            immigrant,
+            clock,
+            done: false,
+            by_task: false,
+        })
     }
 }
 
@@ -269,7 +2097,8 @@ impl<F: ObjFunc> Solver<F> {
     ///
     /// Use [`Solver::build_boxed()`] for dynamic dispatching.
     pub fn build<A: AlgCfg>(cfg: A, func: F) -> SolverBuilder<'static, A::Algorithm<F>, F> {
-        Self::build_default(cfg.algorithm(), A::pop_num(), func)
+        let pop_num_hint = [A::pop_num(), A::min_pop_num()];
+        Self::build_default(cfg.algorithm(), pop_num_hint, A::max_gen, func)
     }
 
     /// Start to build a solver with a boxed algorithm, the dynamic dispatching.
@@ -291,31 +2120,160 @@ impl<F: ObjFunc> Solver<F> {
     ///
     /// Use [`Solver::build()`] for optimized memory allocation and access.
     pub fn build_boxed<A: AlgCfg>(cfg: A, func: F) -> SolverBox<'static, F> {
-        Self::build_default(Box::new(cfg.algorithm()), A::pop_num(), func)
+        let pop_num_hint = [A::pop_num(), A::min_pop_num()];
+        Self::build_default(Box::new(cfg.algorithm()), pop_num_hint, A::max_gen, func)
+    }
+
+    /// Start to build a solver with an algorithm instance, such as the
+    /// meta-methods.
+    ///
+    /// The default population number is 200, and the default maximum
+    /// generation is 200.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{ensemble::Ensemble, De, Rga, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let method = Ensemble::new()
+    ///     .add(De::default(), 0.5)
+    ///     .add(Rga::default(), 0.5);
+    /// let s = Solver::build_algorithm(method, MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .solve();
+    /// ```
+    pub fn build_algorithm<A: Algorithm<F>>(algorithm: A, func: F) -> SolverBuilder<'static, A, F> {
+        Self::build_default(algorithm, [200, 1], |_, _| 200, func)
     }
 
     fn build_default<A: Algorithm<F>>(
         algorithm: A,
-        pop_num: usize,
+        pop_num_hint: [usize; 2],
+        max_gen_hint: fn(usize, usize) -> u64,
         func: F,
     ) -> SolverBuilder<'static, A, F> {
         SolverBuilder {
             func,
             algorithm,
-            pop_num,
+            pop_num: pop_num_hint[0],
+            pop_num_hint,
+            max_gen: None,
+            max_gen_hint,
             pareto_limit: usize::MAX,
+            full_archive: false,
+            truncation: None,
+            maximize: false,
+            compare: None,
             seed: SeedOpt::Entropy,
+            stream_seeds: StreamSeeds::default(),
             pool: Pool::Func(Box::new(uniform_pool())),
-            task: Box::new(|ctx| ctx.gen == 200),
+            incumbent: None,
+            resume: None,
+            groups: Vec::new(),
+            restart: None,
+            max_restarts: u64::MAX,
+            shrink: None,
+            dedup: None,
+            anti_saturation: None,
+            replacement: Replacement::Parent,
+            sharing: None,
+            eval_chunk: 1,
+            eval_parallel: true,
+            reproducible: false,
+            eval_order: EvalOrder::default(),
+            clamp: true,
+            track: None,
+            record_best: false,
+            record_by_evals: false,
+            diversity_stride: None,
+            #[cfg(feature = "std")]
+            gen_times: false,
+            #[cfg(feature = "std")]
+            eval_log: false,
+            #[cfg(feature = "std")]
+            commands: None,
+            task: Box::new(|ctx| ctx.gen >= ctx.max_gen),
+            stop: Vec::new(),
+            task_label: "max_gen".to_string(),
+            stop_labels: Vec::new(),
             callback: Box::new(|_| ()),
+            archive_update: None,
+            immigrant: None,
+            clock: None,
+        }
+    }
+}
+
+// Slope of the least squares line, where x is the index of the values.
+fn slope(ys: &VecDeque<f64>) -> f64 {
+    let n = ys.len() as f64;
+    let x_mean = (n - 1.) * 0.5;
+    let y_mean = ys.iter().sum::<f64>() / n;
+    let (num, den) = ys.iter().enumerate().fold((0., 0.), |(num, den), (x, y)| {
+        let dx = x as f64 - x_mean;
+        (num + dx * (y - y_mean), den + dx * dx)
+    });
+    num / den
+}
+
+/// Detect the variable groups by differential grouping (DG).
+///
+/// Two variables are interacted if the fitness difference of perturbing one
+/// variable is changed by another variable more than `eps`. The interacted
+/// variables are in the same group, and all the separable variables are in a
+/// group. The return value can be used in [`SolverBuilder::cooperative()`].
+///
+/// This function evaluates the objective function `O(dim^2)` times.
+///
+/// ```
+/// use metaheuristics_nature::{differential_grouping, Fx};
+///
+/// let bound = [[-50., 50.]; 4];
+/// let f = Fx::new(&bound, |&[a, b, c, d]| a * b + c * c + d * d);
+/// assert_eq!(differential_grouping(&f, 1e-3), [vec![0, 1], vec![2, 3]]);
+/// ```
+///
+/// Reference: <https://doi.org/10.1109/TEVC.2013.2281543>
+pub fn differential_grouping<F: ObjFunc>(func: &F, eps: f64) -> Vec<Vec<usize>>
+where
+    <F::Ys as Fitness>::Eval: Into<f64>,
+{
+    let f = |xs: &[f64]| -> f64 { func.fitness(xs).eval().into() };
+    let mut rest = (0..func.dim()).collect::<Vec<_>>();
+    let mut groups = Vec::new();
+    let mut separable = Vec::new();
+    while let Some(i) = rest.first().copied() {
+        let mut group = alloc::vec![i];
+        for &j in &rest[1..] {
+            let mut p1 = (0..func.dim()).map(|s| func.lb(s)).collect::<Vec<_>>();
+            let mut p2 = p1.clone();
+            p2[i] = func.ub(i);
+            let d1 = f(&p1) - f(&p2);
+            let mid = 0.5 * (func.lb(j) + func.ub(j));
+            p1[j] = mid;
+            p2[j] = mid;
+            let d2 = f(&p1) - f(&p2);
+            if (d1 - d2).abs() > eps {
+                group.push(j);
+            }
+        }
+        rest.retain(|s| !group.contains(s));
+        if group.len() == 1 {
+            separable.push(i);
+        } else {
+            groups.push(group);
         }
     }
+    if !separable.is_empty() {
+        groups.push(separable);
+    }
+    groups
 }
 
 /// A function generates a uniform pool.
 ///
-/// See also [`gaussian_pool()`], [`Pool::Func`], and
-/// [`SolverBuilder::init_pool()`].
+/// See also [`gaussian_pool()`], [`gaussian_pool_auto()`], [`Pool::Func`],
+/// and [`SolverBuilder::init_pool()`].
 pub fn uniform_pool() -> PoolFunc<'static> {
     Box::new(move |_, range, rng| rng.range(range))
 }
@@ -324,7 +2282,7 @@ pub fn uniform_pool() -> PoolFunc<'static> {
 ///
 /// Where `mean` is the mean value, `std` is the standard deviation.
 ///
-/// See also [`uniform_pool()`], [`Pool::Func`], and
+/// See also [`uniform_pool()`], [`gaussian_pool_auto()`], [`Pool::Func`], and
 /// [`SolverBuilder::init_pool()`].
 ///
 /// # Panics
@@ -334,3 +2292,34 @@ pub fn gaussian_pool<'a>(mean: &'a [f64], std: &'a [f64]) -> PoolFunc<'a> {
     assert_eq!(mean.len(), std.len());
     Box::new(move |s, _, rng| rng.normal(mean[s], std[s]))
 }
+
+/// A function generates a Gaussian pool from the bounds.
+///
+/// Each variable is centered at the midpoint of its bounds, and the standard
+/// deviation is `spread` times the width of the bounds.
+///
+/// ```
+/// use metaheuristics_nature::{gaussian_pool_auto, Pool, Rga, Solver};
+/// # use metaheuristics_nature::tests::TestObj as MyFunc;
+///
+/// let pool = Pool::Func(gaussian_pool_auto(0.1));
+/// let s = Solver::build(Rga::default(), MyFunc::new())
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .init_pool(pool)
+///     .solve();
+/// ```
+///
+/// See also [`uniform_pool()`], [`gaussian_pool()`], [`Pool::Func`], and
+/// [`SolverBuilder::init_pool()`].
+///
+/// # Panics
+///
+/// Panic when `spread` is negative.
+pub fn gaussian_pool_auto(spread: f64) -> PoolFunc<'static> {
+    assert!(spread >= 0., "Spread should be non-negative");
+    Box::new(move |_, range, rng| {
+        let (min, max) = range.into_inner();
+        rng.normal(0.5 * (min + max), spread * (max - min))
+    })
+}
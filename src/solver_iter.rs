@@ -0,0 +1,363 @@
+use crate::{prelude::*, solver_builder::StopFunc};
+use alloc::{boxed::Box, vec::Vec};
+use core::iter::zip;
+
+/// A snapshot of the solver after a generation.
+///
+/// Yielded by [`SolverIter`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct GenerationSnapshot<E> {
+    /// Generation number
+    pub gen: u64,
+    /// Best evaluation value
+    pub best: E,
+    /// Diversity of the population, see [`Ctx::diversity()`]
+    pub diversity: f64,
+}
+
+/// A command to control the solver, see [`SolverBuilder::commands()`].
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolverCmd {
+    /// Pause the solver until resumed or stopped
+    Pause,
+    /// Resume the paused solver
+    Resume,
+    /// Stop the solver, the result is valid as terminated by the task
+    Stop,
+    /// Re-seed the random number generator
+    SetSeed(u64),
+}
+
+/// An iterator over the generations of the solver.
+///
+/// Created by [`SolverBuilder::iter()`].
+#[allow(clippy::type_complexity)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SolverIter<'a, A: Algorithm<F>, F: ObjFunc> {
+    pub(crate) algorithm: A,
+    pub(crate) ctx: Ctx<F>,
+    pub(crate) rng: Rng,
+    pub(crate) name: &'static str,
+    pub(crate) params: Vec<(&'static str, f64)>,
+    pub(crate) groups: Vec<Vec<usize>>,
+    // The variables of the subpopulations
+    pub(crate) stash: Option<Vec<Vec<f64>>>,
+    pub(crate) restart: Option<u64>,
+    pub(crate) max_restarts: u64,
+    pub(crate) restarts: u64,
+    pub(crate) stall: u64,
+    pub(crate) last_best: Option<F::Ys>,
+    pub(crate) shrink: Option<f64>,
+    pub(crate) dedup: Option<f64>,
+    pub(crate) anti_saturation: Option<f64>,
+    pub(crate) track: Option<usize>,
+    pub(crate) tracked: Vec<Vec<f64>>,
+    pub(crate) tracking_stopped: Option<u64>,
+    // The generation, the number of the evaluations, and the best element of
+    // each improvement
+    pub(crate) best_history: Option<Vec<([u64; 2], Vec<f64>, F::Ys)>>,
+    // Key the history by the number of the evaluations
+    pub(crate) record_by_evals: bool,
+    pub(crate) diversity_stride: Option<u64>,
+    pub(crate) diversity_history: Vec<(u64, f64)>,
+    #[cfg(feature = "std")]
+    pub(crate) gen_times: Option<Vec<std::time::Duration>>,
+    #[cfg(feature = "std")]
+    pub(crate) commands: Option<std::sync::mpsc::Receiver<SolverCmd>>,
+    pub(crate) task: maybe_send_box!(FnMut(&Ctx<F>) -> bool + 'a),
+    pub(crate) stop: Vec<StopFunc<'a, F>>,
+    pub(crate) callback: maybe_send_box!(FnMut(&Ctx<F>) + 'a),
+    pub(crate) archive_update: Option<maybe_send_box!(FnMut(&Ctx<F>) + 'a)>,
+    pub(crate) immigrant: Option<maybe_send_box!(FnMut() -> Option<Vec<f64>> + 'a)>,
+    // The clock and its starting time
+    pub(crate) clock: Option<(maybe_send_box!(Clock + 'a), core::time::Duration)>,
+    pub(crate) done: bool,
+    // Terminated by the task only, see `SolverIter::continue_for()`
+    pub(crate) by_task: bool,
+}
+
+impl<A: Algorithm<F>, F: ObjFunc> SolverIter<'_, A, F> {
+    /// Get the context of the current generation.
+    pub fn ctx(&self) -> &Ctx<F> {
+        &self.ctx
+    }
+
+    /// Swap the method with the context kept, including the pool, the best
+    /// set, the generation number, and the random number generator.
+    ///
+    /// The method is initialized by [`Algorithm::init()`] from the current
+    /// pool. Use a boxed method ([`Solver::build_boxed()`]) to switch to a
+    /// different type, such as the hyper-heuristics that select the method
+    /// online. The algorithm name and parameters of the [`Solver`] are still
+    /// the ones before solving.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{AlgCfg, De, Pso, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let mut iter = Solver::build_boxed(Pso::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 50)
+    ///     .iter();
+    /// iter.by_ref().take(25).for_each(drop);
+    /// iter.set_algorithm(Box::new(De::default().algorithm::<MyFunc>()));
+    /// iter.by_ref().for_each(drop);
+    /// let s = iter.into_solver();
+    /// ```
+    pub fn set_algorithm(&mut self, mut algorithm: A) {
+        algorithm.init(&mut self.ctx, &mut self.rng);
+        self.algorithm = algorithm;
+    }
+
+    /// Continue the iteration for the additional generations from the current
+    /// generation, as if the original task had been longer.
+    ///
+    /// The task is replaced, and the other settings of the [`SolverBuilder`]
+    /// are kept, including the termination conditions, the callbacks, the
+    /// restarts, and the cooperative coevolution. So the result can be
+    /// inspected when the task is reached, then the solving is continued with
+    /// the exact state of the method and the random number generator. It has
+    /// no effect if the iteration is terminated by the other conditions or the
+    /// commands. The maximum generation ([`Ctx::progress()`]) is extended if
+    /// it is less than the new end. The [`Solver`] only keeps the results, so
+    /// the continuation is provided by the iterator.
+    ///
+    /// ```
+    /// use metaheuristics_nature::{De, Solver};
+    /// # use metaheuristics_nature::tests::TestObj as MyFunc;
+    ///
+    /// let mut iter = Solver::build(De::default(), MyFunc::new())
+    ///     .seed(0)
+    ///     .task(|ctx| ctx.gen == 20)
+    ///     .iter();
+    /// iter.by_ref().for_each(drop);
+    /// // Inspect the result, then continue
+    /// let best = iter.ctx().best.get_eval();
+    /// iter.continue_for(20);
+    /// iter.by_ref().for_each(drop);
+    /// assert_eq!(iter.ctx().gen, 40);
+    /// let s = iter.into_solver();
+    /// assert!(s.get_best_eval() <= best);
+    /// ```
+    pub fn continue_for(&mut self, extra_gens: u64) {
+        let end = self.ctx.gen + extra_gens;
+        self.ctx.max_gen = self.ctx.max_gen.max(end);
+        self.task = Box::new(move |ctx| ctx.gen >= end);
+        if self.by_task && extra_gens > 0 {
+            self.done = false;
+        }
+    }
+
+    /// Stop the iteration and get the solver of the current status.
+    pub fn into_solver(self) -> Solver<F> {
+        let Self {
+            mut ctx,
+            rng,
+            name,
+            params,
+            restarts,
+            tracked,
+            tracking_stopped,
+            best_history,
+            record_by_evals,
+            diversity_history,
+            #[cfg(feature = "std")]
+            gen_times,
+            ..
+        } = self;
+        ctx.unfreeze();
+        #[cfg(feature = "std")]
+        let eval_log = (ctx.eval_log.take())
+            .map(|log| {
+                log.into_inner()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+            })
+            .unwrap_or_default();
+        let record_by_evals = best_history.is_some().then_some(record_by_evals);
+        Solver {
+            ctx,
+            seed: rng.seed(),
+            rng,
+            name,
+            params,
+            restarts,
+            tracked,
+            tracking_stopped,
+            best_history: best_history.unwrap_or_default(),
+            record_by_evals,
+            diversity_history,
+            #[cfg(feature = "std")]
+            gen_times: gen_times.unwrap_or_default(),
+            #[cfg(feature = "std")]
+            eval_log,
+        }
+    }
+
+    // Process the commands, return false if stopped.
+    #[cfg(feature = "std")]
+    fn commands(&mut self) -> bool {
+        let Some(rx) = &self.commands else {
+            return true;
+        };
+        let mut paused = false;
+        loop {
+            let cmd = if paused {
+                rx.recv().ok()
+            } else {
+                rx.try_recv().ok()
+            };
+            match cmd {
+                Some(SolverCmd::Pause) => paused = true,
+                Some(SolverCmd::Resume) => paused = false,
+                Some(SolverCmd::Stop) => return false,
+                Some(SolverCmd::SetSeed(seed)) => self.rng = Rng::new(SeedOpt::U64(seed)),
+                // Empty or disconnected
+                None => return true,
+            }
+        }
+    }
+
+    // Check the termination conditions before a generation, return false if
+    // the solving is terminated.
+    fn update_clock(&mut self) {
+        if let Some((clock, start)) = &mut self.clock {
+            self.ctx.elapsed = Some(clock.now().saturating_sub(*start));
+        }
+    }
+
+    fn update_seed(&mut self) {
+        if let Some(seed) = self.ctx.take_seed() {
+            self.rng = Rng::new(SeedOpt::U128(seed));
+        }
+    }
+
+    fn check(&mut self) -> bool {
+        self.update_clock();
+        (self.callback)(&self.ctx);
+        if let Some(f) = &mut self.archive_update {
+            f(&self.ctx);
+        }
+        #[cfg(feature = "std")]
+        if !self.commands() {
+            self.done = true;
+            return false;
+        }
+        self.update_seed();
+        // Check all conditions, they may have their own states
+        let ctx = &self.ctx;
+        let stop = self.stop.iter_mut().fold(false, |b, f| f(ctx) | b);
+        if (self.task)(ctx) | stop {
+            self.done = true;
+            self.by_task = !stop;
+            return false;
+        }
+        true
+    }
+
+    // Run a generation, return false if the termination condition is met.
+    pub(crate) fn step(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+        if core::mem::take(&mut self.by_task) {
+            // The conditions have been checked if continued after the task
+            self.update_clock();
+            self.update_seed();
+        } else if !self.check() {
+            return false;
+        }
+        let ctx = &mut self.ctx;
+        let rng = &mut self.rng;
+        #[cfg(feature = "std")]
+        let start = std::time::Instant::now();
+        ctx.prev_best = Some(ctx.best.as_result_fit().clone());
+        ctx.gen += 1;
+        let group = (self.stash.is_some())
+            .then(|| &self.groups[(ctx.gen - 1) as usize % self.groups.len()]);
+        if let (Some(stash), Some(group)) = (&self.stash, group) {
+            ctx.freeze_except(group, stash);
+        }
+        self.algorithm.generation(ctx, rng);
+        if let Some(tol) = self.dedup {
+            ctx.dedup(tol, rng);
+        }
+        if let Some(prob) = self.anti_saturation {
+            ctx.desaturate(prob, rng);
+        }
+        if let Some(xs) = self.immigrant.as_mut().and_then(|f| f()) {
+            ctx.immigrate(xs);
+        }
+        ctx.update_ideal();
+        if let (Some(stash), Some(group)) = (&mut self.stash, group) {
+            for (stash, xs) in zip(stash, &ctx.pool) {
+                group.iter().for_each(|&s| stash[s] = xs[s]);
+            }
+        }
+        if let Some(rate) = self.shrink {
+            ctx.shrink(rate);
+        }
+        if let Some(i) = self.track {
+            if let Some(xs) = ctx.pool.get(i) {
+                self.tracked.push(xs.clone());
+            } else {
+                self.tracking_stopped = Some(ctx.gen);
+                self.track = None;
+            }
+        }
+        if let (Some(patience), Some(last_best)) = (self.restart, &mut self.last_best) {
+            let best = ctx.best.as_result_fit();
+            if ctx.cmp_fitness(best, last_best).is_lt() {
+                *last_best = best.clone();
+                self.stall = 0;
+            } else {
+                self.stall += 1;
+            }
+            if self.stall >= patience && self.restarts < self.max_restarts {
+                self.restarts += 1;
+                self.stall = 0;
+                ctx.reinit(rng);
+                self.algorithm.init(ctx, rng);
+                if let Some(stash) = &mut self.stash {
+                    stash.clone_from(&ctx.pool);
+                }
+            }
+        }
+        ctx.repair_all();
+        if let Some(history) = &mut self.best_history {
+            let (xs, ys) = ctx.best.as_result();
+            if (history.last()).is_none_or(|(.., last)| ctx.cmp_fitness(ys, last).is_lt()) {
+                history.push(([ctx.gen, ctx.eval_count()], xs.to_vec(), ys.clone()));
+            }
+        }
+        if self
+            .diversity_stride
+            .is_some_and(|stride| ctx.gen.is_multiple_of(stride))
+        {
+            self.diversity_history.push((ctx.gen, ctx.diversity()));
+        }
+        #[cfg(feature = "std")]
+        if let Some(times) = &mut self.gen_times {
+            times.push(start.elapsed());
+        }
+        true
+    }
+}
+
+impl<A: Algorithm<F>, F: ObjFunc> Iterator for SolverIter<'_, A, F> {
+    type Item = GenerationSnapshot<<F::Ys as Fitness>::Eval>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.step() {
+            return None;
+        }
+        Some(GenerationSnapshot {
+            gen: self.ctx.gen,
+            best: self.ctx.best.get_eval(),
+            diversity: self.ctx.diversity(),
+        })
+    }
+}
+
+impl<A: Algorithm<F>, F: ObjFunc> core::iter::FusedIterator for SolverIter<'_, A, F> {}
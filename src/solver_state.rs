@@ -0,0 +1,87 @@
+//! The snapshot of the solver for checkpointing, see [`SolverState`].
+use crate::prelude::*;
+use alloc::vec::Vec;
+
+/// A snapshot of the solving progress, which can be resumed by
+/// [`SolverBuilder::resume_from()`].
+///
+/// It can be serialized and deserialized if the `serde` feature is enabled
+/// (the fitness values should support it as well), so the long optimization
+/// can be saved to a file and resumed after the preemption.
+///
+/// The snapshot includes:
+/// + The generation and the number of the evaluations.
+/// + The pool and its fitness values.
+/// + The best element(s), the whole front for the multi-objective.
+/// + The random number generator at its exact position.
+///
+/// The following are skipped:
+/// + The objective function, which is supplied again by the user.
+/// + The method and its internal states, such as the velocities of the
+///   particles, so only the stateless methods can be resumed, see
+///   [`Algorithm::is_stateless()`].
+/// + The settings of the [`SolverBuilder`], such as the task, the callback,
+///   and the restart counter.
+/// + The records, such as the best history and the evaluation log.
+///
+/// So the resumed solving is identical to the uninterrupted one.
+///
+/// ```
+/// use metaheuristics_nature::{De, Solver};
+/// # use metaheuristics_nature::tests::TestObj as MyFunc;
+///
+/// let s = Solver::build(De::default(), MyFunc::new())
+///     .seed(0)
+///     .task(|ctx| ctx.gen == 20)
+///     .solve();
+/// // Save it with `serde` here
+/// let state = s.save_state();
+/// let s = Solver::build(De::default(), MyFunc::new())
+///     .resume_from(state)
+///     .task(|ctx| ctx.gen == 40)
+///     .solve();
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolverState<Y> {
+    pub(crate) gen: u64,
+    pub(crate) evals: u64,
+    pub(crate) pool: Vec<Vec<f64>>,
+    pub(crate) pool_y: Vec<Y>,
+    pub(crate) best: Vec<(Vec<f64>, Y)>,
+    pub(crate) rng: Rng,
+}
+
+impl<Y> SolverState<Y> {
+    /// The saved generation.
+    pub fn gen(&self) -> u64 {
+        self.gen
+    }
+
+    /// The saved number of the evaluations.
+    pub fn eval_count(&self) -> u64 {
+        self.evals
+    }
+
+    /// The saved pool.
+    pub fn pool(&self) -> &[Vec<f64>] {
+        &self.pool
+    }
+}
+
+impl<F: ObjFunc> Solver<F> {
+    /// Save the progress as a snapshot, see [`SolverState`].
+    pub fn save_state(&self) -> SolverState<F::Ys> {
+        let best = (self.ctx.best.elements().into_iter())
+            .map(|(xs, ys)| (xs.to_vec(), ys.clone()))
+            .collect();
+        SolverState {
+            gen: self.ctx.gen,
+            evals: self.ctx.eval_count(),
+            pool: self.ctx.pool.clone(),
+            pool_y: self.ctx.pool_y.clone(),
+            best,
+            rng: self.rng.clone(),
+        }
+    }
+}
@@ -4,6 +4,7 @@ use crate::prelude::*;
 const OFFSET: f64 = 7.;
 
 /// An example for doctest.
+#[derive(Default)]
 pub struct TestObj;
 
 impl TestObj {
@@ -29,6 +30,7 @@ impl ObjFunc for TestObj {
 }
 
 /// A multi-objective example for doctest.
+#[derive(Default)]
 pub struct TestMO;
 
 impl TestMO {
@@ -61,6 +63,10 @@ impl Fitness for TestMOFit {
     fn eval(&self) -> Self::Eval {
         self.cost.max(self.weight)
     }
+
+    fn objectives(&self) -> alloc::vec::Vec<f64> {
+        alloc::vec![self.cost, self.weight]
+    }
 }
 
 impl ObjFunc for TestMO {
@@ -122,6 +128,134 @@ fn tlbo() {
     assert_xs!(test::<Tlbo>());
 }
 
+#[test]
+fn ssa() {
+    // The leader moves around the best of the previous generation
+    let mut food = alloc::vec::Vec::<f64>::new();
+    let mut max_dev = 0f64;
+    let s = Solver::build(Ssa, TestObj)
+        .seed(0)
+        .max_gen(500)
+        .callback(|ctx| {
+            if !food.is_empty() {
+                let c1 = Ssa::c1(ctx.progress());
+                for (x, f) in ctx.pool[0].iter().zip(&food) {
+                    let dev = (x - f).abs();
+                    assert!(dev <= c1 * 50. + 1e-12, "{dev} > {c1} * 50");
+                    max_dev = max_dev.max(dev / c1);
+                }
+            }
+            food = ctx.best.get_xs().to_vec();
+        })
+        .solve();
+    assert!(max_dev > 1.);
+    assert!(s.get_best_eval() - OFFSET < 1e-8, "{}", s.get_best_eval());
+    for x in s.as_best_xs() {
+        assert!(x.abs() < 1e-4, "x: {x}");
+    }
+}
+
+#[test]
+fn mfo() {
+    // Linearly decreased from the population number to one
+    assert_eq!(Mfo::flame_num(50, 0.), 50);
+    assert_eq!(Mfo::flame_num(50, 0.5), 26);
+    assert_eq!(Mfo::flame_num(50, 1.), 1);
+    let flames = (0..=500)
+        .map(|gen| Mfo::flame_num(50, gen as f64 / 500.))
+        .collect::<alloc::vec::Vec<_>>();
+    assert!(flames.windows(2).all(|w| w[0] >= w[1] && w[0] - w[1] <= 1));
+    let s = Solver::build(Mfo::default(), TestObj)
+        .seed(0)
+        .max_gen(500)
+        .solve();
+    assert!(s.get_best_eval() - OFFSET < 1e-8, "{}", s.get_best_eval());
+    for x in s.as_best_xs() {
+        assert!(x.abs() < 1e-4, "x: {x}");
+    }
+}
+
+#[test]
+fn csa() {
+    assert_xs!(test::<Csa>());
+}
+
+#[test]
+fn sa() {
+    assert_xs!(test::<Sa>());
+    // The temperature is decreased from the initial temperature
+    for schedule in [
+        CoolingSchedule::Linear,
+        CoolingSchedule::Geometric,
+        CoolingSchedule::Exponential,
+        CoolingSchedule::Logarithmic,
+    ] {
+        let sa = Sa::new().init_temp(2.).cooling_rate(0.1).schedule(schedule);
+        assert_eq!(sa.temperature(0), 2.);
+        assert!(sa.temperature(5) < sa.temperature(1));
+        assert!(sa.temperature(5) >= 0.);
+    }
+    assert_eq!(
+        Sa::new()
+            .schedule(CoolingSchedule::Linear)
+            .cooling_rate(0.1)
+            .temperature(20),
+        0.
+    );
+}
+
+#[test]
+fn cma_es() {
+    assert_xs!(test::<CmaEs>());
+    let s = Solver::build(CmaEs::new().sigma0(0.1).mu(3), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 100)
+        .solve();
+    assert_eq!(s.algorithm_params(), [("sigma0", 0.1), ("mu", 3.)]);
+    assert!(s.get_best_eval() - OFFSET < 1e-10);
+}
+
+#[test]
+fn abc() {
+    assert_xs!(test::<Abc>());
+}
+
+#[test]
+fn pattern() {
+    assert_xs!(test::<Pattern>());
+}
+
+#[test]
+fn integer_fitness() {
+    // Number of the violations, infeasible if the first variable is negative
+    let f = Fx::new(&[[-5., 5.]; 4], |xs: &[f64; 4]| {
+        if xs[0] < 0. {
+            i64::MAX
+        } else {
+            xs.iter().map(|x| (x.abs() * 10.) as i64).sum()
+        }
+    });
+    let s = Solver::build(De::default(), f)
+        .seed(0)
+        .task(|ctx| ctx.best.get_eval() == 0)
+        .solve();
+    assert_eq!(s.get_best_eval(), 0);
+    assert!(s.as_best_xs().iter().all(|x| (0. ..0.1).contains(&x.abs())));
+    // The float utilities for the 32-bit integers
+    let f = Fx::new(&[[-5., 5.]; 4], |xs: &[f64; 4]| {
+        xs.iter().map(|x| (x.abs() * 10.) as u32).sum::<u32>()
+    });
+    let s = Solver::build(Rga::default(), f)
+        .seed(0)
+        .pop_num(20)
+        .task(|ctx| ctx.gen == 200)
+        .stop_on_slope(10, 0.)
+        .record_best(true)
+        .solve();
+    assert_eq!(s.get_best_eval(), 0);
+    assert_eq!(s.best_history().last().map(|(_, y)| *y), Some(0.));
+}
+
 #[cfg(feature = "rayon")]
 #[test]
 fn test_rng() {
@@ -142,3 +276,2880 @@ fn test_rng() {
         assert_eq!(non_parallel, parallel);
     }
 }
+
+#[test]
+fn stop_on_slope() {
+    // Slowly improving objective
+    let bound = [[0., 10.]; 2];
+    let f = Fx::new(&bound, |&[a, b]| (a + b).sqrt());
+    let mut report = alloc::vec::Vec::new();
+    let s = Solver::build(De::default(), f)
+        .seed(0)
+        .task(|ctx| ctx.gen == 2000)
+        .stop_on_slope(20, 1e-6)
+        .callback(|ctx| report.push(ctx.best.get_eval()))
+        .solve();
+    let gen = report.len() - 1;
+    assert!((20..2000).contains(&gen), "gen: {gen}");
+    // The improvement rate is flattened, not the initial fast convergence
+    assert!(report[gen - 19] - report[gen] < 1e-4);
+    assert!(report[0] - report[19] > 1e-4);
+    assert_eq!(s.get_best_eval(), report[gen]);
+}
+
+#[cfg(any(feature = "libm", not(feature = "std")))]
+#[test]
+fn deterministic_math() {
+    // The golden values are the same in the `std` and `no_std` builds
+    fn run<S: AlgCfg>(cfg: S) -> u64 {
+        let s = Solver::build(cfg, TestObj)
+            .seed(0)
+            .task(|ctx| ctx.gen == 20)
+            .solve();
+        s.get_best_eval().to_bits()
+    }
+    assert_eq!(run(Fa::default()), 4624936887578303370);
+    assert_eq!(run(Rga::default()), 4619568839964831308);
+}
+
+#[test]
+fn max_gen() {
+    let mut report = alloc::vec::Vec::new();
+    let _ = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .max_gen(300)
+        .callback(|ctx| report.push((ctx.gen, ctx.progress())))
+        .solve();
+    assert_eq!(report.last(), Some(&(300, 1.)));
+    assert!(report.windows(2).all(|w| w[0].1 < w[1].1));
+}
+
+#[test]
+fn build_error() {
+    let f = Fx::new(&[], |&[]: &[f64; 0]| 0.);
+    let e = Solver::build(De::default(), f).try_solve().err();
+    assert_eq!(e, Some(BuildError::ZeroDim));
+    let pool = Pool::Ready { pool: alloc::vec![], pool_y: alloc::vec![] };
+    let e = Solver::build(De::default(), TestObj)
+        .init_pool(pool)
+        .try_solve()
+        .err();
+    assert_eq!(e, Some(BuildError::EmptyPool));
+    let e = Solver::build(De::default(), TestObj)
+        .pop_num(0)
+        .try_solve()
+        .err();
+    assert_eq!(e, Some(BuildError::EmptyPool));
+    let pool = Pool::UniformBy {
+        filter: alloc::boxed::Box::new(|_| false),
+        max_tries: 1000,
+    };
+    let e = Solver::build(De::default(), TestObj)
+        .init_pool(pool)
+        .try_solve()
+        .err();
+    assert_eq!(e, Some(BuildError::InfeasibleInit));
+}
+
+#[test]
+fn cooperative() {
+    // Partially-separable objective
+    struct Pairs;
+    impl Bounded for Pairs {
+        fn bound(&self) -> &[[f64; 2]] {
+            &[[-10., 10.]; 100]
+        }
+    }
+    impl ObjFunc for Pairs {
+        type Ys = f64;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            xs.chunks(2)
+                .map(|x| (x[0] + 2. * x[1]).powi(2) + (x[0] - x[1] - 1.).powi(2))
+                .sum()
+        }
+    }
+    let groups = differential_grouping(&Pairs, 1e-6);
+    assert_eq!(groups.len(), 50);
+    // The same evaluation budget, the binomial crossover is efficient for
+    // cooperative coevolution
+    let run = |gen, groups| {
+        Solver::build(De::new().strategy(Strategy::C2F1), Pairs)
+            .seed(0)
+            .pop_num(40)
+            .task(move |ctx| ctx.gen == gen)
+            .cooperative(groups)
+            .solve()
+            .get_best_eval()
+    };
+    let coop = run(500, groups);
+    let mono = run(1000, alloc::vec![]);
+    assert!(coop < mono, "{coop} >= {mono}");
+}
+
+#[test]
+fn algorithm_report() {
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 1)
+        .solve();
+    assert_eq!(s.algorithm_name(), "DE");
+    assert!(s.algorithm_params().contains(&("f", De::new().f)));
+    assert!(s.algorithm_params().contains(&("cross", De::new().cross)));
+    let s = Solver::build_boxed(Pso::default(), TestObj)
+        .task(|ctx| ctx.gen == 1)
+        .solve();
+    assert_eq!(s.algorithm_name(), "PSO");
+}
+
+#[test]
+fn ensemble() {
+    let method = Ensemble::new()
+        .add(De::default(), 0.5)
+        .add(Pso::default(), 0.5);
+    let pool = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 0)
+        .solve()
+        .pool()
+        .to_vec();
+    let s = Solver::build_algorithm(method, TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 1)
+        .init_pool(Pool::Ready {
+            pool_y: pool.iter().map(|xs| TestObj.fitness(xs)).collect(),
+            pool: pool.clone(),
+        })
+        .solve();
+    // Both partitions are updated
+    use core::iter::zip;
+    let (de, pso) = s.pool().split_at(200);
+    assert!(zip(de, &pool[..200]).any(|(a, b)| a != b));
+    assert!(zip(pso, &pool[200..]).all(|(a, b)| a != b));
+    // Convergence
+    let method = Ensemble::new()
+        .add(De::default(), 0.5)
+        .add(Pso::default(), 0.5);
+    let s = Solver::build_algorithm(method, TestObj)
+        .seed(0)
+        .pop_num(400)
+        .task(|ctx| ctx.gen == 200)
+        .solve();
+    assert!(s.get_best_eval() - OFFSET < 1e-10);
+    assert_eq!(s.algorithm_params(), [("DE", 0.5), ("PSO", 0.5)]);
+    // The minimum population of each method
+    let method = Ensemble::new()
+        .add(De::default(), 0.01)
+        .add(Pso::default(), 0.99);
+    let mut iter = Solver::build_algorithm(method, TestObj)
+        .seed(0)
+        .pop_num(50)
+        .task(|ctx| ctx.gen == 20)
+        .iter();
+    assert_eq!(iter.algorithm.sizes(), [De::min_pop_num(), 45]);
+    iter.by_ref().for_each(drop);
+}
+
+#[test]
+fn max_restarts() {
+    let s = Solver::build(Rga::default(), TestObj)
+        .seed(0)
+        .pop_num(50)
+        .task(|ctx| ctx.gen == 100)
+        .restart(1)
+        .max_restarts(3)
+        .solve();
+    assert_eq!(s.restarts(), 3);
+    let s = Solver::build(Rga::default(), TestObj)
+        .seed(0)
+        .pop_num(50)
+        .task(|ctx| ctx.gen == 100)
+        .restart(1)
+        .solve();
+    assert!(s.restarts() > 3);
+}
+
+#[test]
+fn bound_cache() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    #[derive(Default)]
+    struct Counted(AtomicUsize);
+    impl Bounded for Counted {
+        fn bound(&self) -> &[[f64; 2]] {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            TestObj.bound()
+        }
+    }
+    impl ObjFunc for Counted {
+        type Ys = f64;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            TestObj.fitness(xs).ys()
+        }
+    }
+    let run = |gen| {
+        let s = Solver::build(Pso::default(), Counted::default())
+            .seed(0)
+            .task(move |ctx| ctx.gen == gen)
+            .solve();
+        s.func().0.load(Ordering::Relaxed)
+    };
+    assert_eq!(run(10), 1);
+    assert_eq!(run(100), 1);
+}
+
+#[test]
+fn maximize() {
+    // Concave function, the maximum is 3 at (1, 1)
+    let f = Fx::new(&[[-10., 10.]; 2], |&[x, y]: &[f64; 2]| {
+        Maximize(3. - (x - 1.).powi(2) - (y - 1.).powi(2))
+    });
+    let s = Solver::build(De::default(), f)
+        .seed(0)
+        .task(|ctx| ctx.gen == 100)
+        .solve();
+    let Maximize(y) = s.get_best_eval();
+    assert!((3. - y).abs() < 1e-10, "{y}");
+    assert!(s.as_best_xs().iter().all(|x| (x - 1.).abs() < 1e-4));
+    assert_eq!(f64::from(Maximize(y)), -y);
+    // Same function without the wrapper
+    let f = Fx::new(&[[-10., 10.]; 2], |&[x, y]: &[f64; 2]| {
+        3. - (x - 1.).powi(2) - (y - 1.).powi(2)
+    });
+    let s = Solver::build(De::default(), f)
+        .seed(0)
+        .task(|ctx| ctx.gen == 100)
+        .maximize()
+        .solve();
+    let y = s.get_best_eval();
+    assert!((3. - y).abs() < 1e-10, "{y}");
+    assert!(s.as_best_xs().iter().all(|x| (x - 1.).abs() < 1e-4));
+}
+
+#[test]
+fn shrink_bounds() {
+    let mut widths = alloc::vec::Vec::new();
+    let shrunk = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 100)
+        .shrink_bounds(0.05)
+        .callback(|ctx| widths.push(ctx.bound_width(0)))
+        .solve()
+        .get_best_eval();
+    assert!(widths.windows(2).all(|w| w[0] >= w[1]));
+    assert!(widths.last().unwrap() < &(widths[0] * 0.1));
+    assert!(widths.last().unwrap() >= &(widths[0] * 1e-10));
+    let plain = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 100)
+        .solve()
+        .get_best_eval();
+    assert!(shrunk <= plain, "{shrunk} > {plain}");
+}
+
+#[test]
+fn sensitivity() {
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 50)
+        .solve();
+    let sens = s.sensitivity(1e-2);
+    assert_eq!(sens.len(), 4);
+    assert!([0, 2, 3].into_iter().all(|s| sens[1] > sens[s]), "{sens:?}");
+}
+
+#[test]
+fn de_pbest() {
+    // The rounded pbest number is zero for the tiny populations
+    for (pop_num, strategy) in [(3, Strategy::C1F6), (4, Strategy::C2F6)] {
+        let init = Solver::build(De::default(), TestObj)
+            .seed(0)
+            .pop_num(pop_num)
+            .task(|ctx| ctx.gen == 0)
+            .solve()
+            .get_best_eval();
+        let s = Solver::build(De::new().strategy(strategy).p_best_rate(0.01), TestObj)
+            .seed(0)
+            .pop_num(pop_num)
+            .task(|ctx| ctx.gen == 200)
+            .solve();
+        assert!(s.get_best_eval() < init);
+    }
+}
+
+#[test]
+#[should_panic(expected = "The pbest rate should be in (0, 1]")]
+fn de_pbest_invalid() {
+    let _ = De::new().p_best_rate(0.);
+}
+
+#[test]
+fn track_individual() {
+    let s = Solver::build(Pso::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 30)
+        .track_individual(3)
+        .solve();
+    assert_eq!(s.tracked().len(), 30);
+    assert_eq!(s.tracked().last().unwrap(), &s.pool()[3]);
+    assert!(s.tracked().windows(2).any(|w| w[0] != w[1]));
+    assert_eq!(s.tracking_stopped(), None);
+    // Out of the population
+    let s = Solver::build(Pso::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 30)
+        .track_individual(usize::MAX)
+        .solve();
+    assert!(s.tracked().is_empty());
+    assert_eq!(s.tracking_stopped(), Some(1));
+}
+
+#[test]
+fn custom_compare() {
+    // Inverted comparator, optimize toward the worst
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 100)
+        .compare(|a, b| b.ys().total_cmp(&a.ys()))
+        .solve();
+    let y = s.get_best_eval();
+    // The maximum value in the bounds
+    let max = OFFSET + 11. * 2500.;
+    assert!((max - y) / max < 1e-3, "{y}");
+}
+
+#[test]
+fn send() {
+    // Without the `rayon` feature as well
+    fn is_send<T: Send>() {}
+    is_send::<Ctx<TestObj>>();
+    is_send::<Solver<TestObj>>();
+}
+
+#[test]
+fn full_archive() {
+    let s = Solver::build(Rga::default(), TestMO)
+        .seed(0)
+        .task(|ctx| ctx.gen == 30)
+        .pareto_limit(10)
+        .full_archive(true)
+        .solve();
+    let front = s.as_best_set().pareto_from_product();
+    let full = s.full_front().unwrap().pareto_from_product();
+    assert_eq!(front.len(), 10);
+    assert!(full.len() > front.len());
+    // Non-dominated
+    for (i, a) in full.iter().enumerate() {
+        for b in &full[i + 1..] {
+            assert!(!a.is_dominated(b) && !b.is_dominated(a));
+        }
+    }
+    // Superset of the truncated front
+    for a in &front {
+        assert!(full.iter().any(|b| b.is_dominated(a) && a.is_dominated(b)));
+    }
+    // Disabled by default
+    let s = Solver::build(Rga::default(), TestMO)
+        .seed(0)
+        .task(|ctx| ctx.gen == 1)
+        .solve();
+    assert!(s.full_front().is_none());
+}
+
+#[test]
+fn nsga2() {
+    let s = Solver::build(NsgaII::default(), TestMO)
+        .seed(0)
+        .task(|ctx| ctx.gen == 50)
+        .pareto_limit(10)
+        .solve();
+    let front = s.as_best_set().pareto_from_product();
+    assert!(!front.is_empty() && front.len() <= 10);
+    assert!(front.iter().any(|ys| ys.cost < 1e-5 && ys.weight < 1e-5));
+    // A convex front `weight = 1 - sqrt(cost)`, spread by the crowding distance
+    let f = Fx::new(&[[0., 1.]; 2], |&[a, b]| {
+        let g = 1. + 9. * b;
+        TestMOFit { cost: a, weight: g * (1. - (a / g).sqrt()) }
+    });
+    let s = Solver::build(NsgaII::default(), f)
+        .seed(0)
+        .pop_num(40)
+        .task(|ctx| ctx.gen == 200)
+        .pareto_limit(20)
+        .truncation(crate::pareto::CrowdingDistance)
+        .solve();
+    let front = s.as_best_set().as_pareto();
+    assert_eq!(front.len(), 20);
+    assert!(front
+        .iter()
+        .all(|ys| ys.weight - (1. - ys.cost.sqrt()) < 1e-2));
+    assert!(front.iter().any(|ys| ys.cost < 0.05));
+    assert!(front.iter().any(|ys| ys.cost > 0.95));
+}
+
+#[test]
+fn fa_scaled() {
+    use core::iter::zip;
+    // Wide bounds vanish the attraction without the normalization
+    fn solve(scale: f64) -> (alloc::vec::Vec<f64>, f64) {
+        let bound = [[-scale, scale]; 4];
+        let f = Fx::new(&bound, |xs: &[f64; 4]| {
+            xs.iter().map(|x| x * x).sum::<f64>()
+        });
+        let s = Solver::build(Fa::default(), f)
+            .seed(0)
+            .task(|ctx| ctx.gen == 200)
+            .solve();
+        (s.as_best_xs().to_vec(), s.get_best_eval())
+    }
+    let (unit, _) = solve(1.);
+    // A power of 2 keeps the scaling exact
+    let scale = (1 << 20) as f64;
+    let (wide, y) = solve(scale);
+    for (a, b) in zip(unit, wide) {
+        assert_eq!(a * scale, b);
+    }
+    let y = y / (scale * scale);
+    assert!(y < 1e-10, "{y}");
+    // The unnormalized baseline is equal to the gamma scaled by the squared
+    // diagonal, which stalls the attraction on the multimodal function
+    let rastrigin = |fa: Fa| {
+        use core::f64::consts::TAU;
+        let bound = [[-scale, scale]; 4];
+        (0..5)
+            .map(|seed| {
+                let f = Fx::new(&bound, |xs: &[f64; 4]| {
+                    xs.iter()
+                        .map(|x| 5.12 * x / scale - 2.)
+                        .map(|x| x * x - 10. * (TAU * x).cos() + 10.)
+                        .sum::<f64>()
+                });
+                Solver::build(fa.clone(), f)
+                    .seed(seed)
+                    .task(|ctx| ctx.gen == 100)
+                    .solve()
+                    .get_best_eval()
+            })
+            .sum::<f64>()
+    };
+    let y = rastrigin(Fa::default());
+    let baseline = rastrigin(Fa::default().gamma(0.01 * 16. * scale * scale));
+    assert!(y * 10. < baseline, "{y} {baseline}");
+}
+
+#[test]
+fn best_metrics() {
+    struct Penalty;
+    impl Bounded for Penalty {
+        fn bound(&self) -> &[[f64; 2]] {
+            TestObj.bound()
+        }
+    }
+    impl ObjFunc for Penalty {
+        type Ys = f64;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            let m = self.metrics(xs);
+            m.cost + m.penalty
+        }
+    }
+    struct Metrics {
+        cost: f64,
+        penalty: f64,
+    }
+    impl ObjMetrics for Penalty {
+        type Metrics = Metrics;
+        fn metrics(&self, xs: &[f64]) -> Self::Metrics {
+            let cost = TestObj.fitness(xs).ys();
+            let penalty = 100. * (xs[0] - 1.).max(0.);
+            Metrics { cost, penalty }
+        }
+    }
+    let s = Solver::build(De::default(), Penalty)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .solve();
+    let m = s.best_metrics();
+    assert_eq!(m.cost + m.penalty, s.get_best_eval());
+    assert_eq!(m.cost, TestObj.fitness(s.as_best_xs()).ys());
+}
+
+#[test]
+fn solver_iter() {
+    let mut report = alloc::vec::Vec::new();
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .callback(|ctx| report.push((ctx.gen, ctx.best.get_eval(), ctx.diversity())))
+        .solve();
+    let mut iter = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .iter();
+    let snapshots = iter.by_ref().collect::<alloc::vec::Vec<_>>();
+    assert_eq!(snapshots.len(), 20);
+    for (snapshot, (gen, best, diversity)) in snapshots.iter().zip(&report[1..]) {
+        assert_eq!(snapshot.gen, *gen);
+        assert_eq!(snapshot.best, *best);
+        assert_eq!(snapshot.diversity, *diversity);
+    }
+    assert!(iter.next().is_none());
+    assert_eq!(iter.into_solver().as_best_xs(), s.as_best_xs());
+}
+
+#[test]
+fn front_results() {
+    let s = Solver::build(Rga::default(), TestMO)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .pareto_limit(10)
+        .solve();
+    assert_eq!(s.front_results().len(), s.as_best_set().len());
+}
+
+#[test]
+fn pso_init_velocity() {
+    // All particles stall at the optimum without the initial velocity
+    let pool = |func: &TestObj| {
+        let pool = alloc::vec![alloc::vec![0.; 4]; 10];
+        let pool_y = pool.iter().map(|xs| func.fitness(xs)).collect();
+        Pool::Ready { pool, pool_y }
+    };
+    for (init, moved) in [(InitVel::Zero, false), (InitVel::Uniform(0.1), true)] {
+        let s = Solver::build(Pso::default().init_velocity(init), TestObj)
+            .seed(0)
+            .task(|ctx| ctx.gen == 1)
+            .init_pool(pool(&TestObj))
+            .solve();
+        assert_eq!(s.pool().iter().any(|xs| xs != &[0.; 4]), moved);
+    }
+}
+
+#[test]
+fn rng_golden() {
+    // Pin the draw sequence of the current version
+    const GOLDEN: [f64; 10] = [
+        0.7090754154265617,
+        0.46592172228961015,
+        0.6991432426747317,
+        0.060171165634171686,
+        0.8791107179586186,
+        0.5495312687894465,
+        0.8289844760239993,
+        0.935426502913129,
+        0.8037816422279636,
+        0.1542912742358562,
+    ];
+    for seed in [SeedOpt::U64(0), SeedOpt::Versioned(0, RNG_VERSION)] {
+        let mut rng = Rng::new(seed);
+        assert_eq!(GOLDEN.map(|_| rng.rand()), GOLDEN);
+    }
+    let e = Solver::build(De::default(), TestObj)
+        .seed(SeedOpt::Versioned(0, RNG_VERSION + 1))
+        .try_solve()
+        .err();
+    assert_eq!(e, Some(BuildError::UnsupportedRngVersion));
+}
+
+#[test]
+fn evals_per_gen() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    fn count<A: AlgCfg>(cfg: A) -> (usize, usize) {
+        let count = AtomicUsize::new(0);
+        let f = Fx::new(TestObj.bound().try_into().unwrap(), |xs: &[f64; 4]| {
+            count.fetch_add(1, Ordering::Relaxed);
+            TestObj.fitness(xs).ys()
+        });
+        let mut report = alloc::vec::Vec::new();
+        let builder = Solver::build(cfg, f)
+            .seed(0)
+            .task(|ctx| ctx.gen == 1)
+            .callback(|_| report.push(count.load(Ordering::Relaxed)));
+        let evals = builder.evals_per_gen();
+        let _ = builder.solve();
+        (evals, report[1] - report[0])
+    }
+    let (evals, actual) = count(De::default());
+    assert_eq!(evals, actual);
+    let (evals, actual) = count(Pso::default());
+    assert_eq!(evals, actual);
+}
+
+#[test]
+fn gaussian_pool_auto() {
+    let bound = [[0., 10.], [100., 300.]];
+    let f = Fx::new(&bound, |&[a, b]: &[f64; 2]| a + b);
+    let s = Solver::build(Rga::default(), f)
+        .seed(0)
+        .pop_num(10000)
+        .task(|ctx| ctx.gen == 0)
+        .init_pool(Pool::Func(super::gaussian_pool_auto(0.1)))
+        .solve();
+    let n = s.pool().len() as f64;
+    for (s_, [min, max]) in bound.into_iter().enumerate() {
+        let mean = s.pool().iter().map(|xs| xs[s_]).sum::<f64>() / n;
+        let var = s
+            .pool()
+            .iter()
+            .map(|xs| (xs[s_] - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        let width = max - min;
+        assert!((mean - 0.5 * (min + max)).abs() < 0.01 * width, "{mean}");
+        assert!((var.sqrt() - 0.1 * width).abs() < 0.01 * width, "{var}");
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn commands() {
+    use std::sync::mpsc::channel;
+    let (tx, rx) = channel();
+    let mut report = alloc::vec::Vec::new();
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 100)
+        .callback(|ctx| {
+            report.push(ctx.gen);
+            if ctx.gen == 5 {
+                tx.send(SolverCmd::Stop).unwrap();
+            }
+        })
+        .commands(rx)
+        .solve();
+    assert_eq!(report.last(), Some(&5));
+    assert!(s.get_best_eval() >= OFFSET);
+    // Resume from another thread, blocking until then
+    let (tx, rx) = channel();
+    tx.send(SolverCmd::Pause).unwrap();
+    let handle = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        tx.send(SolverCmd::SetSeed(1)).unwrap();
+        tx.send(SolverCmd::Resume).unwrap();
+    });
+    let mut report = alloc::vec::Vec::new();
+    let _ = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 10)
+        .callback(|ctx| report.push(ctx.gen))
+        .commands(rx)
+        .solve();
+    handle.join().unwrap();
+    assert_eq!(report.len(), 11);
+}
+
+#[test]
+fn replacement() {
+    let run = |replacement| {
+        let snapshot = Solver::build(De::default(), TestObj)
+            .seed(0)
+            .task(|ctx| ctx.gen == 30)
+            .replacement(replacement)
+            .iter()
+            .last()
+            .unwrap();
+        (snapshot.best, snapshot.diversity)
+    };
+    let (parent_best, parent_div) = run(Replacement::Parent);
+    let (worst_best, worst_div) = run(Replacement::Worst);
+    // Faster convergence, lower diversity
+    assert!(worst_best < parent_best);
+    assert!(worst_div < parent_div);
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn eval_chunk() {
+    let run = |chunk| {
+        Solver::build(De::default(), TestObj)
+            .seed(0)
+            .task(|ctx| ctx.gen == 20)
+            .eval_chunk(chunk)
+            .solve()
+    };
+    let s1 = run(1);
+    let s2 = run(64);
+    assert_eq!(s1.as_best_xs(), s2.as_best_xs());
+    assert_eq!(s1.as_best_fit().eval(), s2.as_best_fit().eval());
+}
+
+#[test]
+fn on_archive_update() {
+    let front_of = |front: &[WithProduct<TestMOFit, ()>]| {
+        front
+            .iter()
+            .map(|ys| ys.eval())
+            .collect::<alloc::vec::Vec<_>>()
+    };
+    let mut updates = alloc::vec::Vec::new();
+    let mut changes = alloc::vec::Vec::new();
+    let mut last = alloc::vec::Vec::new();
+    let mut gens = 0;
+    let _ = Solver::build(Rga::default(), TestMO)
+        .seed(0)
+        .task(|ctx| ctx.gen == 30)
+        .callback(|ctx| {
+            gens += 1;
+            let front = front_of(ctx.best.as_pareto());
+            if front != last {
+                changes.push(front.clone());
+                last = front;
+            }
+        })
+        .on_archive_update(|front| updates.push(front_of(front)))
+        .solve();
+    // Only called on the genuine changes
+    assert!(updates.len() < gens);
+    assert_eq!(updates, changes);
+}
+
+#[test]
+fn uniform_by_reproducible() {
+    let init = || {
+        let pool = Pool::UniformBy {
+            filter: alloc::boxed::Box::new(|xs| xs[0] > 0.),
+            max_tries: 10000,
+        };
+        let iter = Solver::build(Rga::default(), TestObj)
+            .seed(0)
+            .init_pool(pool)
+            .iter();
+        (iter.ctx().pool.iter().flatten())
+            .map(|x| x.to_bits())
+            .collect::<alloc::vec::Vec<_>>()
+    };
+    let pool = init();
+    assert!(!pool.is_empty());
+    assert_eq!(pool, init());
+}
+
+#[test]
+fn fx_result() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    let errors = AtomicUsize::new(0);
+    let bound = [[-10., 10.]; 2];
+    // Fails on the half domain containing the unconstrained minimum
+    let f = FxResult::new(
+        &bound,
+        |&[x, y]| {
+            if x < 1. {
+                Err(())
+            } else {
+                Ok(x * x + y * y)
+            }
+        },
+    )
+    .on_error(|_| {
+        errors.fetch_add(1, Ordering::Relaxed);
+    });
+    let s = Solver::build(De::default(), f)
+        .seed(0)
+        .task(|ctx| ctx.gen == 100)
+        .solve();
+    assert!(errors.load(Ordering::Relaxed) > 0);
+    assert!(s.as_best_xs()[0] >= 1.);
+    assert!(s.get_best_eval() - 1. < 1e-6, "{}", s.get_best_eval());
+    // The failed points would be the best in maximization
+    let f = FxResult::new(&bound, |&[x, y]| if x < 0. { Err(()) } else { Ok(x + y) });
+    let e = Solver::build(De::default(), f)
+        .seed(0)
+        .task(|ctx| ctx.gen == 10)
+        .maximize()
+        .try_solve()
+        .err();
+    assert_eq!(e, Some(BuildError::MinimizeOnly));
+}
+
+#[test]
+fn pso_neighborhood_schedule() {
+    use core::f64::consts::TAU;
+    let schedule = Neighborhood::Growing(1);
+    let radius = [0., 0.5, 1.].map(|p| schedule.radius(40, p).unwrap());
+    assert_eq!(radius, [1, 11, 20]);
+    // Rastrigin function
+    let bound = [[-5.12, 5.12]; 6];
+    let rastrigin = |xs: &[f64; 6]| {
+        // Shifted optimum
+        let sum = xs
+            .iter()
+            .map(|x| x - 2.)
+            .map(|x| x * x - 10. * (TAU * x).cos());
+        10. * xs.len() as f64 + sum.sum::<f64>()
+    };
+    let run = |neighborhood| {
+        (0..5)
+            .map(|seed| {
+                Solver::build(
+                    Pso::default().neighborhood_schedule(neighborhood),
+                    Fx::new(&bound, rastrigin),
+                )
+                .seed(seed)
+                .pop_num(40)
+                .max_gen(300)
+                .solve()
+                .get_best_eval()
+            })
+            .sum::<f64>()
+    };
+    let growing = run(schedule);
+    let global = run(Neighborhood::Global);
+    assert!(growing < global, "{growing} >= {global}");
+}
+
+#[test]
+fn reseed() {
+    // Record a draw in each generation
+    struct Draws(alloc::vec::Vec<f64>);
+    impl<F: ObjFunc> Algorithm<F> for Draws {
+        fn generation(&mut self, _ctx: &mut Ctx<F>, rng: &mut Rng) {
+            self.0.push(rng.rand());
+        }
+    }
+    let mut iter = Solver::build_algorithm(Draws(alloc::vec::Vec::new()), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .callback(|ctx| {
+            if ctx.gen == 10 {
+                ctx.reseed(u128::MAX - 1);
+            }
+        })
+        .iter();
+    while iter.step() {}
+    let draws = iter.algorithm.0;
+    let mut rng = Rng::new(SeedOpt::U128(u128::MAX - 1));
+    let fresh = (0..10).map(|_| rng.rand()).collect::<alloc::vec::Vec<_>>();
+    assert_eq!(draws.len(), 20);
+    assert_eq!(draws[10..], fresh);
+    assert_ne!(draws[..10], fresh);
+}
+
+#[test]
+fn vectorized_dist2() {
+    let mut rng = Rng::new(SeedOpt::U64(0));
+    for dim in 0..70 {
+        let a = (0..dim)
+            .map(|_| rng.range(-50. ..50.))
+            .collect::<alloc::vec::Vec<_>>();
+        let b = (0..dim)
+            .map(|_| rng.range(-50. ..50.))
+            .collect::<alloc::vec::Vec<_>>();
+        let w = (0..dim)
+            .map(|_| rng.range(0.1..10.))
+            .collect::<alloc::vec::Vec<_>>();
+        // Scalar version
+        let scalar = |w: &[f64]| {
+            (0..dim)
+                .map(|s| (a[s] - b[s]) * w[s])
+                .fold(0., |acc, d| acc + d * d)
+        };
+        let tol = |x: f64| 1e-12 * x.max(1.);
+        let expected = scalar(&alloc::vec![1.; dim]);
+        assert!((crate::math::dist2(&a, &b, None) - expected).abs() < tol(expected));
+        let expected = scalar(&w);
+        assert!((crate::math::dist2(&a, &b, Some(&w)) - expected).abs() < tol(expected));
+    }
+}
+
+#[test]
+fn per_dim_pool() {
+    let bound = [[1e-4, 1.], [-1., 1.]];
+    let f = || Fx::new(&bound, |&[a, b]| a * a + b * b);
+    let pool = Pool::PerDim(alloc::vec![DimDist::LogUniform, DimDist::Uniform]);
+    let iter = Solver::build(Rga::default(), f())
+        .seed(0)
+        .pop_num(4000)
+        .init_pool(pool)
+        .iter();
+    // Evenly spread in each decade
+    let mut decades = [0; 4];
+    for xs in &iter.ctx().pool {
+        assert!((1e-4..=1.).contains(&xs[0]));
+        decades[(-xs[0].log10()).floor().min(3.) as usize] += 1;
+    }
+    assert!(
+        decades.iter().all(|&n| (900..1100).contains(&n)),
+        "{decades:?}"
+    );
+    // Invalid configurations
+    let pool = Pool::PerDim(alloc::vec![DimDist::Uniform]);
+    let e = Solver::build(Rga::default(), f())
+        .init_pool(pool)
+        .try_solve()
+        .err();
+    assert_eq!(e, Some(BuildError::PoolDimMismatched));
+    let pool = Pool::PerDim(alloc::vec![DimDist::Uniform, DimDist::LogUniform]);
+    let e = Solver::build(Rga::default(), f())
+        .init_pool(pool)
+        .try_solve()
+        .err();
+    assert_eq!(e, Some(BuildError::InvalidDimDist));
+}
+
+#[test]
+fn compare_solvers() {
+    let build = |f| {
+        Solver::build(De::default().f(f), TestObj)
+            .pop_num(20)
+            .task(|ctx| ctx.gen == 30)
+    };
+    let report = compare(|| build(0.6), || build(0.01), 0..12);
+    assert_eq!(report.wins + report.ties + report.losses, 12);
+    assert!(report.wins > report.losses);
+    assert!(report.w_plus > report.w_minus);
+    assert!(report.is_significant(), "{report:?}");
+    // Self comparison
+    let report = compare(|| build(0.6), || build(0.6), 0..12);
+    assert_eq!(report.ties, 12);
+    assert!(!report.is_significant());
+    // Follow the direction of maximization
+    let build = |f| {
+        let func = Fx::new(&[[-50., 50.]; 4], |xs: &[f64; 4]| {
+            -xs.iter().map(|x| x * x).sum::<f64>()
+        });
+        Solver::build(De::default().f(f), func)
+            .pop_num(20)
+            .task(|ctx| ctx.gen == 30)
+            .maximize()
+    };
+    let report = compare(|| build(0.6), || build(0.01), 0..12);
+    assert!(report.wins > report.losses);
+    assert!(report.z > 0. && report.is_significant(), "{report:?}");
+    // NaN results are ties
+    struct Nan;
+    impl Bounded for Nan {
+        fn bound(&self) -> &[[f64; 2]] {
+            &[[-1., 1.]; 2]
+        }
+    }
+    impl ObjFunc for Nan {
+        type Ys = f64;
+        fn fitness(&self, _: &[f64]) -> Self::Ys {
+            f64::NAN
+        }
+    }
+    let build = || {
+        Solver::build(De::default(), Nan)
+            .pop_num(20)
+            .task(|ctx| ctx.gen == 5)
+    };
+    let report = compare(build, build, 0..4);
+    assert_eq!(report.ties, 4);
+    assert_eq!(report.z, 0.);
+}
+
+#[test]
+fn de_lambda() {
+    use core::f64::consts::TAU;
+    // Faster convergence on the unimodal function
+    let sphere = |lambda| {
+        Solver::build(De::new().strategy(Strategy::C1F2).lambda(lambda), TestObj)
+            .seed(0)
+            .pop_num(40)
+            .task(|ctx| ctx.gen == 30)
+            .solve()
+            .get_best_eval()
+    };
+    let evals = [0., 0.5, 1.].map(sphere);
+    assert!(evals[0] > evals[1] && evals[1] > evals[2], "{evals:?}");
+    // Premature convergence on the multimodal function
+    let rastrigin = |xs: &[f64; 6]| {
+        let sum = xs
+            .iter()
+            .map(|x| x - 2.)
+            .map(|x| x * x - 10. * (TAU * x).cos());
+        10. * xs.len() as f64 + sum.sum::<f64>()
+    };
+    let bound = [[-5.12, 5.12]; 6];
+    let run = |lambda| {
+        (0..5)
+            .map(|seed| {
+                let de = De::new().strategy(Strategy::C1F2).lambda(lambda);
+                Solver::build(de, Fx::new(&bound, rastrigin))
+                    .seed(seed)
+                    .pop_num(20)
+                    .task(|ctx| ctx.gen == 300)
+                    .solve()
+                    .get_best_eval()
+            })
+            .sum::<f64>()
+    };
+    let (rand, best) = (run(0.), run(1.));
+    assert!(rand < best, "{rand} >= {best}");
+}
+
+#[test]
+fn feasible_ratio() {
+    struct Cons;
+    impl Bounded for Cons {
+        fn bound(&self) -> &[[f64; 2]] {
+            &[[-10., 10.]; 2]
+        }
+    }
+    impl ObjFunc for Cons {
+        type Ys = f64;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            // Penalty of x[0] >= 5
+            xs[0] * xs[0] + xs[1] * xs[1] + 1e3 * (5. - xs[0]).max(0.)
+        }
+    }
+    impl Constrained for Cons {
+        fn is_feasible(&self, xs: &[f64]) -> bool {
+            xs[0] >= 5.
+        }
+    }
+    let mut report = alloc::vec::Vec::new();
+    let _ = Solver::build(De::default(), Cons)
+        .seed(0)
+        .pop_num(40)
+        .task(|ctx| ctx.gen == 30)
+        .callback(|ctx| report.push(ctx.feasible_ratio()))
+        .solve();
+    let (first, last) = (report[0], report[report.len() - 1]);
+    assert!(first < 0.5, "{first}");
+    assert!(last > 0.9, "{last}");
+}
+
+#[test]
+fn clock() {
+    use core::time::Duration;
+    // Advance 10 ms on each reading
+    struct Fake(u64);
+    impl Clock for Fake {
+        fn now(&mut self) -> Duration {
+            self.0 += 10;
+            Duration::from_millis(self.0)
+        }
+    }
+    let mut report = alloc::vec::Vec::new();
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .pop_num(20)
+        .clock(Fake(0))
+        .time_limit(Duration::from_millis(95))
+        .callback(|ctx| report.push(ctx.elapsed().unwrap()))
+        .solve();
+    // Read once at the start and before each generation
+    assert_eq!(s.ctx.gen, 9);
+    assert_eq!(report[0], Duration::from_millis(10));
+    assert_eq!(report.last(), Some(&Duration::from_millis(100)));
+}
+
+#[test]
+fn rga_rates() {
+    let run = |adapt: bool| {
+        let builder = Solver::build(Rga::default(), TestObj).pop_num(40);
+        let rates = builder.algorithm().rates().clone();
+        let mut last = f64::INFINITY;
+        let mut stall = 0;
+        let mut report = alloc::vec::Vec::new();
+        let _ = builder
+            .seed(0)
+            .task(|ctx| ctx.gen == 60)
+            .callback(|ctx| {
+                report.push(ctx.diversity());
+                let best = ctx.best.get_eval();
+                if best < last - 1e-3 {
+                    last = best;
+                    stall = 0;
+                } else {
+                    stall += 1;
+                }
+                // Double the mutation rate after stagnation
+                if adapt && stall == 5 {
+                    rates.set_mutate((rates.mutate() * 2.).min(1.));
+                    stall = 0;
+                }
+            })
+            .solve();
+        (report, rates.mutate())
+    };
+    let (fixed, mutate) = run(false);
+    assert_eq!(mutate, 0.05);
+    let (adapt, mutate) = run(true);
+    assert!(mutate > 0.05);
+    // The same trajectory before the adaptation, then more diverse
+    let tail = |r: &[f64]| r[r.len() - 20..].iter().sum::<f64>() / 20.;
+    assert_eq!(fixed[..20], adapt[..20]);
+    assert!(tail(&adapt) > 2. * tail(&fixed));
+    // The parameters report the current rates
+    let builder = Solver::build(Rga::default(), TestObj);
+    builder.algorithm().rates().set_mutate(0.2);
+    let s = builder.seed(0).task(|ctx| ctx.gen == 1).solve();
+    assert!(s.algorithm_params().contains(&("mutate", 0.2)));
+}
+
+#[test]
+fn sorted_pool() {
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .pop_num(20)
+        .task(|ctx| ctx.gen == 20)
+        .solve();
+    let pool = s.sorted_pool();
+    assert_eq!(pool.len(), s.pool().len());
+    assert_eq!(pool[0].0, s.as_best_xs());
+    assert!(pool.windows(2).all(|w| w[0].1.eval() <= w[1].1.eval()));
+}
+
+#[test]
+fn fitness_partial() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    #[derive(Clone)]
+    struct Fit {
+        cost: f64,
+        weight: f64,
+    }
+    impl Fitness for Fit {
+        type Best<T: Fitness> = Pareto<T>;
+        type Eval = f64;
+        fn is_dominated(&self, rhs: &Self) -> bool {
+            self.cost <= rhs.cost && self.weight <= rhs.weight
+        }
+        fn eval(&self) -> Self::Eval {
+            self.cost.max(self.weight)
+        }
+        fn dominates_prefix(&self, prefix: &[f64]) -> bool {
+            // The lower bound of the weight is zero
+            self.cost <= prefix[0] && self.weight <= 0.
+        }
+    }
+    // Count the evaluations of each objective
+    #[derive(Default)]
+    struct Counted([AtomicUsize; 2]);
+    impl Bounded for Counted {
+        fn bound(&self) -> &[[f64; 2]] {
+            &[[-1., 1.]; 2]
+        }
+    }
+    impl ObjFunc for Counted {
+        type Ys = Fit;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            self.fitness_partial(xs, &|_| false)
+        }
+        fn fitness_partial(&self, xs: &[f64], is_dominated: &dyn Fn(&[f64]) -> bool) -> Fit {
+            self.0[0].fetch_add(1, Ordering::Relaxed);
+            let cost = xs[0] * xs[0];
+            if is_dominated(&[cost]) {
+                return Fit { cost, weight: f64::INFINITY };
+            }
+            self.0[1].fetch_add(1, Ordering::Relaxed);
+            Fit { cost, weight: xs[1].max(0.) }
+        }
+    }
+    let s = Solver::build(De::default(), Counted::default())
+        .seed(0)
+        .pop_num(20)
+        .task(|ctx| ctx.gen == 20)
+        .solve();
+    let [cost, weight] = &s.func().0;
+    let (cost, weight) = (cost.load(Ordering::Relaxed), weight.load(Ordering::Relaxed));
+    assert!(weight < cost, "{weight} >= {cost}");
+    // The aborted candidates are not on the front
+    assert!(s
+        .as_best_set()
+        .as_pareto()
+        .iter()
+        .all(|ys| ys.weight.is_finite()));
+}
+
+#[test]
+fn clamp_to_bounds() {
+    let solve = |clamp| {
+        // The optimum is outside the bounds
+        let f = Fx::new(&[[-10., 10.]], |&[x]| (x - 11.) * (x - 11.));
+        Solver::build(De::default(), f)
+            .seed(0)
+            .pop_num(20)
+            .task(|ctx| ctx.gen == 50)
+            .clamp_to_bounds(clamp)
+            .solve()
+            .as_best_xs()[0]
+    };
+    let x = solve(true);
+    assert!(x <= 10., "{x}");
+    let x = solve(false);
+    assert!(x > 10., "{x}");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn gen_times() {
+    let s = Solver::build(Rga::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .record_gen_times(true)
+        .solve();
+    assert_eq!(s.gen_times().len(), 20);
+    // Opt-in
+    let s = Solver::build(Rga::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .solve();
+    assert!(s.gen_times().is_empty());
+}
+
+#[test]
+fn non_dominated_sort() {
+    let mut rng = Rng::new(SeedOpt::U64(0));
+    // Integers to make the ties
+    let ys = (0..200)
+        .map(|_| TestMOFit { cost: rng.ub(20) as f64, weight: rng.ub(20) as f64 })
+        .collect::<alloc::vec::Vec<_>>();
+    let fronts = crate::pareto::non_dominated_sort(&ys);
+    // Serial reference by peeling the non-dominated elements
+    let dominates = |a: &TestMOFit, b: &TestMOFit| a.is_dominated(b) && !b.is_dominated(a);
+    let mut rest = (0..ys.len()).collect::<alloc::vec::Vec<_>>();
+    let mut expected = alloc::vec::Vec::new();
+    while !rest.is_empty() {
+        let (front, next) = (rest.iter()).partition::<alloc::vec::Vec<usize>, _>(|i| {
+            !rest.iter().any(|j| dominates(&ys[*j], &ys[**i]))
+        });
+        expected.push(front);
+        rest = next;
+    }
+    assert_eq!(fronts, expected);
+    assert!(fronts.len() > 1);
+}
+
+#[test]
+fn best_at() {
+    let mut report = alloc::vec::Vec::new();
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 50)
+        .record_best(true)
+        .callback(|ctx| report.push((ctx.gen, ctx.best.get_xs().to_vec())))
+        .solve();
+    assert_eq!(s.best_at(0), None);
+    // The callback is called before the next generation
+    for (gen, xs) in report.into_iter().skip(1) {
+        let (best, eval) = s.best_at(gen).unwrap();
+        assert_eq!(best, xs);
+        assert_eq!(eval, TestObj.fitness(&xs).eval());
+    }
+    assert_eq!(s.best_at(50).unwrap().0, s.as_best_xs());
+}
+
+#[test]
+fn stop_on_moving_average() {
+    use core::sync::atomic::{AtomicU64, Ordering};
+    // Sphere with the multiplicative noise from a LCG
+    #[derive(Default)]
+    struct Noisy(AtomicU64);
+    impl Bounded for Noisy {
+        fn bound(&self) -> &[[f64; 2]] {
+            &[[-10., 10.]; 2]
+        }
+    }
+    impl ObjFunc for Noisy {
+        type Ys = f64;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            let state = (self.0)
+                .fetch_add(1, Ordering::Relaxed)
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let noise = (state >> 11) as f64 / (1u64 << 53) as f64;
+            (xs[0] * xs[0] + xs[1] * xs[1]) * (0.2 + noise)
+        }
+    }
+    const WINDOW: usize = 10;
+    const TARGET: f64 = 1e-2;
+    let mut report = alloc::vec::Vec::new();
+    let s = Solver::build(De::default(), Noisy::default())
+        .seed(0)
+        .pop_num(20)
+        .stop_on_moving_average(WINDOW, TARGET)
+        .callback(|ctx| report.push(ctx.best_cost()))
+        .solve();
+    let mean = |w: &[f64]| w.iter().sum::<f64>() / w.len() as f64;
+    assert_eq!(report.len() as u64, s.ctx.gen + 1);
+    // Stopped at the first smoothed window
+    let n = report.len();
+    assert!(mean(&report[n - WINDOW..]) < TARGET);
+    assert!(report
+        .windows(WINDOW)
+        .take(n - WINDOW)
+        .all(|w| mean(w) >= TARGET));
+    // Not on the first lucky generation
+    let first = report.iter().position(|c| *c < TARGET).unwrap();
+    assert!(first < n - 1, "{first}");
+}
+
+#[test]
+fn last_improvement() {
+    let mut report = alloc::vec::Vec::new();
+    let mut total = 0.;
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 50)
+        .callback(|ctx| {
+            report.push(ctx.best.get_eval());
+            total += ctx.last_improvement();
+        })
+        .solve();
+    assert_eq!(report.last(), Some(&s.get_best_eval()));
+    let expected = report[0] - s.get_best_eval();
+    assert!(expected > 0.);
+    assert!((total - expected).abs() < 1e-9, "{total} != {expected}");
+}
+
+#[test]
+fn ready_params_pool() {
+    let mut rng = Rng::new(SeedOpt::U64(0));
+    let pool = (0..30)
+        .map(|_| (0..4).map(|_| rng.range(-50.0..50.)).collect())
+        .collect::<alloc::vec::Vec<alloc::vec::Vec<f64>>>();
+    let mut init = alloc::vec::Vec::new();
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 50)
+        .callback(|ctx| {
+            if ctx.gen == 0 {
+                init = ctx.pool_y.iter().map(Fitness::eval).collect();
+            }
+        })
+        .init_pool(Pool::ReadyParams(pool.clone()))
+        .solve();
+    // Evaluated in the initialization
+    let expected = pool.iter().map(|xs| TestObj.fitness(xs).eval());
+    assert!(init.iter().copied().eq(expected));
+    assert_eq!(s.pool().len(), 30);
+    assert!(init.iter().all(|y| s.get_best_eval() < *y));
+    // Validation
+    let e = Solver::build(De::default(), TestObj)
+        .init_pool(Pool::ReadyParams(alloc::vec![alloc::vec![0.; 3]]))
+        .try_solve()
+        .err();
+    assert_eq!(e, Some(BuildError::PoolDimMismatched));
+    let e = Solver::build(De::default(), TestObj)
+        .init_pool(Pool::ReadyParams(alloc::vec![]))
+        .try_solve()
+        .err();
+    assert_eq!(e, Some(BuildError::EmptyPool));
+}
+
+#[test]
+fn phased() {
+    use core::sync::atomic::{AtomicU64, Ordering};
+    static LOCAL_GENS: AtomicU64 = AtomicU64::new(0);
+    // A local refinement around the best
+    struct Local;
+    impl AlgCfg for Local {
+        type Algorithm<F: ObjFunc> = Local;
+        fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+            self
+        }
+    }
+    impl<F: ObjFunc> Algorithm<F> for Local {
+        fn generation(&mut self, ctx: &mut Ctx<F>, rng: &mut Rng) {
+            LOCAL_GENS.fetch_add(1, Ordering::Relaxed);
+            for i in 0..ctx.pop_num() {
+                let xs = (ctx.best.sample_xs(rng).iter())
+                    .map(|v| v + rng.normal(0., 1e-5))
+                    .collect::<alloc::vec::Vec<_>>();
+                let ys = ctx.fitness(&xs);
+                if let Some(i) = ctx.replace(i, xs, ys, rng) {
+                    ctx.best.update(&ctx.pool[i], &ctx.pool_y[i]);
+                }
+            }
+        }
+    }
+    let mut report = alloc::vec::Vec::new();
+    let method = Phased::new()
+        .add(De::default(), |ctx| ctx.gen > 50)
+        .add(Local, |_| false);
+    let s = Solver::build_algorithm(method, TestObj)
+        .seed(0)
+        .pop_num(20)
+        .task(|ctx| ctx.gen == 100)
+        .callback(|ctx| report.push(ctx.best.get_eval()))
+        .solve();
+    // The best is continued and still improved after the switch
+    assert!(report.windows(2).all(|w| w[1] <= w[0]));
+    assert!(report[100] < report[50]);
+    assert_eq!(s.get_best_eval(), report[100]);
+    // Switched at the generation 51
+    assert_eq!(LOCAL_GENS.load(Ordering::Relaxed), 50);
+}
+
+#[test]
+fn dedup() {
+    const TOL: f64 = 1e-6;
+    let distinct = |s: &Solver<TestObj>| {
+        let pool = s.pool();
+        (0..pool.len())
+            .filter(|&i| {
+                !(0..i).any(|j| {
+                    let mut d = core::iter::zip(&pool[i], &pool[j]);
+                    d.all(|(a, b)| (a - b).abs() <= TOL)
+                })
+            })
+            .count()
+    };
+    let solve = |dedup| {
+        let builder = Solver::build(Pso::default(), TestObj)
+            .seed(0)
+            .pop_num(40)
+            .task(|ctx| ctx.gen == 200);
+        if dedup {
+            builder.dedup(TOL).solve()
+        } else {
+            builder.solve()
+        }
+    };
+    // The population is collapsed
+    let s = solve(false);
+    assert!(distinct(&s) < 20, "{}", distinct(&s));
+    let s = solve(true);
+    assert_eq!(distinct(&s), 40);
+    assert!(s.get_best_eval() - OFFSET < 1e-6);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn run_config() {
+    let builder = Solver::build(De::default().f(0.7), TestObj)
+        .seed(0)
+        .pop_num(20)
+        .max_gen(20)
+        .stop_on_slope(10, 1e-8);
+    let config = builder.run_config();
+    assert_eq!(config.method, "DE");
+    assert!(config.params.contains(&("f".into(), 0.7)));
+    assert_eq!(config.pop_num, 20);
+    assert_eq!(
+        config.termination,
+        ["max_gen(20)", "stop_on_slope(10, 0.00000001)"]
+    );
+    let json = serde_json::to_string(&config).unwrap();
+    let config2 = serde_json::from_str::<RunConfig>(&json).unwrap();
+    assert_eq!(config, config2);
+    // Reproduce the run from the seed
+    let s = builder.solve();
+    assert_eq!(config.seed, Some(s.seed()));
+    let s2 = Solver::build(De::default().f(0.7), TestObj)
+        .seed(config2.seed.unwrap())
+        .pop_num(config2.pop_num)
+        .max_gen(20)
+        .solve();
+    assert_eq!(s.as_best_xs(), s2.as_best_xs());
+}
+
+#[test]
+fn truncation() {
+    use crate::pareto::{hypervolume, CrowdingDistance, HypervolumeContribution, Spacing};
+    use alloc::{sync::Arc, vec::Vec};
+    // A convex front, dense in the middle
+    let front = (0..=40)
+        .map(|i| {
+            let t = i as f64 / 40.;
+            let cost = 0.5 + (t - 0.5) * (t - 0.5).abs() * 2.;
+            TestMOFit { cost, weight: (1. - cost).powi(2) }
+        })
+        .collect::<Vec<_>>();
+    let xs = (0..front.len())
+        .map(|i| alloc::vec![i as f64])
+        .collect::<Vec<_>>();
+    let truncate = |t: Arc<dyn Truncation>| {
+        let mut pareto = Pareto::from_limit(8);
+        pareto.set_truncation(t);
+        pareto.update_all(&xs, &front);
+        (pareto.as_pareto().iter())
+            .map(Fitness::objectives)
+            .collect::<Vec<_>>()
+    };
+    let reference = [1.1, 1.1];
+    let hv = truncate(Arc::new(HypervolumeContribution));
+    let cd = truncate(Arc::new(CrowdingDistance));
+    let sp = truncate(Arc::new(Spacing));
+    for front in [&hv, &cd, &sp] {
+        assert_eq!(front.len(), 8);
+        // The extreme points are retained
+        assert!(front.contains(&alloc::vec![0., 1.]));
+        assert!(front.contains(&alloc::vec![1., 0.]));
+    }
+    assert!(hypervolume(&hv, &reference) > hypervolume(&cd, &reference));
+}
+
+#[test]
+fn pareto_crowding() {
+    let f = Fx::new(&[[0., 1.]], |&[a]| TestMOFit { cost: a, weight: 1. - a });
+    let s = Solver::build(NsgaII::default(), f)
+        .seed(0)
+        .pop_num(20)
+        .task(|ctx| ctx.gen == 20)
+        .pareto_limit(10)
+        .truncation(crate::pareto::CrowdingDistance)
+        .solve();
+    let front = s.as_best_set().as_pareto();
+    let crowding = s.pareto_crowding();
+    assert_eq!(crowding.len(), front.len());
+    // The edge members of the linear front
+    let (min, max) = (0..front.len()).fold((0, 0), |(min, max), i| {
+        let cost = |i: usize| front[i].cost;
+        (
+            if cost(i) < cost(min) { i } else { min },
+            if cost(i) > cost(max) { i } else { max },
+        )
+    });
+    for (i, d) in crowding.iter().enumerate() {
+        if i == min || i == max {
+            assert_eq!(*d, f64::INFINITY);
+        } else {
+            assert!(d.is_finite() && *d > 0.);
+        }
+    }
+}
+
+#[test]
+fn hypervolume_mc() {
+    use crate::pareto::{hypervolume, hypervolume_mc};
+    use alloc::vec::Vec;
+    let mut rng = Rng::new(SeedOpt::U64(0));
+    let front = (0..20)
+        .map(|_| (0..3).map(|_| rng.range(0. ..1.)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let reference = [1.5; 3];
+    let exact = hypervolume(&front, &reference);
+    let mc = hypervolume_mc(&front, &reference, 100000, &mut rng);
+    assert!((exact - mc).abs() / exact < 0.01, "{exact} vs {mc}");
+    assert_eq!(hypervolume_mc(&[], &reference, 100, &mut rng), 0.);
+    // Exact for the small number of the objectives
+    let s = Solver::build(Rga::default(), TestMO)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .solve();
+    let front = (s.as_best_set().as_pareto().iter())
+        .map(Fitness::objectives)
+        .collect::<Vec<_>>();
+    assert_eq!(s.hypervolume(&[10., 10.]), hypervolume(&front, &[10., 10.]));
+}
+
+#[test]
+#[should_panic(expected = "The objectives should have the same length as the reference point")]
+fn hypervolume_mismatch() {
+    let _ = crate::pareto::hypervolume(&[alloc::vec![]], &[1., 1.]);
+}
+
+#[test]
+#[cfg(feature = "argmin")]
+fn argmin_obj() {
+    use argmin::core::{CostFunction, Error};
+    struct Shifted;
+    impl CostFunction for Shifted {
+        type Param = alloc::vec::Vec<f64>;
+        type Output = f64;
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            if p[0] < -5. {
+                return Err(Error::msg("out of domain"));
+            }
+            Ok(p.iter().map(|x| (x - 1.) * (x - 1.)).sum())
+        }
+    }
+    let f = ArgminObj::new(Shifted, alloc::vec![[-10., 10.]; 3]);
+    assert_eq!(f.fitness(&[-6., 0., 0.]), f64::INFINITY);
+    let s = Solver::build(De::default(), f)
+        .seed(0)
+        .task(|ctx| ctx.gen == 100)
+        .solve();
+    assert!(s.get_best_eval() < 1e-10);
+    assert!(s.as_best_xs().iter().all(|x| (x - 1.).abs() < 1e-4));
+    // The failed points would be the best in maximization
+    let f = ArgminObj::new(Shifted, alloc::vec![[-10., 10.]; 3]);
+    let e = Solver::build(De::default(), f)
+        .seed(0)
+        .task(|ctx| ctx.gen == 10)
+        .maximize()
+        .try_solve()
+        .err();
+    assert_eq!(e, Some(BuildError::MinimizeOnly));
+}
+
+#[test]
+fn immigrant_source() {
+    use alloc::vec::Vec;
+    let mut calls = 0;
+    let snapshots = Solver::build(Rga::default(), TestObj)
+        .seed(0)
+        .pop_num(10)
+        .task(|ctx| ctx.gen == 20)
+        .immigrant_source(move || {
+            calls += 1;
+            (calls == 10).then(|| alloc::vec![0.; 4])
+        })
+        .iter()
+        .collect::<Vec<_>>();
+    // The optimum is injected at the 10th generation
+    assert!(snapshots[..9].iter().all(|s| s.best > OFFSET));
+    assert!(snapshots[9..].iter().all(|s| s.best == OFFSET));
+}
+
+#[test]
+fn refine_lbfgs() {
+    let builder = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 5);
+    let evals = builder.evals_per_gen() * 5;
+    let mut s = builder.solve();
+    assert!(s.get_best_eval() - OFFSET > 1e-2);
+    let refine_evals = s.refine_lbfgs(100);
+    assert!(s.get_best_eval() - OFFSET < 1e-12, "{}", s.get_best_eval());
+    assert!(s.as_best_xs().iter().all(|x| x.abs() < 1e-6));
+    assert!(refine_evals * 10 < evals, "{refine_evals} {evals}");
+    // Follow the direction of maximization
+    let f = Fx::new(&[[-10., 10.]; 2], |&[x, y]: &[f64; 2]| {
+        3. - (x - 1.).powi(2) - (y - 1.).powi(2)
+    });
+    let mut s = Solver::build(De::default(), f)
+        .seed(0)
+        .task(|ctx| ctx.gen == 5)
+        .maximize()
+        .solve();
+    assert!(3. - s.get_best_eval() > 1e-6);
+    s.refine_lbfgs(100);
+    assert!(3. - s.get_best_eval() < 1e-12, "{}", s.get_best_eval());
+}
+
+#[test]
+#[cfg(not(feature = "rayon"))]
+fn eval_order() {
+    use alloc::{vec, vec::Vec};
+    use core::cell::RefCell;
+    #[derive(Default)]
+    struct Recorder(RefCell<Vec<f64>>);
+    impl Bounded for Recorder {
+        fn bound(&self) -> &[[f64; 2]] {
+            &[[-5., 5.]; 2]
+        }
+    }
+    impl ObjFunc for Recorder {
+        type Ys = f64;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            self.0.borrow_mut().push(xs[0]);
+            xs[0] * xs[0] + xs[1] * xs[1]
+        }
+    }
+    let solve = |order| {
+        let pool = vec![vec![3., 0.], vec![0., 1.], vec![2., 0.], vec![1., 0.5]];
+        let s = Solver::build(Rga::default(), Recorder::default())
+            .seed(0)
+            .task(|ctx| ctx.gen == 0)
+            .init_pool(Pool::ReadyParams(pool))
+            .eval_order(order)
+            .solve();
+        // The fitness values are matched to their individuals
+        for (xs, ys) in s.sorted_pool() {
+            assert_eq!(*ys, xs[0] * xs[0] + xs[1] * xs[1]);
+        }
+        let calls = s.func().0.borrow()[..4].to_vec();
+        calls
+    };
+    assert_eq!(solve(EvalOrder::PoolOrder), [3., 0., 2., 1.]);
+    assert_eq!(solve(EvalOrder::SortedByFirstVar), [0., 1., 2., 3.]);
+    assert_eq!(solve(EvalOrder::NearestNeighbor), [3., 2., 1., 0.]);
+}
+
+#[test]
+fn front_spacing_spread() {
+    use crate::pareto::{spacing, spread};
+    use alloc::vec::Vec;
+    // An evenly-spaced linear front
+    let front = (0..=10)
+        .map(|i| {
+            let t = i as f64 / 10.;
+            alloc::vec![t, 1. - t]
+        })
+        .collect::<Vec<_>>();
+    assert!(spacing(&front) < 1e-12);
+    assert!((spread(&front) - core::f64::consts::SQRT_2).abs() < 1e-12);
+    // Uneven spacing
+    let mut uneven = front.clone();
+    uneven[1] = alloc::vec![0.02, 0.98];
+    assert!(spacing(&uneven) > 1e-2);
+    assert_eq!(spacing(&front[..1]), 0.);
+    assert_eq!(spread(&[]), 0.);
+    let s = Solver::build(Rga::default(), TestMO)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .solve();
+    assert!(s.front_spacing() >= 0.);
+    assert!(s.front_spread() > 0.);
+}
+
+#[test]
+fn anti_saturation() {
+    let solve = |prob| {
+        // A narrow interior basin, and the outside slopes toward the bounds
+        let f = Fx::new(&[[-50., 50.]; 4], |xs: &[f64; 4]| {
+            (xs.iter())
+                .map(|x| {
+                    if x.abs() < 5. {
+                        x * x - 100.
+                    } else {
+                        50. - x.abs()
+                    }
+                })
+                .sum::<f64>()
+        });
+        let mut b = Solver::build(Pso::default(), f)
+            .seed(0)
+            .pop_num(40)
+            .task(|ctx| ctx.gen == 30);
+        if let Some(prob) = prob {
+            b = b.anti_saturation(prob);
+        }
+        let s = b.solve();
+        let saturated = (s.pool().iter())
+            .filter(|xs| xs.iter().any(|x| x.abs() >= 50.))
+            .count();
+        (saturated, s.get_best_eval())
+    };
+    let (saturated, best) = solve(None);
+    assert_eq!(saturated, 40);
+    let (saturated_anti, best_anti) = solve(Some(0.1));
+    assert!(saturated_anti < 10, "{saturated_anti}");
+    // The interior basin is found
+    assert!(best_anti < best - 100., "{best_anti} vs {best}");
+}
+
+#[test]
+fn with_immigration() {
+    use core::sync::atomic::{AtomicBool, Ordering};
+    let solve = |k| {
+        // A deeper optimum appears far away at the generation 50
+        let appeared = AtomicBool::new(false);
+        let f = Fx::new(&[[-50., 50.]; 4], |xs: &[f64; 4]| {
+            let old = xs.iter().map(|x| x * x).sum::<f64>();
+            let new = xs.iter().map(|x| (x - 30.) * (x - 30.)).sum::<f64>() - 1000.;
+            if appeared.load(Ordering::Relaxed) {
+                old.min(new)
+            } else {
+                old
+            }
+        });
+        let s = Solver::build(WithImmigration::new(De::default()).k(k), f)
+            .seed(0)
+            .pop_num(40)
+            .task(|ctx| ctx.gen == 100)
+            .callback(|ctx| appeared.store(ctx.gen >= 50, Ordering::Relaxed))
+            .record_diversity(50)
+            .solve();
+        let div = s.diversity_history()[1].1;
+        (div, s.get_best_eval(), s.eval_count())
+    };
+    let (div, best, evals) = solve(0);
+    let (div_imm, best_imm, evals_imm) = solve(5);
+    // The diversity at the generation 50, when the optimum appears
+    assert!(div_imm > div * 10., "{div_imm} vs {div}");
+    assert!(best > -1., "{best}");
+    assert!(best_imm < -999., "{best_imm}");
+    assert_eq!(evals_imm - evals, 5 * 100);
+}
+
+#[test]
+fn report_series() {
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .pop_num(20)
+        .task(|ctx| ctx.gen == 50)
+        .record_best(true)
+        .solve();
+    let history = s.best_history();
+    let (x, y) = s.convergence_series();
+    assert_eq!(x.len(), history.len());
+    assert_eq!(y.len(), history.len());
+    assert_eq!(x[0], history[0].0 as f64);
+    assert_eq!(y.last(), Some(&s.get_best_eval()));
+    // Not recorded
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .pop_num(20)
+        .task(|ctx| ctx.gen == 50)
+        .solve();
+    assert_eq!(s.convergence_series(), (alloc::vec![], alloc::vec![]));
+    // Front
+    let s = Solver::build(Rga::default(), TestMO)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .solve();
+    let front = s.front_series();
+    assert_eq!(front.len(), s.as_best_set().len());
+    assert!(front.windows(2).all(|w| w[0].0 <= w[1].0));
+}
+
+#[test]
+#[cfg(feature = "arrow")]
+fn write_parquet() {
+    use arrow_array::{cast::AsArray as _, types::Float64Type, types::UInt64Type};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .record_best(true)
+        .solve();
+    let mut buf = alloc::vec::Vec::new();
+    s.write_parquet(&mut buf).unwrap();
+    let builder = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(buf)).unwrap();
+    let names = (builder.schema().fields().iter())
+        .map(|field| field.name().as_str())
+        .collect::<alloc::vec::Vec<_>>();
+    assert_eq!(
+        names,
+        ["gen", "eval_count", "best_fitness", "x0", "x1", "x2", "x3"]
+    );
+    // The short run fits in a batch
+    let mut reader = builder.build().unwrap();
+    let batch = reader.next().unwrap().unwrap();
+    assert!(reader.next().is_none());
+    let history = s.best_history();
+    assert_eq!(batch.num_rows(), history.len());
+    let gen = batch.column(0).as_primitive::<UInt64Type>();
+    let best = batch.column(2).as_primitive::<Float64Type>();
+    for (i, (g, y)) in history.into_iter().enumerate() {
+        assert_eq!(gen.value(i), g);
+        assert_eq!(best.value(i), y);
+    }
+    let x0 = batch.column(3).as_primitive::<Float64Type>();
+    assert_eq!(x0.value(x0.len() - 1), s.as_best_xs()[0]);
+}
+
+#[test]
+fn set_algorithm() {
+    use alloc::{boxed::Box, vec::Vec};
+    let mut iter = Solver::build_boxed(Pso::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 100)
+        .iter();
+    let mut bests = iter.by_ref().take(25).map(|s| s.best).collect::<Vec<_>>();
+    let pool = iter.ctx().pool.clone();
+    iter.set_algorithm(Box::new(De::default().algorithm::<TestObj>()));
+    // The context is kept
+    assert_eq!(iter.ctx().gen, 25);
+    assert_eq!(iter.ctx().pool, pool);
+    bests.extend(iter.by_ref().map(|s| s.best));
+    assert_eq!(bests.len(), 100);
+    assert!(bests.windows(2).all(|w| w[1] <= w[0]));
+    assert!(bests[99] < bests[24]);
+    let s = iter.into_solver();
+    assert_eq!(s.ctx.gen, 100);
+}
+
+#[test]
+fn hyper_heuristic() {
+    use crate::hyper_heuristic::Policy;
+    use core::sync::atomic::{AtomicU64, Ordering};
+    static IDLE_GENS: [AtomicU64; 2] = [AtomicU64::new(0), AtomicU64::new(0)];
+    // A heuristic that never improves
+    struct Idle(usize);
+    impl AlgCfg for Idle {
+        type Algorithm<F: ObjFunc> = Idle;
+        fn algorithm<F: ObjFunc>(self) -> Self::Algorithm<F> {
+            self
+        }
+    }
+    impl<F: ObjFunc> Algorithm<F> for Idle {
+        fn generation(&mut self, _: &mut Ctx<F>, _: &mut Rng) {
+            IDLE_GENS[self.0].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    for (i, policy) in [Policy::EpsilonGreedy(0.1), Policy::Ucb(0.5)]
+        .into_iter()
+        .enumerate()
+    {
+        let method = HyperHeuristic::new()
+            .with(Idle(i))
+            .with(De::default())
+            .policy(policy);
+        let s = Solver::build_algorithm(method, TestObj)
+            .seed(0)
+            .task(|ctx| ctx.gen == 100)
+            .solve();
+        assert!(s.get_best_eval() - OFFSET < 1e-6);
+        // The usage is shifted to DE
+        let idle = IDLE_GENS[i].load(Ordering::Relaxed);
+        assert!((1..20).contains(&idle), "{policy:?}: {idle}");
+    }
+}
+
+#[test]
+#[should_panic(expected = "The epsilon should be in [0, 1]")]
+fn hyper_heuristic_eps_invalid() {
+    use crate::hyper_heuristic::Policy;
+    let _ = HyperHeuristic::<TestObj>::new().policy(Policy::EpsilonGreedy(1.5));
+}
+
+#[test]
+fn adaptive_ref_point() {
+    use crate::pareto::{spread, tchebycheff};
+    use alloc::{vec, vec::Vec};
+    use core::iter::zip;
+    let mut ideals = Vec::new();
+    let s = Solver::build(Rga::default(), TestMO)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .callback(|ctx| {
+            // The ideal point is never worse than the pool
+            let g = ctx.tchebycheff(&ctx.pool_y[0], &[1., 0.], &RefPoint::Adaptive);
+            assert!(g >= 0. && ctx.pool_y[0].objectives()[0] - ctx.ideal_point()[0] == g);
+            ideals.push(ctx.ideal_point().to_vec());
+        })
+        .solve();
+    assert!(ideals
+        .windows(2)
+        .all(|w| zip(&w[0], &w[1]).all(|(a, b)| b <= a)));
+    assert!(zip(&ideals[0], &ideals[20]).all(|(a, b)| b < a));
+    for ys in s.as_best_set().as_pareto() {
+        assert!(zip(s.ctx.ideal_point(), ys.objectives()).all(|(z, y)| *z <= y));
+    }
+    // A convex front, each weight vector selects a solution
+    let front = (0..=40)
+        .map(|i| {
+            let t = i as f64 / 40.;
+            vec![t, (1. - t) * (1. - t)]
+        })
+        .collect::<Vec<_>>();
+    let ideal = vec![0., 0.];
+    let select = |reference: &RefPoint| {
+        let reference = reference.resolve(&ideal);
+        (1..10)
+            .map(|k| {
+                let w = [k as f64 / 10., 1. - k as f64 / 10.];
+                let g = |ys: &Vec<f64>| tchebycheff(ys, &w, reference);
+                front
+                    .iter()
+                    .min_by(|a, b| g(a).total_cmp(&g(b)))
+                    .unwrap()
+                    .clone()
+            })
+            .collect::<Vec<_>>()
+    };
+    let adaptive = select(&RefPoint::Adaptive);
+    let fixed = select(&RefPoint::Fixed(vec![0.5, 0.5]));
+    assert!(spread(&adaptive) > 2. * spread(&fixed));
+}
+
+#[test]
+fn stop_on_hypervolume() {
+    let mut gens = 0;
+    let s = Solver::build(Rga::default(), TestMO)
+        .seed(0)
+        .task(|ctx| ctx.gen == 1000)
+        .stop_on_hypervolume(alloc::vec![100., 100.], 10, 1e-6)
+        .callback(|ctx| gens = ctx.gen)
+        .solve();
+    // Stopped once the front stops growing
+    assert!((10..1000).contains(&gens), "{gens}");
+    let front = (s.as_best_set().as_pareto().iter())
+        .map(Fitness::objectives)
+        .collect::<alloc::vec::Vec<_>>();
+    assert!(1e4 - crate::pareto::hypervolume(&front, &[100., 100.]) < 1e-3);
+}
+
+#[test]
+#[should_panic(expected = "The objectives should have the same length as the reference point")]
+fn stop_on_hypervolume_mismatch() {
+    let _ = Solver::build(Rga::default(), TestMO)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .stop_on_hypervolume(alloc::vec![100.], 10, 1e-6)
+        .solve();
+}
+
+#[test]
+#[cfg(all(feature = "std", not(feature = "rayon")))]
+fn record_evals() {
+    let builder = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 10)
+        .record_evals(true);
+    let evals = builder.evals_per_gen() * 11;
+    let s = builder.solve();
+    assert_eq!(s.eval_log().len(), evals);
+    for method in [
+        Solver::build_boxed(Rga::default(), TestObj),
+        Solver::build_boxed(Pso::default(), TestObj),
+    ] {
+        let s = method
+            .seed(0)
+            .task(|ctx| ctx.gen == 10)
+            .record_evals(true)
+            .solve();
+        let log = s.eval_log();
+        assert!(log.len() > s.pool().len());
+        // The best is one of the evaluations
+        assert!(log.iter().any(|(xs, _)| xs == s.as_best_xs()));
+        let replayed = replay(&TestObj, log);
+        assert!(core::iter::zip(log, &replayed).all(|((_, a), b)| a.eval() == b.eval()));
+    }
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 10)
+        .solve();
+    assert!(s.eval_log().is_empty());
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn eval_parallel() {
+    use alloc::vec::Vec;
+    use std::{sync::Mutex, thread::ThreadId};
+    // Record the threads of the evaluations
+    #[derive(Default)]
+    struct Threads(Mutex<Vec<ThreadId>>);
+    impl Bounded for Threads {
+        fn bound(&self) -> &[[f64; 2]] {
+            TestObj.bound()
+        }
+    }
+    impl ObjFunc for Threads {
+        type Ys = f64;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            self.0.lock().unwrap().push(std::thread::current().id());
+            TestObj.fitness(xs).eval()
+        }
+    }
+    let solve = |seed, parallel| {
+        Solver::build(De::default(), Threads::default())
+            .seed(seed)
+            .pop_num(20)
+            .task(|ctx| ctx.gen == 50)
+            .eval_parallel(parallel)
+            .solve()
+    };
+    // The runs are parallel, the evaluations are not nested
+    let results = (0..8u64)
+        .into_par_iter()
+        .map(|seed| {
+            let s = solve(seed, false);
+            let threads = s.func().0.lock().unwrap();
+            assert!(threads.iter().all(|id| *id == threads[0]));
+            (s.get_best_eval(), s.as_best_xs().to_vec())
+        })
+        .collect::<Vec<_>>();
+    for (seed, (best, xs)) in (0..8u64).zip(results) {
+        let s = solve(seed, true);
+        assert_eq!(s.get_best_eval(), best);
+        assert_eq!(s.as_best_xs(), xs);
+    }
+}
+
+#[test]
+fn continue_for() {
+    fn check<S: AlgCfg + Default>() {
+        let build = |gen| {
+            Solver::build(S::default(), TestObj)
+                .seed(0)
+                .pop_num(20)
+                .task(move |ctx| ctx.gen == gen)
+                .record_best(true)
+                .restart(10)
+                .dedup(1e-3)
+        };
+        let s1 = build(200).solve();
+        let mut iter = build(100).iter();
+        iter.by_ref().for_each(drop);
+        iter.continue_for(100);
+        iter.by_ref().for_each(drop);
+        let s2 = iter.into_solver();
+        assert_eq!(s1.pool(), s2.pool());
+        assert_eq!(s1.as_best_xs(), s2.as_best_xs());
+        assert_eq!(s1.eval_count(), s2.eval_count());
+        assert_eq!(s1.best_history(), s2.best_history());
+        assert_eq!(s1.restarts(), s2.restarts());
+    }
+    check::<De>();
+    check::<Pso>();
+    check::<Rga>();
+    check::<Mfo>();
+    check::<Csa>();
+    // The seed and the clock are refreshed before the continued generation
+    use core::time::Duration;
+    struct Fake(u64);
+    impl Clock for Fake {
+        fn now(&mut self) -> Duration {
+            self.0 += 10;
+            Duration::from_millis(self.0)
+        }
+    }
+    let build = |gen| {
+        Solver::build(De::default(), TestObj)
+            .seed(0)
+            .pop_num(20)
+            .task(move |ctx| ctx.gen == gen)
+    };
+    let s1 = build(20)
+        .callback(|ctx| {
+            if ctx.gen == 10 {
+                ctx.reseed(1);
+            }
+        })
+        .solve();
+    let mut iter = build(10).clock(Fake(0)).iter();
+    iter.by_ref().for_each(drop);
+    let elapsed = iter.ctx().elapsed();
+    iter.ctx().reseed(1);
+    iter.continue_for(10);
+    iter.next();
+    assert!(iter.ctx().elapsed() > elapsed);
+    iter.by_ref().for_each(drop);
+    assert_eq!(s1.pool(), iter.into_solver().pool());
+}
+
+#[test]
+fn incumbent() {
+    let build = || {
+        Solver::build(De::default(), TestObj)
+            .seed(0)
+            .pop_num(20)
+            .task(|ctx| ctx.gen == 20)
+    };
+    // The optimum is the best before the first generation
+    let iter = build().incumbent(alloc::vec![0.; 4]).iter();
+    assert_eq!(iter.ctx().best.get_eval(), OFFSET);
+    assert_eq!(iter.ctx().pop_num(), 20);
+    assert_eq!(iter.ctx().eval_count(), 21);
+    assert!(!iter.ctx().pool.contains(&alloc::vec![0.; 4]));
+    let s = iter.into_solver();
+    assert_eq!(s.as_best_xs(), [0.; 4]);
+    // A worse guess is ignored
+    let iter = build().incumbent(alloc::vec![50.; 4]).iter();
+    assert_ne!(iter.ctx().best.get_xs(), [50.; 4]);
+    let e = build().incumbent(alloc::vec![0.; 3]).try_solve().err();
+    assert_eq!(e, Some(BuildError::PoolDimMismatched));
+}
+
+#[test]
+fn pop_num_factor() {
+    let pop_num = |b: SolverBuilder<_, _>| b.task(|ctx| ctx.gen == 1).solve().pool().len();
+    let b = Solver::build(De::default(), TestObj);
+    assert_eq!(pop_num(b.pop_num_factor(2.)), 2 * <De as AlgCfg>::pop_num());
+    // Rounded, and at least the minimum
+    let b = Solver::build(De::default(), TestObj);
+    assert_eq!(pop_num(b.pop_num_factor(0.0213)), 9);
+    let b = Solver::build(De::default(), TestObj);
+    assert_eq!(pop_num(b.pop_num_factor(1e-6)), 5);
+    let s = Solver::build(Rga::default(), TestObj)
+        .pop_num_factor(1e-6)
+        .task(|ctx| ctx.gen == 1)
+        .solve();
+    assert_eq!(s.pool().len(), 2);
+}
+
+#[test]
+fn record_by_evals() {
+    let mut evals = alloc::vec::Vec::new();
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .pop_num(20)
+        .task(|ctx| ctx.gen == 50)
+        .callback(|ctx| evals.push(ctx.eval_count()))
+        .record_by_evals(true)
+        .solve();
+    // The initial pool and 20 trials per generation
+    assert_eq!(evals[0], 20);
+    assert_eq!(s.eval_count(), 20 + 20 * 50);
+    let history = s.best_history();
+    assert!(history.len() > 1);
+    assert!(history
+        .windows(2)
+        .all(|w| w[0].0 < w[1].0 && w[0].1 > w[1].1));
+    // Recorded at the end of the generations
+    assert!(history.iter().all(|(key, _)| evals.contains(key)));
+    assert!(history.last().unwrap().0 <= s.eval_count());
+    assert_eq!(s.best_at(history[1].0).unwrap().1, history[1].1);
+    assert_eq!(s.best_at(history[1].0 - 1).unwrap().1, history[0].1);
+    // By the generation
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .pop_num(20)
+        .task(|ctx| ctx.gen == 50)
+        .record_best(true)
+        .solve();
+    assert!(s.best_history().iter().all(|(gen, _)| *gen <= 50));
+}
+
+#[test]
+fn record_diversity() {
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 95)
+        .record_diversity(10)
+        .solve();
+    let history = s.diversity_history();
+    let gens = history
+        .iter()
+        .map(|(gen, _)| *gen)
+        .collect::<alloc::vec::Vec<_>>();
+    assert_eq!(gens, (0..=90).step_by(10).collect::<alloc::vec::Vec<_>>());
+    // The population is converged
+    let (first, last) = (history[0].1, history[history.len() - 1].1);
+    assert!(last < first * 1e-2, "{first} -> {last}");
+    let drops = history.windows(2).filter(|w| w[1].1 < w[0].1).count();
+    assert!(drops >= history.len() - 2, "{history:?}");
+    // Opt-in
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 95)
+        .solve();
+    assert!(s.diversity_history().is_empty());
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn reproducible() {
+    use alloc::vec::Vec;
+    use std::sync::Mutex;
+    // A stateful objective function depends on the evaluation order
+    #[derive(Default)]
+    struct Stateful(Mutex<f64>);
+    impl Bounded for Stateful {
+        fn bound(&self) -> &[[f64; 2]] {
+            TestObj.bound()
+        }
+    }
+    impl ObjFunc for Stateful {
+        type Ys = f64;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            let mut last = self.0.lock().unwrap();
+            let y = TestObj.fitness(xs).eval() + *last * 1e-9;
+            *last = xs[0];
+            y
+        }
+    }
+    let solve = |threads| {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        pool.install(|| {
+            let s = Solver::build(De::default(), Stateful::default())
+                .seed(0)
+                .task(|ctx| ctx.gen == 30)
+                .reproducible(true)
+                .record_evals(true)
+                .solve();
+            let log = s.eval_log().iter().map(|(xs, _)| xs.clone());
+            (s.as_best_xs().to_vec(), log.collect::<Vec<_>>())
+        })
+    };
+    let (xs, log) = solve(1);
+    for threads in [2, 8] {
+        let (xs_n, log_n) = solve(threads);
+        assert_eq!(xs_n, xs);
+        assert_eq!(log_n, log);
+    }
+}
+
+#[test]
+fn fitness_and_grad() {
+    use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    // A sphere with the joint gradient
+    #[derive(Default)]
+    struct Adjoint {
+        joint: AtomicUsize,
+        refining: AtomicBool,
+    }
+    impl Bounded for Adjoint {
+        fn bound(&self) -> &[[f64; 2]] {
+            TestObj.bound()
+        }
+    }
+    impl ObjFunc for Adjoint {
+        type Ys = f64;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            assert!(!self.refining.load(Ordering::Relaxed), "separate fitness");
+            xs.iter().map(|x| x * x).sum()
+        }
+        fn gradient(&self, _: &[f64]) -> Option<alloc::vec::Vec<f64>> {
+            panic!("separate gradient")
+        }
+        fn fitness_and_grad(&self, xs: &[f64]) -> (Self::Ys, Option<alloc::vec::Vec<f64>>) {
+            self.joint.fetch_add(1, Ordering::Relaxed);
+            let y = xs.iter().map(|x| x * x).sum();
+            (y, Some(xs.iter().map(|x| 2. * x).collect()))
+        }
+        fn is_joint_grad(&self) -> bool {
+            true
+        }
+    }
+    let mut s = Solver::build(De::default(), Adjoint::default())
+        .seed(0)
+        .task(|ctx| ctx.gen == 5)
+        .solve();
+    s.func().refining.store(true, Ordering::Relaxed);
+    let evals = s.refine_lbfgs(100);
+    assert_eq!(s.func().joint.load(Ordering::Relaxed), evals);
+    assert!(s.get_best_eval() < 1e-12, "{}", s.get_best_eval());
+    // Rosenbrock function with the gradient only
+    #[derive(Default)]
+    struct GradOnly {
+        fitness: AtomicUsize,
+        gradient: AtomicUsize,
+    }
+    impl Bounded for GradOnly {
+        fn bound(&self) -> &[[f64; 2]] {
+            &[[-2., 2.]; 2]
+        }
+    }
+    impl ObjFunc for GradOnly {
+        type Ys = f64;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            self.fitness.fetch_add(1, Ordering::Relaxed);
+            let [x, y] = [xs[0], xs[1]];
+            (1. - x) * (1. - x) + 100. * (y - x * x) * (y - x * x)
+        }
+        fn gradient(&self, xs: &[f64]) -> Option<alloc::vec::Vec<f64>> {
+            self.gradient.fetch_add(1, Ordering::Relaxed);
+            let [x, y] = [xs[0], xs[1]];
+            let dx = -2. * (1. - x) - 400. * x * (y - x * x);
+            Some(alloc::vec![dx, 200. * (y - x * x)])
+        }
+    }
+    let mut s = Solver::build(De::default(), GradOnly::default())
+        .seed(0)
+        .task(|ctx| ctx.gen == 5)
+        .solve();
+    let fitness = s.func().fitness.load(Ordering::Relaxed);
+    let evals = s.refine_lbfgs(100);
+    let fitness = s.func().fitness.load(Ordering::Relaxed) - fitness;
+    let gradient = s.func().gradient.load(Ordering::Relaxed);
+    assert_eq!(fitness, evals);
+    // The rejected trials are not differentiated
+    assert!(gradient < fitness, "{gradient} {fitness}");
+    assert!(s.get_best_eval() < 1e-12, "{}", s.get_best_eval());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn pool_from_file() {
+    use alloc::{string::String, vec::Vec};
+    use core::fmt::Write as _;
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 10)
+        .solve();
+    let mut csv = String::from("# x0,x1,x2,x3\n");
+    for xs in s.pool() {
+        let row = xs.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+        writeln!(csv, "{}", row.join(",")).unwrap();
+    }
+    let Ok(Pool::<TestObj>::ReadyParams(pool)) = Pool::from_csv(csv.as_bytes()) else {
+        panic!("invalid CSV");
+    };
+    assert_eq!(pool, s.pool());
+    let n = pool.len();
+    let s = Solver::build(De::default(), TestObj)
+        .task(|ctx| ctx.gen == 0)
+        .init_pool(Pool::ReadyParams(pool))
+        .solve();
+    assert_eq!(s.pool().len(), n);
+    // Inconsistent rows
+    let e = Pool::<TestObj>::from_csv("1,2\n3\n".as_bytes())
+        .err()
+        .unwrap();
+    assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+    // A Fortran-ordered array of the shape (2, 3)
+    let header = "{'descr': '>f4', 'fortran_order': True, 'shape': (2, 3), }";
+    let mut npy = b"\x93NUMPY\x01\x00".to_vec();
+    npy.extend((header.len() as u16).to_le_bytes());
+    npy.extend(header.bytes());
+    [1f32, 4., 2., 5., 3., 6.]
+        .iter()
+        .for_each(|x| npy.extend(x.to_be_bytes()));
+    let Ok(Pool::<TestObj>::ReadyParams(pool)) = Pool::from_npy(&npy[..]) else {
+        panic!("invalid NPY");
+    };
+    assert_eq!(pool, [[1., 2., 3.], [4., 5., 6.]]);
+    // The shape is larger than the data
+    for shape in ["(1000000000, 1000000000)", "(18446744073709551615, 2)"] {
+        let header = format!("{{'descr': '<f8', 'fortran_order': False, 'shape': {shape}, }}");
+        let mut npy = b"\x93NUMPY\x01\x00".to_vec();
+        npy.extend((header.len() as u16).to_le_bytes());
+        npy.extend(header.bytes());
+        npy.extend(1f64.to_le_bytes());
+        let e = Pool::<TestObj>::from_npy(&npy[..]).err().unwrap();
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
+
+#[test]
+fn evaluate() {
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .solve();
+    let ys = s.evaluate(s.as_best_xs());
+    assert!((ys.eval() - s.get_best_eval()).abs() < 1e-12);
+    // Repaired into the bounds
+    let ys = s.evaluate(&[100., 0., 0., 0.]);
+    assert_eq!(ys.eval(), TestObj.fitness(&[50., 0., 0., 0.]).eval());
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .task(|ctx| ctx.gen == 0)
+        .clamp_to_bounds(false)
+        .solve();
+    let ys = s.evaluate(&[100., 0., 0., 0.]);
+    assert_eq!(ys.eval(), TestObj.fitness(&[100., 0., 0., 0.]).eval());
+}
+
+#[test]
+fn fitness_sharing() {
+    let solve = |sharing: Option<(f64, f64)>| {
+        // Two equal peaks at x = -2 and x = 2
+        let f = Fx::new(&[[-5., 5.]; 2], |&[x, y]: &[f64; 2]| {
+            f64::min((x - 2.) * (x - 2.), (x + 2.) * (x + 2.)) + y * y
+        });
+        let mut builder = Solver::build(Rga::default(), f)
+            .seed(0)
+            .pop_num(60)
+            .task(|ctx| ctx.gen == 100);
+        if let Some((radius, alpha)) = sharing {
+            builder = builder.fitness_sharing(radius, alpha);
+        }
+        let s = builder.solve();
+        let near = |p: f64| s.pool().iter().filter(|xs| (xs[0] - p).abs() < 0.5).count();
+        (near(-2.), near(2.))
+    };
+    let (left, right) = solve(Some((0.1, 1.)));
+    assert!(left > 0 && right > 0, "{left} {right}");
+    // Collapsed to one peak without the sharing
+    let (left, right) = solve(None);
+    assert!(left == 0 || right == 0, "{left} {right}");
+    // Only the methods with the fitness-based selection are affected
+    fn is_shared<S: AlgCfg + Default>() -> bool {
+        let solve = |sharing: bool| {
+            let mut builder = Solver::build(S::default(), TestObj)
+                .seed(0)
+                .pop_num(20)
+                .task(|ctx| ctx.gen == 10);
+            if sharing {
+                builder = builder.fitness_sharing(1., 1.);
+            }
+            builder.solve().pool().to_vec()
+        };
+        solve(true) != solve(false)
+    }
+    assert!(is_shared::<PermGa>());
+    assert!(is_shared::<Abc>());
+    assert!(!is_shared::<Pso>());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn cached() {
+    let solve = |f| {
+        Solver::build(De::default(), f)
+            .seed(0)
+            .task(|ctx| ctx.gen == 20)
+            .solve()
+    };
+    let f = Cached::new(TestObj);
+    let s1 = solve(f.clone());
+    let single = f.evals();
+    assert!(single > 0);
+    let s2 = solve(f.clone());
+    // The identical run is answered by the cache
+    assert!(f.evals() - single < single / 100, "{} {single}", f.evals());
+    assert!(f.hits() >= single);
+    assert_eq!(s1.as_best_xs(), s2.as_best_xs());
+    assert_eq!(s1.get_best_eval(), s2.get_best_eval());
+    // The overridden methods are forwarded
+    struct Joint;
+    impl Bounded for Joint {
+        fn bound(&self) -> &[[f64; 2]] {
+            &[[0., 1.]]
+        }
+    }
+    impl ObjFunc for Joint {
+        type Ys = f64;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            xs[0]
+        }
+        fn fitness_partial(&self, xs: &[f64], is_dominated: &dyn Fn(&[f64]) -> bool) -> f64 {
+            if is_dominated(&[]) {
+                f64::INFINITY
+            } else {
+                self.fitness(xs)
+            }
+        }
+        fn fitness_and_grad(&self, xs: &[f64]) -> (Self::Ys, Option<Vec<f64>>) {
+            (xs[0], Some(vec![1.]))
+        }
+    }
+    let f = Cached::new(Joint);
+    assert_eq!(f.fitness_partial(&[0.5], &|_| true), f64::INFINITY);
+    assert!(f.is_empty());
+    assert_eq!(f.fitness_and_grad(&[0.5]), (0.5, Some(vec![1.])));
+    // Answered by the cache
+    assert_eq!(f.fitness_partial(&[0.5], &|_| true), 0.5);
+    assert_eq!([f.evals(), f.hits()], [2, 1]);
+    // The complete partial evaluation is cached
+    assert_eq!(f.fitness_partial(&[0.25], &|_| false), 0.25);
+    assert_eq!(f.fitness(&[0.25]), 0.25);
+    assert_eq!([f.evals(), f.hits()], [3, 2]);
+    // The discrete variables are snapped
+    struct Int;
+    impl Bounded for Int {
+        fn bound(&self) -> &[[f64; 2]] {
+            &[[-10., 10.]]
+        }
+    }
+    impl ObjFunc for Int {
+        type Ys = f64;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            (xs[0] - 2.4).abs()
+        }
+        fn var_kind(&self, _: usize) -> VarKind {
+            VarKind::Integer
+        }
+    }
+    let f = Cached::new(Int);
+    assert_eq!(f.var_kind(0), VarKind::Integer);
+    let s = Solver::build(De::default(), f)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .solve();
+    assert_eq!(s.as_best_xs(), [2.]);
+}
+
+#[test]
+fn stream_seeds() {
+    use crate::random::StreamSeeds;
+    use alloc::vec::Vec;
+    // Record the draws of the selection and the mutation in each generation
+    #[derive(Default)]
+    struct Draws(Vec<f64>, Vec<f64>);
+    impl<F: ObjFunc> Algorithm<F> for Draws {
+        fn generation(&mut self, _ctx: &mut Ctx<F>, rng: &mut Rng) {
+            self.0.push(rng.rand());
+            // The forks carry the forked mutation streams
+            let mut forks = rng.stream(2);
+            let mutate = forks.iter_mut().map(|rng| rng.mutation().rand());
+            self.1.extend(mutate);
+        }
+    }
+    let draws = |select, mutate| {
+        let seeds = StreamSeeds { select, mutate, ..Default::default() };
+        let mut iter = Solver::build_algorithm(Draws::default(), TestObj)
+            .seed(0)
+            .task(|ctx| ctx.gen == 10)
+            .stream_seeds(seeds)
+            .iter();
+        while iter.step() {}
+        let Draws(select, mutate) = iter.algorithm;
+        (select, mutate)
+    };
+    let (select1, mutate1) = draws(Some(1), Some(0));
+    let (select2, mutate2) = draws(Some(2), Some(0));
+    assert_ne!(select1, select2);
+    assert_eq!(mutate1, mutate2);
+    assert_ne!(mutate1[0], mutate1[1]);
+    let (select3, mutate3) = draws(Some(1), Some(3));
+    assert_eq!(select1, select3);
+    assert_ne!(mutate1, mutate3);
+    // The mutation of RGA
+    let solve = |select, mutate| {
+        let seeds = StreamSeeds { select, mutate, ..Default::default() };
+        Solver::build(Rga::default(), TestObj)
+            .seed(0)
+            .pop_num(40)
+            .task(|ctx| ctx.gen == 20)
+            .stream_seeds(seeds)
+            .solve()
+            .as_best_xs()
+            .to_vec()
+    };
+    assert_eq!(solve(Some(1), Some(0)), solve(Some(1), Some(0)));
+    assert_ne!(solve(Some(1), Some(0)), solve(Some(2), Some(0)));
+    assert_ne!(solve(Some(1), Some(0)), solve(Some(1), Some(3)));
+    // Not separated by default
+    let s = Solver::build(Rga::default(), TestObj)
+        .seed(0)
+        .pop_num(40)
+        .task(|ctx| ctx.gen == 20)
+        .solve();
+    assert_eq!(solve(None, None), s.as_best_xs());
+}
+
+#[test]
+fn nondomination_ranks() {
+    use alloc::vec;
+    let s = Solver::build(Rga::default(), TestMO)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .solve();
+    let ranks = s.nondomination_ranks();
+    assert_eq!(ranks.len(), s.pool().len());
+    // Consistent with the pairwise dominance
+    let ys = s
+        .pool()
+        .iter()
+        .map(|xs| TestMO.fitness(xs))
+        .collect::<alloc::vec::Vec<_>>();
+    for (i, a) in ys.iter().enumerate() {
+        for (j, b) in ys.iter().enumerate() {
+            if a.is_dominated(b) && !b.is_dominated(a) {
+                assert!(ranks[i] < ranks[j], "{i} {j}");
+            }
+        }
+        let first = !ys.iter().any(|b| b.is_dominated(a) && !a.is_dominated(b));
+        assert_eq!(ranks[i] == 0, first);
+    }
+    // A crafted population
+    let pool = vec![
+        vec![1., 3.],
+        vec![2., 3.],
+        vec![2., 2.],
+        vec![3., 3.],
+        vec![3., 1.],
+    ];
+    let s = Solver::build(Rga::default(), TestMO)
+        .task(|ctx| ctx.gen == 0)
+        .init_pool(Pool::ReadyParams(pool))
+        .solve();
+    assert_eq!(s.nondomination_ranks(), [0, 1, 0, 2, 0]);
+}
+
+#[test]
+fn stop_on_param_convergence() {
+    let mut report = alloc::vec::Vec::new();
+    let s = Solver::build(De::default(), TestObj)
+        .seed(0)
+        .stop_on_param_convergence(1e-6)
+        .callback(|ctx| report.push(ctx.max_spread()))
+        .solve();
+    let gen = s.ctx.gen as usize;
+    assert!(gen > 0 && gen < s.ctx.max_gen as usize, "gen: {gen}");
+    // Stopped at the first collapsed generation
+    assert!(report[gen] < 1e-6, "{}", report[gen]);
+    assert!(report[..gen].iter().all(|spread| *spread >= 1e-6));
+    let spread = s.pool().iter().map(|xs| (xs[0] - s.as_best_xs()[0]).abs());
+    assert!(spread.fold(0., f64::max) < 1e-6 * 100.);
+}
+
+#[test]
+fn obj_func_macro() {
+    crate::obj_func! {
+        struct Stateless {}
+        bound = [[-50., 50.]; 4];
+        fn fitness(&self, xs) -> f64 {
+            TestObj.fitness(xs).eval()
+        }
+    }
+    crate::obj_func! {
+        /// A weighted sphere.
+        struct Weighted {
+            w: [f64; 2],
+        }
+        bound = [[-1., 1.], [-2., 2.]];
+        fn fitness(&self, xs) -> WithProduct<f64, f64> {
+            let y = self.w[0] * xs[0] * xs[0] + self.w[1] * xs[1] * xs[1];
+            WithProduct::new(y, y)
+        }
+        fn metrics(&self, xs) -> [f64; 2] {
+            [self.w[0] * xs[0] * xs[0], self.w[1] * xs[1] * xs[1]]
+        }
+    }
+    let s = Solver::build(De::default(), Stateless {})
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .solve();
+    assert_eq!(s.func().dim(), 4);
+    let f = Weighted { w: [1., 2.] };
+    assert_eq!(f.bound(), [[-1., 1.], [-2., 2.]]);
+    assert_eq!(f.fitness(&[1., 1.]).eval(), 3.);
+    assert_eq!(f.metrics(&[1., 1.]), [1., 2.]);
+}
+
+#[test]
+fn feasible_seek() {
+    use core::sync::atomic::{AtomicU64, Ordering};
+    // A thin hypersphere shell
+    let shell = |xs: &[f64]| {
+        let r = xs.iter().map(|x| x * x).sum::<f64>().sqrt();
+        (20. ..20.1).contains(&r)
+    };
+    let draws = |seek| {
+        let calls = AtomicU64::new(0);
+        let filter = alloc::boxed::Box::new(|xs: &[f64]| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            shell(xs)
+        });
+        let max_tries = u64::MAX;
+        let pool = match seek {
+            true => Pool::FeasibleSeek { filter, max_tries },
+            false => Pool::UniformBy { filter, max_tries },
+        };
+        let s = Solver::build(De::default(), TestObj)
+            .seed(0)
+            .pop_num(20)
+            .task(|ctx| ctx.gen == 0)
+            .init_pool(pool)
+            .solve();
+        assert!(s.pool().iter().all(|xs| shell(xs)));
+        calls.load(Ordering::Relaxed)
+    };
+    let (seek, uniform) = (draws(true), draws(false));
+    assert!(seek * 10 < uniform, "{seek} {uniform}");
+}
+
+#[test]
+fn resume_state() {
+    // The fitness values should be serializable
+    struct Sphere;
+    impl Bounded for Sphere {
+        fn bound(&self) -> &[[f64; 2]] {
+            &[[-50., 50.]; 4]
+        }
+    }
+    impl ObjFunc for Sphere {
+        type Ys = f64;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            xs.iter().map(|x| (x - OFFSET).powi(2)).sum()
+        }
+    }
+    let run = |gen| {
+        Solver::build(De::default(), Sphere)
+            .seed(0)
+            .task(move |ctx| ctx.gen == gen)
+            .solve()
+    };
+    let state = run(20).save_state();
+    assert_eq!(state.gen(), 20);
+    #[cfg(feature = "serde")]
+    let state = {
+        let json = serde_json::to_string(&state).unwrap();
+        serde_json::from_str::<SolverState<f64>>(&json).unwrap()
+    };
+    let resumed = Solver::build(De::default(), Sphere)
+        .resume_from(state)
+        .task(|ctx| ctx.gen == 40)
+        .solve();
+    let whole = run(40);
+    assert_eq!(resumed.as_best_xs(), whole.as_best_xs());
+    assert_eq!(resumed.as_best_fit(), whole.as_best_fit());
+    assert_eq!(resumed.pool(), whole.pool());
+    assert_eq!(resumed.eval_count(), whole.eval_count());
+    // The velocities of PSO are not saved
+    let state = Solver::build(Pso::default(), Sphere)
+        .seed(0)
+        .task(|ctx| ctx.gen == 20)
+        .solve()
+        .save_state();
+    let e = Solver::build(Pso::default(), Sphere)
+        .resume_from(state)
+        .task(|ctx| ctx.gen == 40)
+        .try_solve()
+        .err();
+    assert_eq!(e, Some(BuildError::StatefulResume));
+}
+
+#[test]
+fn var_kind() {
+    // One integer variable and three continuous variables
+    struct Mixed;
+    impl Bounded for Mixed {
+        fn bound(&self) -> &[[f64; 2]] {
+            &[[-10., 10.]; 4]
+        }
+    }
+    impl ObjFunc for Mixed {
+        type Ys = f64;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            assert_eq!(xs[0].fract(), 0.);
+            (xs[0] - 2.6).powi(2) + xs[1..].iter().map(|x| x * x).sum::<f64>()
+        }
+        fn var_kind(&self, s: usize) -> VarKind {
+            if s == 0 {
+                VarKind::Integer
+            } else {
+                VarKind::Continuous
+            }
+        }
+    }
+    fn run<A: AlgCfg>(cfg: A) {
+        let s = Solver::build(cfg, Mixed)
+            .seed(0)
+            .task(|ctx| ctx.gen == 100)
+            .solve();
+        assert!(s.pool().iter().all(|xs| xs[0].fract() == 0.));
+        let xs = s.as_best_xs();
+        assert_eq!(xs[0], 3.);
+        assert!(xs[1..].iter().all(|x| x.abs() < 1e-2), "{xs:?}");
+    }
+    run(De::default());
+    run(Pso::default());
+    run(Rga::default());
+    run(Fa::default());
+    // The nearest choice
+    let choice = VarKind::Choice(alloc::vec![1., 2.5, 4.]);
+    assert_eq!(choice.snap(3.1), 2.5);
+    assert_eq!(choice.snap(-3.), 1.);
+    assert_eq!(VarKind::Choice(alloc::vec::Vec::new()).snap(0.3), 0.3);
+}
+
+#[test]
+fn perm_ga() {
+    use core::f64::consts::TAU;
+    // The cities on a circle in a scrambled order, the shortest tour is the
+    // circle itself
+    const ANGLES: [usize; 8] = [3, 6, 0, 5, 1, 7, 2, 4];
+    struct Tour;
+    impl Bounded for Tour {
+        fn bound(&self) -> &[[f64; 2]] {
+            &[[0., 1.]; 8]
+        }
+    }
+    impl ObjFunc for Tour {
+        type Ys = f64;
+        fn fitness(&self, xs: &[f64]) -> Self::Ys {
+            let city = |i: usize| {
+                let t = ANGLES[i] as f64 / 8. * TAU;
+                [t.cos(), t.sin()]
+            };
+            let order = permutation_from_keys(xs);
+            let next = order.iter().cycle().skip(1);
+            (order.iter().zip(next))
+                .map(|(&a, &b)| {
+                    let ([x1, y1], [x2, y2]) = (city(a), city(b));
+                    (x1 - x2).hypot(y1 - y2)
+                })
+                .sum()
+        }
+    }
+    let shortest = 16. * (TAU / 16.).sin();
+    for crossover in [PermCross::Ox, PermCross::Pmx] {
+        for mutation in [PermMutate::Swap, PermMutate::Insert] {
+            let s = Solver::build(PermGa::new().crossover(crossover).mutation(mutation), Tour)
+                .seed(0)
+                .task(|ctx| ctx.gen == 50)
+                .init_pool(Pool::Permutation)
+                .solve();
+            assert!((s.as_best_fit() - shortest).abs() < 1e-9);
+        }
+    }
+    // The keys of the different bounds are not comparable
+    let f = Fx::new(&[[0., 1.], [0., 2.]], |&[a, b]| a - b);
+    let e = Solver::build(PermGa::default(), f)
+        .init_pool(Pool::Permutation)
+        .try_solve()
+        .err();
+    assert_eq!(e, Some(BuildError::UnequalBounds));
+    // The children are still permutations
+    let mut rng = Rng::new(SeedOpt::U64(0));
+    let is_perm = |perm: &[usize]| {
+        let mut perm = perm.to_vec();
+        perm.sort_unstable();
+        perm.into_iter().eq(0..8)
+    };
+    for _ in 0..100 {
+        let mut p1 = (0..8).collect::<alloc::vec::Vec<_>>();
+        let mut p2 = p1.clone();
+        rng.shuffle(p1.as_mut_slice());
+        rng.shuffle(p2.as_mut_slice());
+        for crossover in [PermCross::Ox, PermCross::Pmx] {
+            for mut child in crossover.apply(&mut rng, &p1, &p2) {
+                assert!(is_perm(&child), "{child:?}");
+                PermMutate::Insert.apply(&mut rng, &mut child);
+                assert!(is_perm(&child), "{child:?}");
+            }
+        }
+    }
+}